@@ -0,0 +1,66 @@
+use std::fmt;
+
+use reqwest::header::HeaderMap;
+
+use crate::client::{DEMO_HOST, PRO_HOST};
+use crate::key_pool::KeyPool;
+
+/// API key issued by CoinGecko
+///
+/// Each kind of key only authenticates against its own host, which clients default to when given a key:
+///
+/// | Key            | Host                                                | Constructor                          |
+/// |----------------|-----------------------------------------------------|--------------------------------------|
+/// | none           | `PUBLIC_HOST`, <https://api.coingecko.com/api/v3>   | `CoinGeckoClient::default`           |
+/// | `ApiKey::Demo` | `DEMO_HOST`, <https://api.coingecko.com/api/v3>     | `CoinGeckoClient::new_with_demo_key` |
+/// | `ApiKey::Pro`  | `PRO_HOST`, <https://pro-api.coingecko.com/api/v3>  | `CoinGeckoClient::new_with_pro_key`  |
+///
+/// Use `CoinGeckoClient::new_with_key` or `CoinGeckoClientBuilder::host` to send a key to another host, such as a proxy.
+#[derive(Clone, PartialEq, Eq)]
+pub enum ApiKey {
+    /// Key of a paid plan, sent as the `x-cg-pro-api-key` header
+    Pro(String),
+    /// Free Demo key, sent as the `x-cg-demo-api-key` header
+    Demo(String),
+}
+
+impl ApiKey {
+    /// Host the key authenticates against
+    pub(crate) fn host(&self) -> &'static str {
+        match self {
+            ApiKey::Pro(_) => PRO_HOST,
+            ApiKey::Demo(_) => DEMO_HOST,
+        }
+    }
+
+    /// Name and value of the header carrying the key
+    pub(crate) fn header(&self) -> (&'static str, &str) {
+        match self {
+            ApiKey::Pro(api_key) => ("x-cg-pro-api-key", api_key),
+            ApiKey::Demo(api_key) => ("x-cg-demo-api-key", api_key),
+        }
+    }
+}
+
+/// Hides the key itself so it does not end up in logs
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKey::Pro(_) => f.write_str("Pro(..)"),
+            ApiKey::Demo(_) => f.write_str("Demo(..)"),
+        }
+    }
+}
+
+/// A `***` for each API key a client sends, showing how many keys it has without revealing them
+pub(crate) fn redacted_keys(headers: &HeaderMap, pool: Option<&KeyPool>) -> Vec<&'static str> {
+    let count = match pool {
+        Some(pool) => pool.len(),
+        None => ["x-cg-pro-api-key", "x-cg-demo-api-key"]
+            .iter()
+            .filter(|name| headers.contains_key(**name))
+            .count(),
+    };
+
+    vec!["***"; count]
+}
@@ -0,0 +1,1293 @@
+#![allow(clippy::too_many_arguments)]
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use reqwest::blocking::Response;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
+use crate::api_key::{redacted_keys, ApiKey};
+use crate::builder::CoinGeckoClientBuilder;
+#[cfg(feature = "cache")]
+use crate::cache::{EtagCache, HistoryCache};
+use crate::circuit::CircuitBreaker;
+use crate::client::{sort_by_volume, DEFAULT_PRICE_CHUNK_SIZE, EXCHANGES_PER_PAGE};
+use crate::endpoints;
+use crate::error::CoinGeckoError;
+use crate::key_pool::KeyPool;
+use crate::rate_limit::RateLimit;
+use crate::reference_cache::ReferenceCache;
+use crate::retry::{retry_after, RetryPolicy};
+use crate::throttle::Throttle;
+use crate::transport::check_response_size;
+
+use crate::params::{
+    check_positive, check_range, single_filter, AssetPlatformFilter, AssetPlatformId, ChartDays,
+    CoinId, CompaniesCoinId, DerivativeExchangeOrder, DerivativesIncludeTickers, ExchangeStatus,
+    IncludeTokens, Interval, MarketsFilter, MarketsOrder, NftsOrder, OhlcDays, OhlcInterval,
+    Precision, PriceChangePercentage, TickersOrder, TopCoins, TopGainersLosersDuration, VsCurrency,
+};
+
+use crate::response::{
+    asset_platforms::AssetPlatform,
+    coins::{
+        Category, CategoryId, CirculatingSupplyChart, CoinsItem, CoinsListItem, CoinsMarketItem,
+        Contract, History, MarketChart, NewCoin, Ohlc, TopGainersLosers, TotalSupplyChart,
+    },
+    common::{StatusUpdates, Tickers},
+    companies::CompaniesPublicTreasury,
+    derivatives::{Derivative, DerivativeExchangeDetail, DerivativeExchangeId},
+    events::Events,
+    events::{EventCountries, EventTypes},
+    exchange_rates::ExchangeRates,
+    exchanges::VolumeChartData,
+    exchanges::{Exchange, ExchangeId},
+    finance::{FinancePlatform, FinanceProduct},
+    global::{Global, GlobalDefi, GlobalMarketCapChart},
+    indexes::Index,
+    indexes::{IndexId, MarketIndex},
+    nfts::{Nft, NftId, NftMarketChart, NftMarketItem},
+    ping::SimplePing,
+    search::SearchResults,
+    simple::{Price, SupportedVsCurrencies},
+    trending::Trending,
+};
+
+/// Blocking CoinGecko client
+///
+/// Mirrors [`crate::CoinGeckoClient`] with the same method names, without requiring an async runtime.
+/// Like `reqwest::blocking::Client`, it must not be created or used from within an async runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// use coingecko::blocking::CoinGeckoClient;
+/// let client = CoinGeckoClient::default();
+///
+/// client.price(&["bitcoin", "ethereum"], &["usd"], true, true, true, true, None);
+/// ```
+#[derive(Clone)]
+pub struct CoinGeckoClient {
+    pub(crate) host: String,
+    pub(crate) headers: HeaderMap,
+    pub(crate) keys: Option<Arc<KeyPool>>,
+    pub(crate) client: reqwest::blocking::Client,
+    pub(crate) rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) max_response_bytes: Option<u64>,
+    pub(crate) strict_currencies: bool,
+    pub(crate) locale: Option<String>,
+    pub(crate) supported_vs_currencies: Arc<Mutex<Option<SupportedVsCurrencies>>>,
+    pub(crate) reference_cache: Option<Arc<ReferenceCache>>,
+    pub(crate) throttle: Option<Arc<Throttle>>,
+    pub(crate) circuit: Option<Arc<CircuitBreaker>>,
+    #[cfg(feature = "cache")]
+    pub(crate) cache: Option<Arc<EtagCache>>,
+    #[cfg(feature = "cache")]
+    pub(crate) history_cache: Option<Arc<HistoryCache>>,
+}
+
+/// Shows the configuration of the client with every API key redacted as `***`
+impl fmt::Debug for CoinGeckoClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoinGeckoClient")
+            .field("host", &self.host)
+            .field(
+                "api_keys",
+                &redacted_keys(&self.headers, self.keys.as_deref()),
+            )
+            .field("retry", &self.retry)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("strict_currencies", &self.strict_currencies)
+            .field("locale", &self.locale)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates a new blocking CoinGeckoClient with host `PUBLIC_HOST`
+impl Default for CoinGeckoClient {
+    fn default() -> Self {
+        CoinGeckoClient::builder().build_blocking()
+    }
+}
+
+impl CoinGeckoClient {
+    /// Creates a new blocking CoinGeckoClient client with a custom host url
+    pub fn new(host: &'static str) -> Self {
+        CoinGeckoClient::builder().host(host).build_blocking()
+    }
+
+    /// Creates a new blocking CoinGeckoClient authenticated with a free Demo API key against `DEMO_HOST`
+    pub fn new_with_demo_key(api_key: impl Into<String>) -> Self {
+        CoinGeckoClient::builder()
+            .demo_key(api_key)
+            .build_blocking()
+    }
+
+    /// Creates a new blocking CoinGeckoClient authenticated with a Pro API key against `PRO_HOST`
+    pub fn new_with_pro_key(api_key: impl Into<String>) -> Self {
+        CoinGeckoClient::builder().pro_key(api_key).build_blocking()
+    }
+
+    /// Creates a new blocking CoinGeckoClient with a custom host url authenticated with an API key
+    pub fn new_with_key(host: impl Into<String>, api_key: ApiKey) -> Self {
+        CoinGeckoClient::builder()
+            .host(host)
+            .api_key(api_key)
+            .build_blocking()
+    }
+
+    /// Creates a new blocking CoinGeckoClient with a custom host url sending requests with a pre-built `reqwest::blocking::Client`
+    pub fn with_client(host: impl Into<String>, client: reqwest::blocking::Client) -> Self {
+        CoinGeckoClient::builder()
+            .host(host)
+            .blocking_client(client)
+            .build_blocking()
+    }
+
+    /// Creates a CoinGeckoClientBuilder, finish it with `build_blocking` to get a blocking client
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::blocking::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build_blocking();
+    /// ```
+    pub fn builder() -> CoinGeckoClientBuilder {
+        CoinGeckoClientBuilder::new()
+    }
+
+    /// Rate limit state reported on the most recent response, shared between clones of this client
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
+    /// Default language set with `CoinGeckoClientBuilder::locale`
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Escape hatch to call any endpoint, e.g. one not yet covered by this crate, with the host, API key,
+    /// rate limit and retry handling of the client
+    pub fn get_json<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T, CoinGeckoError> {
+        self.get(&endpoints::raw(endpoint, params))
+    }
+
+    /// Whether requests are authenticated with a Pro API key
+    fn is_pro(&self) -> bool {
+        self.headers.contains_key("x-cg-pro-api-key")
+            || self.keys.as_ref().is_some_and(|keys| keys.is_pro())
+    }
+
+    /// Full url of an endpoint, never containing the API key so it is safe to log
+    fn get_url(&self, endpoint: &str) -> String {
+        format!("{host}/{ep}", host = self.host, ep = endpoint)
+    }
+
+    pub(crate) fn get<R: DeserializeOwned>(&self, endpoint: &str) -> Result<R, CoinGeckoError> {
+        self.with_circuit(|| {
+            let (headers, body) = self.send(endpoint)?;
+            CoinGeckoError::decode(&headers, &body, &self.get_url(endpoint))
+        })
+    }
+
+    /// Like `get` for responses that never change, kept in the history cache if one is set
+    ///
+    /// Cached responses are answered even while the circuit breaker is open.
+    pub(crate) fn get_immutable<R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> Result<R, CoinGeckoError> {
+        #[cfg(feature = "cache")]
+        if let Some(history) = &self.history_cache {
+            let url = self.get_url(endpoint);
+            if let Some(cached) = history.get(&url) {
+                return Ok(cached);
+            }
+
+            return self.with_circuit(|| {
+                let (headers, body) = self.send(endpoint)?;
+                let res = CoinGeckoError::decode(&headers, &body, &url)?;
+                history.store(&url, &body);
+                Ok(res)
+            });
+        }
+
+        self.get(endpoint)
+    }
+
+    /// Runs a request through the circuit breaker if one is configured
+    fn with_circuit<T>(
+        &self,
+        request: impl FnOnce() -> Result<T, CoinGeckoError>,
+    ) -> Result<T, CoinGeckoError> {
+        let circuit = match &self.circuit {
+            Some(circuit) => circuit,
+            None => return request(),
+        };
+
+        circuit.check()?;
+        let res = request();
+        circuit.record(res.as_ref().err());
+        res
+    }
+
+    /// Headers and body of a successful response
+    fn send(&self, endpoint: &str) -> Result<(HeaderMap, Vec<u8>), CoinGeckoError> {
+        let url = self.get_url(endpoint);
+
+        let mut headers = self.headers.clone();
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            cache.revalidate(&url, &mut headers);
+        }
+
+        let mut attempt = 0;
+        let mut waited = Duration::ZERO;
+        let mut rotations = 0;
+        let response = loop {
+            let key = self.keys.as_ref().map(|keys| keys.select(&mut headers));
+
+            if let Some(throttle) = &self.throttle {
+                std::thread::sleep(throttle.reserve());
+            }
+
+            #[cfg(feature = "tracing")]
+            let started = std::time::Instant::now();
+
+            let response = self
+                .client
+                .get(&url)
+                .headers(headers.clone())
+                .send()
+                .map_err(|e| CoinGeckoError::from(e).with_url(&url))?;
+
+            // the endpoint never contains the API key, which is only sent as a header
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                endpoint,
+                status = response.status().as_u16(),
+                attempt,
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "received CoinGecko response"
+            );
+
+            if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                *self.rate_limit.lock().unwrap() = Some(rate_limit);
+            }
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                break response;
+            }
+
+            // try each other key once before falling back to the retry policy
+            if let (Some(keys), Some(key)) = (&self.keys, key) {
+                if keys.rate_limited(key, retry_after(response.headers()))
+                    && rotations + 1 < keys.len()
+                {
+                    rotations += 1;
+                    continue;
+                }
+            }
+
+            match self
+                .retry
+                .delay(attempt, waited, retry_after(response.headers()))
+            {
+                Some(delay) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        endpoint,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "rate limited by CoinGecko, retrying"
+                    );
+
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                    waited += delay;
+                }
+                None => break response,
+            }
+        };
+
+        let status = response.status();
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            if status == StatusCode::NOT_MODIFIED {
+                if let Some(cached) = cache.get(&url) {
+                    return Ok(cached);
+                }
+            }
+        }
+
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let body = self.read_body(response, &url)?;
+            let body = String::from_utf8_lossy(&body).into_owned();
+            return Err(CoinGeckoError::from_status(status, &headers, body, &url));
+        }
+
+        let headers = response.headers().clone();
+        let body = self.read_body(response, &url)?;
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            cache.store(&url, &headers, &body);
+        }
+
+        Ok((headers, body))
+    }
+
+    /// Reads a response body, failing as soon as it grows over `max_response_bytes`
+    fn read_body(&self, mut response: Response, url: &str) -> Result<Vec<u8>, CoinGeckoError> {
+        let limit = match self.max_response_bytes {
+            Some(limit) => limit,
+            None => {
+                let body = response
+                    .bytes()
+                    .map_err(|e| CoinGeckoError::from(e).with_url(url))?;
+                return Ok(body.to_vec());
+            }
+        };
+
+        check_response_size(response.content_length().unwrap_or(0), Some(limit))?;
+
+        let mut body = LimitedBody {
+            body: Vec::new(),
+            limit,
+            exceeded: false,
+        };
+        match response.copy_to(&mut body) {
+            Ok(_) => Ok(body.body),
+            Err(_) if body.exceeded => Err(CoinGeckoError::ResponseTooLarge { limit }),
+            Err(e) => Err(CoinGeckoError::from(e).with_url(url)),
+        }
+    }
+
+    /// Check API server status
+    pub fn ping(&self) -> Result<SimplePing, CoinGeckoError> {
+        self.get("/ping")
+    }
+
+    /// Whether the API server answers `ping`, any error is reported as `false`
+    pub fn is_up(&self) -> bool {
+        self.ping().is_ok()
+    }
+
+    /// Get the current price of any cryptocurrencies in any other supported currencies that you need
+    pub fn price<Id: AsRef<str>, Curr: AsRef<str>>(
+        &self,
+        ids: &[Id],
+        vs_currencies: &[Curr],
+        include_market_cap: bool,
+        include_24hr_vol: bool,
+        include_24hr_change: bool,
+        include_last_updated_at: bool,
+        precision: Option<Precision>,
+    ) -> Result<HashMap<String, Price>, CoinGeckoError> {
+        self.check_currencies(vs_currencies)?;
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::price(
+            ids,
+            vs_currencies,
+            include_market_cap,
+            include_24hr_vol,
+            include_24hr_change,
+            include_last_updated_at,
+            precision.as_deref(),
+        ))
+    }
+
+    /// Same as `price`, with the prices keyed by [`CoinId`]
+    pub fn price_by_id<Id: AsRef<str>, Curr: AsRef<str>>(
+        &self,
+        ids: &[Id],
+        vs_currencies: &[Curr],
+        include_market_cap: bool,
+        include_24hr_vol: bool,
+        include_24hr_change: bool,
+        include_last_updated_at: bool,
+        precision: Option<Precision>,
+    ) -> Result<HashMap<CoinId, Price>, CoinGeckoError> {
+        let prices = self.price(
+            ids,
+            vs_currencies,
+            include_market_cap,
+            include_24hr_vol,
+            include_24hr_change,
+            include_last_updated_at,
+            precision,
+        )?;
+
+        Ok(prices
+            .into_iter()
+            .map(|(id, price)| (CoinId::from(id), price))
+            .collect())
+    }
+
+    /// Get the current price of any number of cryptocurrencies, split into several requests to stay within url length limits
+    pub fn prices_chunked<Id: AsRef<str>, Curr: AsRef<str>>(
+        &self,
+        ids: &[Id],
+        vs_currencies: &[Curr],
+        include_market_cap: bool,
+        include_24hr_vol: bool,
+        include_24hr_change: bool,
+        include_last_updated_at: bool,
+        precision: Option<Precision>,
+        chunk_size: Option<usize>,
+    ) -> Result<HashMap<String, Price>, CoinGeckoError> {
+        self.check_currencies(vs_currencies)?;
+        let precision = precision.map(Precision::param).transpose()?;
+
+        let mut prices = HashMap::new();
+
+        for ids in endpoints::chunk_ids(ids, chunk_size.unwrap_or(DEFAULT_PRICE_CHUNK_SIZE)) {
+            prices.extend(self.get::<HashMap<String, Price>>(&endpoints::price(
+                &ids,
+                vs_currencies,
+                include_market_cap,
+                include_24hr_vol,
+                include_24hr_change,
+                include_last_updated_at,
+                precision.as_deref(),
+            ))?);
+        }
+
+        Ok(prices)
+    }
+
+    /// Get current price of tokens (using contract addresses) for a given platform in any other currency that you need
+    pub fn token_price<Addr: AsRef<str>, Curr: AsRef<str>>(
+        &self,
+        id: impl Into<AssetPlatformId>,
+        contract_addresses: &[Addr],
+        vs_currencies: &[Curr],
+        include_market_cap: bool,
+        include_24hr_vol: bool,
+        include_24hr_change: bool,
+        include_last_updated_at: bool,
+        precision: Option<Precision>,
+        include_tokens: Option<IncludeTokens>,
+    ) -> Result<HashMap<String, Price>, CoinGeckoError> {
+        self.check_currencies(vs_currencies)?;
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::token_price(
+            id.into().as_ref(),
+            contract_addresses,
+            vs_currencies,
+            include_market_cap,
+            include_24hr_vol,
+            include_24hr_change,
+            include_last_updated_at,
+            precision.as_deref(),
+            include_tokens,
+        ))
+    }
+
+    /// Get list of supported_vs_currencies
+    pub fn supported_vs_currencies(&self) -> Result<SupportedVsCurrencies, CoinGeckoError> {
+        let cache = self.reference_cache.as_ref();
+        if let Some(cached) = cache.and_then(|cache| cache.supported_vs_currencies.get()) {
+            return Ok(cached);
+        }
+
+        let supported: SupportedVsCurrencies = self.get("/simple/supported_vs_currencies")?;
+        if let Some(cache) = cache {
+            cache.supported_vs_currencies.set(supported.clone());
+        }
+
+        Ok(supported)
+    }
+
+    /// Checks that CoinGecko supports every currency, failing with `CoinGeckoError::UnsupportedCurrencies` listing the others
+    pub fn validate_currencies<Curr: AsRef<str>>(
+        &self,
+        currencies: &[Curr],
+    ) -> Result<(), CoinGeckoError> {
+        let cached = self.supported_vs_currencies.lock().unwrap().clone();
+        let supported = match cached {
+            Some(supported) => supported,
+            None => {
+                let supported = self.supported_vs_currencies()?;
+                *self.supported_vs_currencies.lock().unwrap() = Some(supported.clone());
+                supported
+            }
+        };
+
+        let unsupported: Vec<_> = currencies
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|currency| {
+                !supported
+                    .iter()
+                    .any(|supported| supported.eq_ignore_ascii_case(currency))
+            })
+            .map(String::from)
+            .collect();
+
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(CoinGeckoError::UnsupportedCurrencies(unsupported))
+        }
+    }
+
+    /// Validates the currencies if the client was built with `strict_currencies`
+    pub(crate) fn check_currencies<Curr: AsRef<str>>(
+        &self,
+        currencies: &[Curr],
+    ) -> Result<(), CoinGeckoError> {
+        if self.strict_currencies {
+            self.validate_currencies(currencies)?;
+        }
+        Ok(())
+    }
+
+    /// List all supported coins id, name and symbol (no pagination required)
+    pub fn coins_list(&self, include_platform: bool) -> Result<Vec<CoinsListItem>, CoinGeckoError> {
+        self.get(&endpoints::coins_list(include_platform))
+    }
+
+    /// List the 200 coins most recently listed on CoinGecko (Pro API only)
+    pub fn coins_list_new(&self) -> Result<Vec<NewCoin>, CoinGeckoError> {
+        self.get("/coins/list/new")
+    }
+
+    /// Get the top 30 coins with the largest price gain and loss over a duration (Pro API only)
+    pub fn top_gainers_losers(
+        &self,
+        vs_currency: impl Into<VsCurrency>,
+        duration: TopGainersLosersDuration,
+        top_coins: Option<TopCoins>,
+    ) -> Result<TopGainersLosers, CoinGeckoError> {
+        self.get(&endpoints::top_gainers_losers(
+            vs_currency.into().as_ref(),
+            duration,
+            top_coins,
+        ))
+    }
+
+    /// Ids of all coins with the given symbol, matched case-insensitively
+    pub fn resolve_coin_id(&self, symbol: &str) -> Result<Vec<CoinId>, CoinGeckoError> {
+        let coins = self.coins_list(false)?;
+
+        Ok(coins
+            .into_iter()
+            .filter(|coin| coin.symbol.eq_ignore_ascii_case(symbol))
+            .map(|coin| CoinId::from(coin.id))
+            .collect())
+    }
+
+    /// List all supported coins price, market cap, volume, and market related data
+    pub fn coins_markets<Id: AsRef<str>>(
+        &self,
+        vs_currency: impl Into<VsCurrency>,
+        ids: &[Id],
+        category: Option<&str>,
+        order: MarketsOrder,
+        per_page: i64,
+        page: i64,
+        sparkline: bool,
+        price_change_percentage: &[PriceChangePercentage],
+    ) -> Result<Vec<CoinsMarketItem>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        let filters = MarketsFilter::from_args(ids, category);
+        let filter = single_filter(&filters)?;
+
+        let vs_currency = vs_currency.into();
+        self.check_currencies(&[&vs_currency])?;
+
+        self.get(&endpoints::coins_markets(
+            vs_currency.as_ref(),
+            filter,
+            order,
+            per_page,
+            page,
+            sparkline,
+            price_change_percentage,
+            self.locale.as_deref(),
+        ))
+    }
+
+    /// Get current data (name, price, market, ... including exchange tickers) for a coin
+    pub fn coin(
+        &self,
+        id: impl Into<CoinId>,
+        localization: bool,
+        tickers: bool,
+        market_data: bool,
+        community_data: bool,
+        developer_data: bool,
+        sparkline: bool,
+    ) -> Result<CoinsItem, CoinGeckoError> {
+        self.get(&endpoints::coin(
+            id.into().as_ref(),
+            localization,
+            tickers,
+            market_data,
+            community_data,
+            developer_data,
+            sparkline,
+        ))
+    }
+
+    /// Get coin tickers (paginated to 100 items)
+    pub fn coin_tickers<Ex: AsRef<str>>(
+        &self,
+        id: impl Into<CoinId>,
+        exchange_ids: Option<&[Ex]>,
+        include_exchange_logo: bool,
+        page: i64,
+        order: TickersOrder,
+        depth: bool,
+    ) -> Result<Tickers, CoinGeckoError> {
+        check_positive("page", page)?;
+
+        self.get(&endpoints::coin_tickers(
+            id.into().as_ref(),
+            exchange_ids,
+            include_exchange_logo,
+            page,
+            order,
+            depth,
+        ))
+    }
+
+    /// Get historical data (name, price, market, stats) at a given date for a coin
+    pub fn coin_history(
+        &self,
+        id: impl Into<CoinId>,
+        date: NaiveDate,
+        localization: bool,
+    ) -> Result<History, CoinGeckoError> {
+        let endpoint = endpoints::coin_history(id.into().as_ref(), date, localization);
+
+        // the snapshot of a day is final once the day is over
+        if date < Utc::now().date_naive() {
+            self.get_immutable(&endpoint)
+        } else {
+            self.get(&endpoint)
+        }
+    }
+
+    /// Get historical market data include price, market cap, and 24h volume (granularity auto)
+    pub fn coin_market_chart(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        days: impl Into<ChartDays>,
+        use_daily_interval: bool,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::coin_market_chart(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            days,
+            use_daily_interval,
+            precision.as_deref(),
+        ))
+    }
+
+    /// Get historical market data include price, market cap, and 24h volume with an explicit granularity
+    pub fn coin_market_chart_with_interval(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        days: impl Into<ChartDays>,
+        interval: Interval,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::coin_market_chart_with_interval(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            days,
+            interval,
+            precision.as_deref(),
+        ))
+    }
+
+    /// Get historical market data include price, market cap, and 24h volume within a range of timestamp
+    pub fn coin_market_chart_range(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        interval: Option<Interval>,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        check_range(from, to)?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::coin_market_chart_range(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            from,
+            to,
+            interval,
+            precision.as_deref(),
+        ))
+    }
+
+    /// Get the historical circulating supply of a coin (Pro API only)
+    pub fn coin_circulating_supply_chart(
+        &self,
+        id: impl Into<CoinId>,
+        days: impl Into<ChartDays>,
+    ) -> Result<CirculatingSupplyChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        self.get(&endpoints::coin_circulating_supply_chart(
+            id.into().as_ref(),
+            days,
+        ))
+    }
+
+    /// Get the historical total supply of a coin (Pro API only)
+    pub fn coin_total_supply_chart(
+        &self,
+        id: impl Into<CoinId>,
+        days: impl Into<ChartDays>,
+    ) -> Result<TotalSupplyChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        self.get(&endpoints::coin_total_supply_chart(
+            id.into().as_ref(),
+            days,
+        ))
+    }
+
+    /// Get coin's OHLC
+    pub fn coin_ohlc(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        days: OhlcDays,
+    ) -> Result<Vec<Ohlc>, CoinGeckoError> {
+        if !self.is_pro() {
+            days.check_free_tier()?;
+        }
+
+        self.get(&endpoints::coin_ohlc(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            days,
+        ))
+    }
+
+    /// Get coin's OHLC between two dates (Pro API only)
+    pub fn coin_ohlc_range(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        interval: OhlcInterval,
+    ) -> Result<Vec<Ohlc>, CoinGeckoError> {
+        check_range(from, to)?;
+        interval.check_span(from, to)?;
+
+        self.get(&endpoints::coin_ohlc_range(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            from,
+            to,
+            interval,
+        ))
+    }
+
+    /// Get coin info from contract address
+    pub fn contract(
+        &self,
+        id: impl Into<AssetPlatformId>,
+        contract_address: &str,
+    ) -> Result<Contract, CoinGeckoError> {
+        self.get(&endpoints::contract(id.into().as_ref(), contract_address))
+    }
+
+    /// Get historical market data include price, market cap, and 24h volume (granularity auto)
+    pub fn contract_market_chart(
+        &self,
+        id: impl Into<AssetPlatformId>,
+        contract_address: &str,
+        vs_currency: impl Into<VsCurrency>,
+        days: impl Into<ChartDays>,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::contract_market_chart(
+            id.into().as_ref(),
+            contract_address,
+            vs_currency.into().as_ref(),
+            days,
+            precision.as_deref(),
+        ))
+    }
+
+    /// Get historical market data include price, market cap, and 24h volume within a range of timestamp
+    pub fn contract_market_chart_range(
+        &self,
+        id: impl Into<AssetPlatformId>,
+        contract_address: &str,
+        vs_currency: impl Into<VsCurrency>,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        interval: Option<Interval>,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        check_range(from, to)?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::contract_market_chart_range(
+            id.into().as_ref(),
+            contract_address,
+            vs_currency.into().as_ref(),
+            from,
+            to,
+            interval,
+            precision.as_deref(),
+        ))
+    }
+
+    /// List all asset platforms (Blockchain networks)
+    pub fn asset_platforms(&self) -> Result<Vec<AssetPlatform>, CoinGeckoError> {
+        self.asset_platforms_filtered(None)
+    }
+
+    /// List asset platforms (Blockchain networks), e.g. only the ones supporting NFTs
+    pub fn asset_platforms_filtered(
+        &self,
+        filter: Option<AssetPlatformFilter>,
+    ) -> Result<Vec<AssetPlatform>, CoinGeckoError> {
+        self.get(&endpoints::asset_platforms(filter))
+    }
+
+    /// List all categories
+    pub fn categories_list(&self) -> Result<Vec<CategoryId>, CoinGeckoError> {
+        self.get("/coins/categories/list")
+    }
+
+    /// List all categories with market data
+    pub fn categories(&self) -> Result<Vec<Category>, CoinGeckoError> {
+        self.get("/coins/categories")
+    }
+
+    /// List all exchanges
+    ///
+    /// Exchanges are ranked by `trust_score_rank`, the endpoint takes no parameter to order them differently,
+    /// see `exchanges_by_volume` for a ranking by volume.
+    pub fn exchanges(&self, per_page: i64, page: i64) -> Result<Vec<Exchange>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::exchanges(per_page, page))
+    }
+
+    /// List all exchanges ranked by `trade_volume24_h_btc`, the highest first
+    ///
+    /// Fetches every page and sorts them, see `CoinGeckoClient::exchanges_by_volume`.
+    pub fn exchanges_by_volume(&self) -> Result<Vec<Exchange>, CoinGeckoError> {
+        let mut exchanges = Vec::new();
+        for page in 1.. {
+            let items = self.exchanges(EXCHANGES_PER_PAGE, page)?;
+            let last = items.len() < EXCHANGES_PER_PAGE as usize;
+            exchanges.extend(items);
+            if last {
+                break;
+            }
+        }
+
+        sort_by_volume(&mut exchanges);
+        Ok(exchanges)
+    }
+
+    /// List all supported markets id and name (no pagination required)
+    pub fn exchanges_list(&self) -> Result<Vec<ExchangeId>, CoinGeckoError> {
+        self.exchanges_list_filtered(None)
+    }
+
+    /// List the id and name of the markets with a status, e.g. only the active ones
+    pub fn exchanges_list_filtered(
+        &self,
+        status: Option<ExchangeStatus>,
+    ) -> Result<Vec<ExchangeId>, CoinGeckoError> {
+        self.get(&endpoints::exchanges_list(status))
+    }
+
+    /// Get exchange volume in BTC and top 100 tickers only
+    pub fn exchange(&self, id: &str) -> Result<Exchange, CoinGeckoError> {
+        self.get(&endpoints::exchange(id))
+    }
+
+    /// Get exchange tickers (paginated)
+    pub fn exchange_tickers<CoinId: AsRef<str>>(
+        &self,
+        id: &str,
+        coin_ids: Option<&[CoinId]>,
+        include_exchange_logo: bool,
+        page: i64,
+        order: TickersOrder,
+        depth: bool,
+    ) -> Result<Tickers, CoinGeckoError> {
+        check_positive("page", page)?;
+
+        self.get(&endpoints::exchange_tickers(
+            id,
+            coin_ids,
+            include_exchange_logo,
+            page,
+            order,
+            depth,
+        ))
+    }
+
+    /// Get status updates for a given exchange
+    pub fn exchange_status_updates(
+        &self,
+        id: &str,
+        per_page: i64,
+        page: i64,
+    ) -> Result<StatusUpdates, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::exchange_status_updates(id, per_page, page))
+    }
+
+    /// Get volume_chart data for a given exchange
+    pub fn exchange_volume_chart(
+        &self,
+        id: &str,
+        days: i64,
+    ) -> Result<Vec<VolumeChartData>, CoinGeckoError> {
+        check_positive("days", days)?;
+
+        self.get(&endpoints::exchange_volume_chart(id, days))
+    }
+
+    /// Get volume_chart data for a given exchange within a range of timestamp (Pro API only)
+    pub fn exchange_volume_chart_range(
+        &self,
+        id: &str,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<VolumeChartData>, CoinGeckoError> {
+        check_range(from, to)?;
+
+        self.get(&endpoints::exchange_volume_chart_range(id, from, to))
+    }
+
+    /// List all finance platforms
+    pub fn finance_platforms(
+        &self,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<FinancePlatform>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::finance_platforms(per_page, page))
+    }
+
+    /// List all finance products
+    pub fn finance_products(
+        &self,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<FinanceProduct>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::finance_products(per_page, page))
+    }
+
+    /// List all market indexes
+    pub fn indexes(&self, per_page: i64, page: i64) -> Result<Vec<Index>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::indexes(per_page, page))
+    }
+
+    /// Get market index by market id and index id
+    pub fn indexes_market_id(
+        &self,
+        market_id: &str,
+        id: &str,
+    ) -> Result<MarketIndex, CoinGeckoError> {
+        self.get(&endpoints::indexes_market_id(market_id, id))
+    }
+
+    /// List market indexes id and name
+    pub fn indexes_list(&self) -> Result<Vec<IndexId>, CoinGeckoError> {
+        self.get("/indexes/list")
+    }
+
+    /// List all derivative tickers
+    pub fn derivatives(
+        &self,
+        include_tickers: Option<DerivativesIncludeTickers>,
+    ) -> Result<Vec<Derivative>, CoinGeckoError> {
+        self.get(&endpoints::derivatives(include_tickers))
+    }
+
+    /// List all derivative exchanges
+    pub fn derivative_exchanges(
+        &self,
+        order: DerivativeExchangeOrder,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<Derivative>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::derivative_exchanges(order, per_page, page))
+    }
+
+    /// Show derivative exchange data
+    pub fn derivatives_exchange(
+        &self,
+        id: &str,
+        include_tickers: Option<DerivativesIncludeTickers>,
+    ) -> Result<DerivativeExchangeDetail, CoinGeckoError> {
+        self.get(&endpoints::derivatives_exchange(id, include_tickers))
+    }
+
+    /// List all derivative exchanges name and identifier
+    pub fn derivative_exchanges_list(&self) -> Result<Vec<DerivativeExchangeId>, CoinGeckoError> {
+        self.get("/derivatives/exchanges/list")
+    }
+
+    /// List all supported NFT ids with their contract address and asset platform
+    pub fn nfts_list(
+        &self,
+        order: Option<NftsOrder>,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<NftId>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::nfts_list(order, per_page, page))
+    }
+
+    /// List NFT collections with their floor price, market cap and 24h volume, ranked by `order` (Pro API only)
+    pub fn nfts_markets(
+        &self,
+        asset_platform_id: Option<AssetPlatformId>,
+        order: Option<NftsOrder>,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<NftMarketItem>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::nfts_markets(
+            asset_platform_id.as_ref().map(AsRef::as_ref),
+            order,
+            per_page,
+            page,
+        ))
+    }
+
+    /// Get current data (name, floor price, 24h volume, etc.) for an NFT collection, floor prices are given in the native currency and usd
+    pub fn nft(&self, id: &str) -> Result<Nft, CoinGeckoError> {
+        self.get(&endpoints::nft(id))
+    }
+
+    /// Get current data (name, floor price, 24h volume, etc.) for an NFT collection by its contract address
+    pub fn nft_contract(
+        &self,
+        asset_platform_id: impl Into<AssetPlatformId>,
+        contract_address: &str,
+    ) -> Result<Nft, CoinGeckoError> {
+        self.get(&endpoints::nft_contract(
+            asset_platform_id.into().as_ref(),
+            contract_address,
+        ))
+    }
+
+    /// Get historical floor price, market cap and 24h volume of an NFT collection in the native currency and usd (Pro API only)
+    pub fn nft_market_chart(&self, id: &str, days: i64) -> Result<NftMarketChart, CoinGeckoError> {
+        check_positive("days", days)?;
+
+        self.get(&endpoints::nft_market_chart(id, days))
+    }
+
+    /// List all status_updates with data (description, category, created_at, user, user_title and pin)
+    pub fn status_updates(
+        &self,
+        category: Option<&str>,
+        project_type: Option<&str>,
+        per_page: i64,
+        page: i64,
+    ) -> Result<StatusUpdates, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::status_updates(
+            category,
+            project_type,
+            per_page,
+            page,
+        ))
+    }
+
+    /// Get events, paginated by 100
+    pub fn events(
+        &self,
+        country_code: Option<&str>,
+        event_type: Option<&str>,
+        page: i64,
+        upcoming_events_only: bool,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+    ) -> Result<Events, CoinGeckoError> {
+        check_positive("page", page)?;
+
+        self.get(&endpoints::events(
+            country_code,
+            event_type,
+            page,
+            upcoming_events_only,
+            from_date,
+            to_date,
+        ))
+    }
+
+    /// Get list of event countries
+    pub fn event_countries(&self) -> Result<EventCountries, CoinGeckoError> {
+        self.get("/events/countries")
+    }
+
+    /// Get list of event types
+    pub fn event_types(&self) -> Result<EventTypes, CoinGeckoError> {
+        self.get("/events/types")
+    }
+
+    /// Get BTC-to-Currency exchange rates
+    pub fn exchange_rates(&self) -> Result<ExchangeRates, CoinGeckoError> {
+        let cache = self.reference_cache.as_ref();
+        if let Some(cached) = cache.and_then(|cache| cache.exchange_rates.get()) {
+            return Ok(cached);
+        }
+
+        let rates: ExchangeRates = self.get("/exchange_rates")?;
+        if let Some(cache) = cache {
+            cache.exchange_rates.set(rates.clone());
+        }
+
+        Ok(rates)
+    }
+
+    /// Search for coins, categories, exchanges and NFTs matching a query
+    pub fn search(&self, query: &str) -> Result<SearchResults, CoinGeckoError> {
+        self.get(&endpoints::search(query))
+    }
+
+    /// Top-7 trending coins on CoinGecko as searched by users in the last 24 hours (Ordered by most popular first)
+    pub fn trending(&self) -> Result<Trending, CoinGeckoError> {
+        self.get("/search/trending")
+    }
+
+    /// Get cryptocurrency global data
+    pub fn global(&self) -> Result<Global, CoinGeckoError> {
+        self.get("/global")
+    }
+
+    /// Get Top 100 Cryptocurrency Global Eecentralized Finance(defi) data
+    pub fn global_defi(&self) -> Result<GlobalDefi, CoinGeckoError> {
+        self.get("/global/decentralized_finance_defi")
+    }
+
+    /// Get historical global market cap and volume data (Pro API only)
+    pub fn global_market_cap_chart(
+        &self,
+        days: i64,
+        vs_currency: impl Into<VsCurrency>,
+    ) -> Result<GlobalMarketCapChart, CoinGeckoError> {
+        check_positive("days", days)?;
+
+        self.get(&endpoints::global_market_cap_chart(
+            days,
+            vs_currency.into().as_ref(),
+        ))
+    }
+
+    /// Get public companies bitcoin or ethereum holdings (Ordered by total holdings descending)
+    pub fn companies(
+        &self,
+        coin_id: CompaniesCoinId,
+    ) -> Result<CompaniesPublicTreasury, CoinGeckoError> {
+        self.get(&endpoints::companies(coin_id))
+    }
+
+    /// Get public companies holdings of any coin CoinGecko tracks treasuries for (Ordered by total holdings descending)
+    pub fn companies_by_id(
+        &self,
+        coin_id: impl Into<CoinId>,
+    ) -> Result<CompaniesPublicTreasury, CoinGeckoError> {
+        self.get(&endpoints::companies_by_id(coin_id.into().as_ref()))
+    }
+}
+
+/// Buffers a response body, refusing to write past `limit` bytes
+struct LimitedBody {
+    body: Vec<u8>,
+    limit: u64,
+    exceeded: bool,
+}
+
+impl Write for LimitedBody {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if (self.body.len() + buf.len()) as u64 > self.limit {
+            self.exceeded = true;
+            return Err(io::Error::other("response body exceeds the limit"));
+        }
+
+        self.body.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
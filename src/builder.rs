@@ -0,0 +1,664 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+
+use crate::api_key::ApiKey;
+#[cfg(feature = "cache")]
+use crate::cache::{EtagCache, HistoryCache};
+use crate::circuit::CircuitBreaker;
+use crate::client::{CoinGeckoClient, PUBLIC_HOST};
+use crate::key_pool::KeyPool;
+use crate::reference_cache::ReferenceCache;
+use crate::retry::RetryPolicy;
+use crate::single_flight::SingleFlight;
+use crate::throttle::Throttle;
+use crate::transport::{HttpTransport, ReqwestTransport};
+
+/// `User-Agent` sent unless one is set, identifying the client to CoinGecko
+const DEFAULT_USER_AGENT: &str = concat!("coingecko-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Builder for a configured [`CoinGeckoClient`]
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use coingecko::CoinGeckoClient;
+///
+/// let client = CoinGeckoClient::builder()
+///     .timeout(Duration::from_secs(10))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CoinGeckoClientBuilder {
+    host: Option<String>,
+    api_keys: Vec<ApiKey>,
+    user_agent: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    http2_prior_knowledge: bool,
+    max_response_bytes: Option<u64>,
+    retry: RetryPolicy,
+    transport: Option<Arc<dyn HttpTransport>>,
+    #[cfg(feature = "blocking")]
+    blocking_client: Option<reqwest::blocking::Client>,
+    strict_currencies: bool,
+    locale: Option<String>,
+    requests_per_minute: u32,
+    circuit_breaker_threshold: u32,
+    circuit_breaker_window: Option<Duration>,
+    circuit_breaker_cooldown: Option<Duration>,
+    coalesce_requests: bool,
+    reference_data_ttl: Option<Duration>,
+    #[cfg(feature = "cache")]
+    etag_cache: bool,
+    #[cfg(feature = "cache")]
+    history_cache_dir: Option<std::path::PathBuf>,
+    #[cfg(feature = "cancellation")]
+    cancellation_token: Option<tokio_util::sync::CancellationToken>,
+}
+
+impl CoinGeckoClientBuilder {
+    /// Creates a new builder with host `PUBLIC_HOST` and no timeouts
+    pub fn new() -> Self {
+        CoinGeckoClientBuilder::default()
+    }
+
+    /// Sets the host url requests are sent to
+    ///
+    /// Defaults to the host matching the `api_key`, or `PUBLIC_HOST` without one.
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Authenticates every request with a Pro or Demo API key
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::{ApiKey, CoinGeckoClient};
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .api_key(ApiKey::Demo("CG-xxxx".to_string()))
+    ///     .build();
+    /// ```
+    pub fn api_key(mut self, api_key: ApiKey) -> Self {
+        self.api_keys = vec![api_key];
+        self
+    }
+
+    /// Authenticates requests with several API keys taking turns, e.g. to spread load across Demo keys
+    ///
+    /// Keys are used round-robin, and a key answered with 429 Too Many Requests is skipped until its
+    /// `Retry-After` has passed, or for a minute without one. A rate limited request is retried right away with
+    /// the next key while one is available, before the retry policy set with `max_retries` applies.
+    /// Requests are sent to the host of the first key unless `host` is set, so use keys of the same kind.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::{ApiKey, CoinGeckoClient};
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .api_keys([
+    ///         ApiKey::Demo("CG-xxxx".to_string()),
+    ///         ApiKey::Demo("CG-yyyy".to_string()),
+    ///     ])
+    ///     .build();
+    /// ```
+    pub fn api_keys(mut self, api_keys: impl IntoIterator<Item = ApiKey>) -> Self {
+        self.api_keys = api_keys.into_iter().collect();
+        self
+    }
+
+    /// Authenticates every request with a Pro API key, sent to `PRO_HOST` unless `host` is set
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .pro_key("CG-xxxx")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .max_retries(3)
+    ///     .user_agent("my-app/1.0")
+    ///     .build();
+    /// ```
+    pub fn pro_key(self, api_key: impl Into<String>) -> Self {
+        self.api_key(ApiKey::Pro(api_key.into()))
+    }
+
+    /// Authenticates every request with a free Demo API key, sent to `DEMO_HOST` unless `host` is set
+    pub fn demo_key(self, api_key: impl Into<String>) -> Self {
+        self.api_key(ApiKey::Demo(api_key.into()))
+    }
+
+    /// Sets the `User-Agent` header of every request, replacing the default `coingecko-rs/<version>`
+    ///
+    /// Also replaces the user agent of a pre-built `reqwest::Client`, which otherwise keeps its own.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets a total timeout for each request, from connecting until the response body has been read
+    ///
+    /// Also bounds the connect phase unless `connect_timeout` is set explicitly.
+    /// Requests are unbounded by default.
+    ///
+    /// A request exceeding the timeout fails with `CoinGeckoError::Transport`, whose `source` error returns `true` for `is_timeout()`.
+    /// Ignored on wasm32, where requests are sent with the browser's fetch API.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a timeout for only the connect phase of each request, ignored on wasm32
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets how many idle connections are kept open per host for reuse (defaults to no limit), ignored on wasm32
+    ///
+    /// Keeping connections warm saves a TLS handshake per request when polling frequently.
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// Sets how long an idle connection is kept open for reuse (defaults to 90s), ignored on wasm32
+    ///
+    /// Set it above the polling interval so connections are reused between polls.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// // poll every minute over the same connection
+    /// let client = CoinGeckoClient::builder()
+    ///     .pool_idle_timeout(Duration::from_secs(120))
+    ///     .pool_max_idle_per_host(4)
+    ///     .build();
+    /// ```
+    pub fn pool_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Sends requests over HTTP/2 without negotiating it first (defaults to `false`), ignored on wasm32
+    ///
+    /// HTTP/2 is already negotiated over TLS with CoinGecko, multiplexing concurrent requests on one connection.
+    /// Prior knowledge only helps with hosts speaking HTTP/2 without TLS, e.g. a proxy set with `host`,
+    /// and makes requests fail against hosts that only speak HTTP/1.
+    pub fn http2_prior_knowledge(mut self, http2_prior_knowledge: bool) -> Self {
+        self.http2_prior_knowledge = http2_prior_knowledge;
+        self
+    }
+
+    /// Fails requests whose response body exceeds `max_bytes` with `CoinGeckoError::ResponseTooLarge` (defaults to no limit)
+    ///
+    /// Guards against a misbehaving host, e.g. a proxy set with `host`, sending a body too large to hold in memory.
+    /// The default transport stops reading the body once it is over the limit, while the body returned
+    /// by a custom `transport` is only checked after it has been read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// // `coins_list` is the largest response at a few MB
+    /// let client = CoinGeckoClient::builder()
+    ///     .max_response_bytes(64 * 1024 * 1024)
+    ///     .build();
+    /// ```
+    pub fn max_response_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_response_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets how many times a request answered with 429 Too Many Requests is retried
+    ///
+    /// Retries wait for the `Retry-After` sent by CoinGecko or else back off exponentially from `retry_base_delay`.
+    /// Once retries are exhausted, or the next wait would exceed `max_retry_wait`, the request fails with `CoinGeckoError::RateLimited`.
+    /// Requests are not retried by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder().max_retries(3).build();
+    /// ```
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.retry.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled on every following retry (defaults to 500ms)
+    pub fn retry_base_delay(mut self, base_delay: Duration) -> Self {
+        self.retry.base_delay = base_delay;
+        self
+    }
+
+    /// Caps the total time a single request may spend waiting on retries (defaults to 30s)
+    pub fn max_retry_wait(mut self, max_wait: Duration) -> Self {
+        self.retry.max_wait = max_wait;
+        self
+    }
+
+    /// Randomizes each backoff delay between half and all of its value to spread out concurrent retries (defaults to `true`)
+    pub fn retry_jitter(mut self, jitter: bool) -> Self {
+        self.retry.jitter = jitter;
+        self
+    }
+
+    /// Spaces out requests to send at most `requests_per_minute`, waiting before a request instead of being answered with 429 Too Many Requests
+    ///
+    /// The limit is shared between clones of the built client, so it holds across concurrent tasks.
+    /// Retries also wait for their turn. Requests are not limited by default or with `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// // CoinGecko's public API allows around 30 requests per minute
+    /// let client = CoinGeckoClient::builder()
+    ///     .requests_per_minute(30)
+    ///     .max_retries(3)
+    ///     .build();
+    /// ```
+    pub fn requests_per_minute(mut self, requests_per_minute: u32) -> Self {
+        self.requests_per_minute = requests_per_minute;
+        self
+    }
+
+    /// Opens a circuit breaker after this many consecutive failed requests within `circuit_breaker_window`
+    ///
+    /// While open, requests fail right away with `CoinGeckoError::CircuitOpen` instead of adding load to an API
+    /// that is down. After `circuit_breaker_cooldown` a single request is let through, closing the circuit again on success.
+    /// Only failures pointing to an outage count: transport errors, 5xx responses, non-JSON responses and exhausted
+    /// rate limit retries. The circuit is shared between clones of the built client. Disabled by default or with `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .circuit_breaker_threshold(5)
+    ///     .circuit_breaker_window(Duration::from_secs(30))
+    ///     .circuit_breaker_cooldown(Duration::from_secs(60))
+    ///     .build();
+    /// ```
+    pub fn circuit_breaker_threshold(mut self, failures: u32) -> Self {
+        self.circuit_breaker_threshold = failures;
+        self
+    }
+
+    /// Sets the time within which failures count towards `circuit_breaker_threshold` (defaults to 60s)
+    pub fn circuit_breaker_window(mut self, window: Duration) -> Self {
+        self.circuit_breaker_window = Some(window);
+        self
+    }
+
+    /// Sets how long an open circuit fails requests before letting one through (defaults to 30s)
+    pub fn circuit_breaker_cooldown(mut self, cooldown: Duration) -> Self {
+        self.circuit_breaker_cooldown = Some(cooldown);
+        self
+    }
+
+    /// Reuses the responses of `exchange_rates` and `supported_vs_currencies` for `ttl` instead of requesting them again
+    ///
+    /// Both change slowly, so conversion-heavy code can call them freely. Responses are requested again once older
+    /// than `ttl`, and the cache is shared between clones of the built client. Not cached by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .reference_data_ttl(Duration::from_secs(60))
+    ///     .build();
+    /// ```
+    pub fn reference_data_ttl(mut self, ttl: Duration) -> Self {
+        self.reference_data_ttl = Some(ttl);
+        self
+    }
+
+    /// Sends identical requests made at the same time only once, sharing the response (defaults to `false`)
+    ///
+    /// Requests are identical when their urls, including the query params, are the same.
+    /// This keeps e.g. a server answering many clients with the same `price` from multiplying upstream requests.
+    /// Errors wrapping a transport or decoding error cannot be shared, the waiting requests are then sent themselves.
+    /// Only applies to the async client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder().coalesce_requests(true).build();
+    /// ```
+    pub fn coalesce_requests(mut self, coalesce_requests: bool) -> Self {
+        self.coalesce_requests = coalesce_requests;
+        self
+    }
+
+    /// Caches responses sent with an `ETag` and revalidates them with `If-None-Match` (defaults to `false`)
+    ///
+    /// On a 304 Not Modified the cached body is deserialized again instead of downloading it,
+    /// which saves bandwidth for large, rarely changing responses like `coins_list`.
+    /// The cache is shared between clones of the built client and grows with every distinct url requested.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder().etag_cache(true).build();
+    /// ```
+    #[cfg(feature = "cache")]
+    pub fn etag_cache(mut self, etag_cache: bool) -> Self {
+        self.etag_cache = etag_cache;
+        self
+    }
+
+    /// Keeps responses that never change in files in `dir`, answering them from disk in later runs
+    ///
+    /// Covers `coin_history` for dates before today (UTC), whose snapshot is final,
+    /// which saves requests when backtests read the same dates again. The directory is created if missing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .history_cache_dir("target/coingecko-history")
+    ///     .build();
+    /// ```
+    #[cfg(feature = "cache")]
+    pub fn history_cache_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.history_cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Fails requests of the async client with `CoinGeckoError::Cancelled` once `token` is cancelled
+    ///
+    /// A request in flight is aborted, including while it waits for a retry or the `requests_per_minute` limit.
+    /// Cancelling a parent token stops every client using one of its child tokens,
+    /// see `CoinGeckoClient::with_cancellation_token` to scope a token to a batch of requests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// let shutdown = CancellationToken::new();
+    /// let client = CoinGeckoClient::builder()
+    ///     .cancellation_token(shutdown.child_token())
+    ///     .build();
+    /// ```
+    #[cfg(feature = "cancellation")]
+    pub fn cancellation_token(mut self, token: tokio_util::sync::CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Validates the quote currencies of `price`, `token_price` and `coins_markets` before sending them (defaults to `false`)
+    ///
+    /// CoinGecko silently leaves unsupported currencies out of responses, in strict mode these requests
+    /// fail with `CoinGeckoError::UnsupportedCurrencies` instead. See `validate_currencies`.
+    pub fn strict_currencies(mut self, strict_currencies: bool) -> Self {
+        self.strict_currencies = strict_currencies;
+        self
+    }
+
+    /// Sets the default language, e.g. `de` or `ja`, English is used without one
+    ///
+    /// Only `coins_markets` is localized by CoinGecko, which receives it as `locale` param.
+    /// `coin` and `coin_history` return every language with `localization`, pick one from those with
+    /// `CoinsItem::localized_description`, `CoinsItem::localized_name` or `History::localized_name` and `CoinGeckoClient::locale`.
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Sends requests through a custom transport instead of `reqwest`, e.g. a fake one in tests
+    ///
+    /// Timeouts and connection pool settings are not applied to a custom transport. Only used by `build`, the blocking client always uses `reqwest`.
+    pub fn transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sends requests of the blocking client with a pre-built `reqwest::blocking::Client`, ignoring the configured timeouts and connection pool settings
+    #[cfg(feature = "blocking")]
+    pub fn blocking_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.blocking_client = Some(client);
+        self
+    }
+
+    /// Builds the CoinGeckoClient
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying `reqwest::Client` cannot be initialized, in the same cases as `reqwest::Client::new`,
+    /// or if the API key or user agent contains characters not allowed in a header
+    pub fn build(self) -> CoinGeckoClient {
+        let transport = match &self.transport {
+            Some(transport) => transport.clone(),
+            None => {
+                #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+                let mut client = reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT);
+
+                // the fetch based client on wasm32 has no timeouts
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if let Some(timeout) = self.timeout {
+                        client = client.timeout(timeout);
+                    }
+
+                    if let Some(connect_timeout) = self.effective_connect_timeout() {
+                        client = client.connect_timeout(connect_timeout);
+                    }
+
+                    if let Some(max_idle) = self.pool_max_idle_per_host {
+                        client = client.pool_max_idle_per_host(max_idle);
+                    }
+
+                    if let Some(idle_timeout) = self.pool_idle_timeout {
+                        client = client.pool_idle_timeout(idle_timeout);
+                    }
+
+                    if self.http2_prior_knowledge {
+                        client = client.http2_prior_knowledge();
+                    }
+                }
+
+                let mut transport =
+                    ReqwestTransport::new(client.build().expect("failed to build reqwest client"));
+
+                if let Some(max_bytes) = self.max_response_bytes {
+                    transport = transport.with_max_response_bytes(max_bytes);
+                }
+
+                Arc::new(transport)
+            }
+        };
+
+        CoinGeckoClient {
+            host: self.effective_host(),
+            headers: self.headers(),
+            keys: self.keys(),
+            throttle: self.throttle(),
+            circuit: self.circuit(),
+            single_flight: self
+                .coalesce_requests
+                .then(|| Arc::new(SingleFlight::default())),
+            #[cfg(feature = "cache")]
+            cache: self.cache(),
+            #[cfg(feature = "cache")]
+            history_cache: self.history_cache(),
+            transport,
+            #[cfg(feature = "cancellation")]
+            cancellation_token: self.cancellation_token.clone(),
+            rate_limit: Arc::new(Mutex::new(None)),
+            retry: self.retry,
+            max_response_bytes: self.max_response_bytes,
+            strict_currencies: self.strict_currencies,
+            locale: self.locale.clone(),
+            supported_vs_currencies: Arc::new(Mutex::new(None)),
+            reference_cache: self
+                .reference_data_ttl
+                .map(|ttl| Arc::new(ReferenceCache::new(ttl))),
+        }
+    }
+
+    /// Builds a blocking CoinGeckoClient
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying `reqwest::blocking::Client` cannot be initialized, when called from within an async runtime,
+    /// or if the API key or user agent contains characters not allowed in a header
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> crate::blocking::CoinGeckoClient {
+        let client = match &self.blocking_client {
+            Some(client) => client.clone(),
+            None => {
+                // reqwest's blocking client defaults to a 30s timeout, keep requests unbounded unless configured
+                let mut client = reqwest::blocking::Client::builder()
+                    .user_agent(DEFAULT_USER_AGENT)
+                    .timeout(self.timeout);
+
+                if let Some(connect_timeout) = self.effective_connect_timeout() {
+                    client = client.connect_timeout(connect_timeout);
+                }
+
+                if let Some(max_idle) = self.pool_max_idle_per_host {
+                    client = client.pool_max_idle_per_host(max_idle);
+                }
+
+                if let Some(idle_timeout) = self.pool_idle_timeout {
+                    client = client.pool_idle_timeout(idle_timeout);
+                }
+
+                if self.http2_prior_knowledge {
+                    client = client.http2_prior_knowledge();
+                }
+
+                client.build().expect("failed to build reqwest client")
+            }
+        };
+
+        crate::blocking::CoinGeckoClient {
+            host: self.effective_host(),
+            headers: self.headers(),
+            keys: self.keys(),
+            throttle: self.throttle(),
+            circuit: self.circuit(),
+            #[cfg(feature = "cache")]
+            cache: self.cache(),
+            #[cfg(feature = "cache")]
+            history_cache: self.history_cache(),
+            client,
+            rate_limit: Arc::new(Mutex::new(None)),
+            retry: self.retry,
+            max_response_bytes: self.max_response_bytes,
+            strict_currencies: self.strict_currencies,
+            locale: self.locale.clone(),
+            supported_vs_currencies: Arc::new(Mutex::new(None)),
+            reference_cache: self
+                .reference_data_ttl
+                .map(|ttl| Arc::new(ReferenceCache::new(ttl))),
+        }
+    }
+
+    fn throttle(&self) -> Option<Arc<Throttle>> {
+        Throttle::per_minute(self.requests_per_minute).map(Arc::new)
+    }
+
+    fn circuit(&self) -> Option<Arc<CircuitBreaker>> {
+        CircuitBreaker::new(
+            self.circuit_breaker_threshold,
+            self.circuit_breaker_window
+                .unwrap_or(Duration::from_secs(60)),
+            self.circuit_breaker_cooldown
+                .unwrap_or(Duration::from_secs(30)),
+        )
+        .map(Arc::new)
+    }
+
+    #[cfg(feature = "cache")]
+    fn cache(&self) -> Option<Arc<EtagCache>> {
+        self.etag_cache.then(|| Arc::new(EtagCache::default()))
+    }
+
+    #[cfg(feature = "cache")]
+    fn history_cache(&self) -> Option<Arc<HistoryCache>> {
+        self.history_cache_dir
+            .clone()
+            .map(|dir| Arc::new(HistoryCache::new(dir)))
+    }
+
+    fn effective_host(&self) -> String {
+        match (&self.host, self.api_keys.first()) {
+            (Some(host), _) => host.clone(),
+            (None, Some(api_key)) => api_key.host().to_string(),
+            (None, None) => PUBLIC_HOST.to_string(),
+        }
+    }
+
+    /// Headers sent with every request, the API key is marked sensitive so it is not printed
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        // several keys are set per request by the key pool
+        if let [api_key] = self.api_keys.as_slice() {
+            let (name, value) = api_key_header(api_key);
+            headers.insert(name, value);
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            let value = HeaderValue::from_str(user_agent).expect("invalid user agent");
+            headers.insert(USER_AGENT, value);
+        }
+
+        headers
+    }
+
+    fn keys(&self) -> Option<Arc<KeyPool>> {
+        KeyPool::new(self.api_keys.iter().map(api_key_header).collect()).map(Arc::new)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    fn effective_connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout.or(self.timeout)
+    }
+}
+
+/// Header carrying an API key, marked sensitive so it is not printed
+///
+/// # Panics
+///
+/// Panics if the key contains characters not allowed in a header
+fn api_key_header(api_key: &ApiKey) -> (HeaderName, HeaderValue) {
+    let (name, value) = api_key.header();
+    let mut value = HeaderValue::from_str(value).expect("invalid API key");
+    value.set_sensitive(true);
+    (HeaderName::from_static(name), value)
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_NONE_MATCH};
+use serde::de::DeserializeOwned;
+
+use crate::file_name::request_path;
+
+/// Response bodies sent with an `ETag`, revalidated with `If-None-Match` instead of downloading them again
+///
+/// Keyed by url, which includes the query params of the endpoint.
+#[derive(Debug, Default)]
+pub(crate) struct EtagCache {
+    responses: Mutex<HashMap<String, CachedResponse>>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    etag: HeaderValue,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl EtagCache {
+    /// Adds `If-None-Match` to the request headers if a response to this url is cached
+    pub(crate) fn revalidate(&self, url: &str, headers: &mut HeaderMap) {
+        if let Some(cached) = self.responses.lock().unwrap().get(url) {
+            headers.insert(IF_NONE_MATCH, cached.etag.clone());
+        }
+    }
+
+    /// Headers and body of the cached response to this url, to answer a 304 Not Modified
+    pub(crate) fn get(&self, url: &str) -> Option<(HeaderMap, Vec<u8>)> {
+        let responses = self.responses.lock().unwrap();
+        let cached = responses.get(url)?;
+
+        Some((cached.headers.clone(), cached.body.clone()))
+    }
+
+    /// Caches a successful response if it carries an `ETag`
+    pub(crate) fn store(&self, url: &str, headers: &HeaderMap, body: &[u8]) {
+        if let Some(etag) = headers.get(ETAG) {
+            self.responses.lock().unwrap().insert(
+                url.to_string(),
+                CachedResponse {
+                    etag: etag.clone(),
+                    headers: headers.clone(),
+                    body: body.to_vec(),
+                },
+            );
+        }
+    }
+}
+
+/// Response bodies that never change, e.g. `coin_history` for past dates, kept on disk across runs
+///
+/// Each body is stored as JSON in a file named after the path and query params of the request.
+/// The cache is best-effort, files that cannot be read or written are fetched again.
+#[derive(Debug)]
+pub(crate) struct HistoryCache {
+    dir: PathBuf,
+}
+
+impl HistoryCache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        HistoryCache { dir }
+    }
+
+    /// Response to this url stored earlier, `None` if it is missing or no longer parses
+    pub(crate) fn get<R: DeserializeOwned>(&self, url: &str) -> Option<R> {
+        let body = fs::read(request_path(&self.dir, url)).ok()?;
+        serde_json::from_slice(&body).ok()
+    }
+
+    /// Stores a response, writing to a temporary file first so concurrent readers never see a partial body
+    pub(crate) fn store(&self, url: &str, body: &[u8]) {
+        let path = request_path(&self.dir, url);
+        let partial = path.with_extension(format!("json.{}.partial", std::process::id()));
+
+        let res = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&partial, body))
+            .and_then(|_| fs::rename(&partial, &path));
+
+        if let Err(_e) = res {
+            let _ = fs::remove_file(&partial);
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!(path = %path.display(), error = %_e, "failed to write the history cache");
+        }
+    }
+}
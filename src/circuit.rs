@@ -0,0 +1,107 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::CoinGeckoError;
+
+/// Circuit breaker failing requests fast while CoinGecko is unavailable
+///
+/// Opens after `threshold` consecutive failures within `window`, then fails every request with
+/// `CoinGeckoError::CircuitOpen` until `cooldown` has passed. The first request afterwards is sent as a probe:
+/// a success closes the circuit, a failure opens it for another cooldown.
+/// Only failures pointing to an outage count, see `is_outage`.
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    threshold: u32,
+    window: chrono::Duration,
+    cooldown: chrono::Duration,
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    /// Consecutive failures since `first_failure`
+    failures: u32,
+    first_failure: Option<DateTime<Utc>>,
+    open_until: Option<DateTime<Utc>>,
+}
+
+impl CircuitBreaker {
+    /// Circuit breaker opening after `threshold` failures, or `None` to never open when zero
+    pub(crate) fn new(threshold: u32, window: Duration, cooldown: Duration) -> Option<Self> {
+        if threshold == 0 {
+            return None;
+        }
+
+        Some(CircuitBreaker {
+            threshold,
+            window: chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX),
+            cooldown: chrono::Duration::from_std(cooldown).unwrap_or(chrono::Duration::MAX),
+            state: Mutex::new(CircuitState::default()),
+        })
+    }
+
+    /// Fails with `CoinGeckoError::CircuitOpen` while the circuit is open
+    pub(crate) fn check(&self) -> Result<(), CoinGeckoError> {
+        self.check_at(Utc::now())
+    }
+
+    pub(crate) fn check_at(&self, now: DateTime<Utc>) -> Result<(), CoinGeckoError> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.open_until {
+            Some(open_until) if open_until > now => Err(CoinGeckoError::CircuitOpen {
+                retry_after: (open_until - now).to_std().unwrap_or_default(),
+            }),
+            Some(_) => {
+                // let a probe through, whose failure alone opens the circuit again
+                state.open_until = None;
+                state.failures = self.threshold - 1;
+                state.first_failure = Some(now);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Records the outcome of a request
+    pub(crate) fn record(&self, error: Option<&CoinGeckoError>) {
+        self.record_at(Utc::now(), error)
+    }
+
+    pub(crate) fn record_at(&self, now: DateTime<Utc>, error: Option<&CoinGeckoError>) {
+        let mut state = self.state.lock().unwrap();
+
+        if !error.is_some_and(is_outage) {
+            *state = CircuitState::default();
+            return;
+        }
+
+        match state.first_failure {
+            Some(first_failure) if now - first_failure <= self.window => state.failures += 1,
+            _ => {
+                state.failures = 1;
+                state.first_failure = Some(now);
+            }
+        }
+
+        if state.failures >= self.threshold {
+            state.open_until = Some(
+                now.checked_add_signed(self.cooldown)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            );
+        }
+    }
+}
+
+/// Whether an error points to CoinGecko being unavailable rather than to a bad request
+fn is_outage(error: &CoinGeckoError) -> bool {
+    match error {
+        CoinGeckoError::Transport { .. }
+        | CoinGeckoError::RateLimited { .. }
+        | CoinGeckoError::UnexpectedContentType { .. } => true,
+        CoinGeckoError::Http { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
@@ -1,44 +1,123 @@
 #![allow(clippy::too_many_arguments)]
+use std::cmp::Ordering;
 use std::collections::HashMap;
-
-use chrono::{NaiveDate, NaiveDateTime};
-use reqwest::Error;
+use std::fmt;
+use std::future::{self, Future};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::Duration;
+
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
 
+use crate::api_key::{redacted_keys, ApiKey};
+use crate::builder::CoinGeckoClientBuilder;
+#[cfg(feature = "cache")]
+use crate::cache::{EtagCache, HistoryCache};
+use crate::circuit::CircuitBreaker;
+use crate::endpoints;
+use crate::error::{sanitize_url, CoinGeckoError};
+use crate::key_pool::KeyPool;
+use crate::rate_limit::RateLimit;
+use crate::reference_cache::ReferenceCache;
+use crate::retry::{retry_after, RetryPolicy};
+use crate::single_flight::{Join, SingleFlight};
+use crate::throttle::Throttle;
+use crate::transport::{check_response_size, HttpTransport, ReqwestTransport};
+
 use crate::params::{
-    CompaniesCoinId, DerivativeExchangeOrder, DerivativesIncludeTickers, MarketsOrder, OhlcDays,
-    PriceChangePercentage, TickersOrder,
+    check_positive, check_range, single_filter, AssetPlatformFilter, AssetPlatformId, ChartDays,
+    CoinId, CompaniesCoinId, DerivativeExchangeOrder, DerivativesIncludeTickers, ExchangeStatus,
+    IncludeTokens, Interval, MarketsFilter, MarketsOrder, NftsOrder, OhlcDays, OhlcInterval,
+    Precision, PriceChangePercentage, TickersOrder, TopCoins, TopGainersLosersDuration, VsCurrency,
 };
 
 use crate::response::{
     asset_platforms::AssetPlatform,
     coins::{
-        Category, CategoryId, CoinsItem, CoinsListItem, CoinsMarketItem, Contract, History,
-        MarketChart,
+        Category, CategoryId, CirculatingSupplyChart, CoinsItem, CoinsListItem, CoinsMarketItem,
+        Contract, History, MarketChart, NewCoin, Ohlc, TopGainersLosers, TotalSupplyChart,
     },
     common::{StatusUpdates, Tickers},
     companies::CompaniesPublicTreasury,
-    derivatives::{Derivative, DerivativeExchangeId},
+    derivatives::{Derivative, DerivativeExchangeDetail, DerivativeExchangeId},
     events::Events,
     events::{EventCountries, EventTypes},
     exchange_rates::ExchangeRates,
     exchanges::VolumeChartData,
     exchanges::{Exchange, ExchangeId},
     finance::{FinancePlatform, FinanceProduct},
-    global::{Global, GlobalDefi},
+    global::{Global, GlobalDefi, GlobalMarketCapChart},
     indexes::Index,
     indexes::{IndexId, MarketIndex},
+    nfts::{Nft, NftId, NftMarketChart, NftMarketItem},
     ping::SimplePing,
+    search::SearchResults,
     simple::{Price, SupportedVsCurrencies},
     trending::Trending,
 };
 
+/// Host of the public API, used without an API key
+pub const PUBLIC_HOST: &str = "https://api.coingecko.com/api/v3";
+/// Host accepting Demo API keys, the same as the public one
+pub const DEMO_HOST: &str = PUBLIC_HOST;
+/// Host accepting Pro API keys, which answers requests with a Demo key or without a key with 401 Unauthorized
+pub const PRO_HOST: &str = "https://pro-api.coingecko.com/api/v3";
+
+/// Requests in flight at a time in `prices_chunked` and the batch streams unless configured
+pub(crate) const BATCH_CONCURRENCY: usize = 5;
+
+/// Page size of `exchanges_by_volume`, the largest `/exchanges` accepts
+pub(crate) const EXCHANGES_PER_PAGE: i64 = 250;
+
+/// Number of ids sent per request by `prices_chunked` unless configured, keeping urls well below common length limits
+pub const DEFAULT_PRICE_CHUNK_SIZE: usize = 250;
+
 /// CoinGecko client
+#[derive(Clone)]
 pub struct CoinGeckoClient {
-    host: &'static str,
+    pub(crate) host: String,
+    pub(crate) headers: HeaderMap,
+    pub(crate) keys: Option<Arc<KeyPool>>,
+    pub(crate) transport: Arc<dyn HttpTransport>,
+    pub(crate) rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) max_response_bytes: Option<u64>,
+    pub(crate) strict_currencies: bool,
+    pub(crate) locale: Option<String>,
+    pub(crate) supported_vs_currencies: Arc<Mutex<Option<SupportedVsCurrencies>>>,
+    pub(crate) reference_cache: Option<Arc<ReferenceCache>>,
+    pub(crate) throttle: Option<Arc<Throttle>>,
+    pub(crate) circuit: Option<Arc<CircuitBreaker>>,
+    pub(crate) single_flight: Option<Arc<SingleFlight>>,
+    #[cfg(feature = "cache")]
+    pub(crate) cache: Option<Arc<EtagCache>>,
+    #[cfg(feature = "cache")]
+    pub(crate) history_cache: Option<Arc<HistoryCache>>,
+    #[cfg(feature = "cancellation")]
+    pub(crate) cancellation_token: Option<tokio_util::sync::CancellationToken>,
 }
 
-/// Creates a new CoinGeckoClient with host https://api.coingecko.com/api/v3
+/// Shows the configuration of the client with every API key redacted as `***`
+impl fmt::Debug for CoinGeckoClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoinGeckoClient")
+            .field("host", &self.host)
+            .field(
+                "api_keys",
+                &redacted_keys(&self.headers, self.keys.as_deref()),
+            )
+            .field("retry", &self.retry)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("strict_currencies", &self.strict_currencies)
+            .field("locale", &self.locale)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates a new CoinGeckoClient with host `PUBLIC_HOST`
 ///
 /// # Examples
 ///
@@ -48,12 +127,12 @@ pub struct CoinGeckoClient {
 /// ```
 impl Default for CoinGeckoClient {
     fn default() -> Self {
-        CoinGeckoClient::new("https://api.coingecko.com/api/v3")
+        CoinGeckoClient::builder().build()
     }
 }
 
 impl CoinGeckoClient {
-    /// Creates a new CoinGeckoClient client with a custom host url
+    /// Creates a new CoinGeckoClient client with a custom host url, e.g. a proxy or a test server
     ///
     /// # Examples
     ///
@@ -62,14 +141,470 @@ impl CoinGeckoClient {
     /// let client = CoinGeckoClient::new("https://some.url");
     /// ```
     pub fn new(host: &'static str) -> Self {
-        CoinGeckoClient { host }
+        CoinGeckoClient::builder().host(host).build()
     }
 
-    async fn get<R: DeserializeOwned>(&self, endpoint: &str) -> Result<R, Error> {
-        reqwest::get(format!("{host}/{ep}", host = self.host, ep = endpoint))
-            .await?
-            .json()
-            .await
+    /// Creates a new CoinGeckoClient authenticated with a free Demo API key against `DEMO_HOST`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    /// let client = CoinGeckoClient::new_with_demo_key("CG-xxxx");
+    /// ```
+    pub fn new_with_demo_key(api_key: impl Into<String>) -> Self {
+        CoinGeckoClient::builder().demo_key(api_key).build()
+    }
+
+    /// Creates a new CoinGeckoClient authenticated with a Pro API key against `PRO_HOST`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::CoinGeckoClient;
+    /// let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    /// ```
+    pub fn new_with_pro_key(api_key: impl Into<String>) -> Self {
+        CoinGeckoClient::builder().pro_key(api_key).build()
+    }
+
+    /// Creates a new CoinGeckoClient with a custom host url authenticated with an API key
+    ///
+    /// The host is used as is, prefer `new_with_pro_key` or `new_with_demo_key` to get the host matching the key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use coingecko::{ApiKey, CoinGeckoClient, PRO_HOST};
+    /// let client = CoinGeckoClient::new_with_key(PRO_HOST, ApiKey::Pro("CG-xxxx".to_string()));
+    /// ```
+    pub fn new_with_key(host: impl Into<String>, api_key: ApiKey) -> Self {
+        CoinGeckoClient::builder()
+            .host(host)
+            .api_key(api_key)
+            .build()
+    }
+
+    /// Creates a new CoinGeckoClient with a custom host url sending requests with a pre-built `reqwest::Client`
+    ///
+    /// Use this to configure a proxy, TLS, root certificates, connection pooling or timeouts on the `reqwest::Client` directly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = reqwest::Client::builder()
+    ///     .pool_max_idle_per_host(4)
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .unwrap();
+    /// let client = CoinGeckoClient::with_client("https://api.coingecko.com/api/v3", client);
+    /// ```
+    pub fn with_client(host: impl Into<String>, client: reqwest::Client) -> Self {
+        CoinGeckoClient::builder()
+            .host(host)
+            .transport(ReqwestTransport::new(client))
+            .build()
+    }
+
+    /// Creates a new CoinGeckoClient with host `PUBLIC_HOST` writing the body of every successful response to `dir`
+    ///
+    /// Replay the recorded responses with `replaying`, e.g. to add test fixtures without hitting the API in tests.
+    /// Use a [`RecordingTransport`](crate::transport::RecordingTransport) with the builder to record with an API key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::recording(std::env::temp_dir().join("coingecko-fixtures"));
+    ///
+    ///     client.ping().await;
+    /// }
+    /// ```
+    #[cfg(feature = "vcr")]
+    pub fn recording(dir: impl Into<std::path::PathBuf>) -> Self {
+        CoinGeckoClient::builder()
+            .transport(crate::transport::RecordingTransport::new(
+                dir,
+                ReqwestTransport::new(reqwest::Client::new()),
+            ))
+            .build()
+    }
+
+    /// Creates a new CoinGeckoClient answering requests with the responses recorded to `dir` by `recording`
+    ///
+    /// No request reaches the network, requests without a recorded response fail with `CoinGeckoError::Fixture`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{CoinGeckoClient, CoinGeckoError};
+    ///     let client = CoinGeckoClient::replaying(std::env::temp_dir().join("coingecko-fixtures"));
+    ///
+    ///     match client.ping().await {
+    ///         Ok(ping) => println!("{}", ping.gecko_says),
+    ///         Err(CoinGeckoError::Fixture { path, .. }) => println!("record {} first", path.display()),
+    ///         Err(e) => println!("{}", e),
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "vcr")]
+    pub fn replaying(dir: impl Into<std::path::PathBuf>) -> Self {
+        CoinGeckoClient::builder()
+            .transport(crate::transport::ReplayTransport::new(dir))
+            .build()
+    }
+
+    /// Creates a CoinGeckoClientBuilder to configure timeouts and other client options
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use coingecko::CoinGeckoClient;
+    ///
+    /// let client = CoinGeckoClient::builder()
+    ///     .host("https://some.url")
+    ///     .timeout(Duration::from_secs(10))
+    ///     .build();
+    /// ```
+    pub fn builder() -> CoinGeckoClientBuilder {
+        CoinGeckoClientBuilder::new()
+    }
+
+    /// Rate limit state reported on the most recent response, shared between clones of this client
+    ///
+    /// Returns `None` until a response carrying rate limit headers has been received.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.ping().await;
+    ///
+    ///     if let Some(rate_limit) = client.last_rate_limit() {
+    ///         println!("{:?} requests remaining", rate_limit.remaining);
+    ///     }
+    /// }
+    /// ```
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
+    /// Clone of this client whose requests fail with `CoinGeckoError::Cancelled` once `token` is cancelled
+    ///
+    /// Shares the connection pool, rate limiting and caches with this client, so it is cheap to create
+    /// one per batch of requests, e.g. the backfill behind a view the user can navigate away from.
+    /// Replaces any token set with `CoinGeckoClientBuilder::cancellation_token`, pass one of its child tokens to keep both.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{CoinGeckoClient, CoinGeckoError};
+    ///     use tokio_util::sync::CancellationToken;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let token = CancellationToken::new();
+    ///     let scoped = client.with_cancellation_token(token.clone());
+    ///     token.cancel();
+    ///
+    ///     assert!(matches!(scoped.ping().await, Err(CoinGeckoError::Cancelled)));
+    /// }
+    /// ```
+    #[cfg(feature = "cancellation")]
+    pub fn with_cancellation_token(&self, token: tokio_util::sync::CancellationToken) -> Self {
+        CoinGeckoClient {
+            cancellation_token: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Default language set with `CoinGeckoClientBuilder::locale`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::builder().locale("de").build();
+    ///
+    ///     if let Ok(coin) = client.coin("bitcoin", true, false, false, false, false, false).await {
+    ///         println!("{:?}", coin.localized_description(client.locale().unwrap_or("en")));
+    ///     }
+    /// }
+    /// ```
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Escape hatch to call any endpoint, e.g. one not yet covered by this crate, with the host, API key,
+    /// rate limit and retry handling of the client
+    ///
+    /// `endpoint` is the path below the host like `/coins/list`, `params` are url encoded into its query.
+    /// Prefer the typed methods where they exist, the response is deserialized into any `T`,
+    /// including `serde_json::Value` to read raw JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let coins: Result<serde_json::Value, _> = client
+    ///         .get_json("/coins/list", &[("include_platform", "false")])
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn get_json<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T, CoinGeckoError> {
+        self.get(&endpoints::raw(endpoint, params)).await
+    }
+
+    /// Whether requests are authenticated with a Pro API key
+    fn is_pro(&self) -> bool {
+        self.headers.contains_key("x-cg-pro-api-key")
+            || self.keys.as_ref().is_some_and(|keys| keys.is_pro())
+    }
+
+    /// Full url of an endpoint, never containing the API key so it is safe to log
+    pub(crate) fn get_url(&self, endpoint: &str) -> String {
+        format!("{host}/{ep}", host = self.host, ep = endpoint)
+    }
+
+    pub(crate) async fn get<R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> Result<R, CoinGeckoError> {
+        self.cancellable(self.with_circuit(async {
+            let (headers, body) = self.send(endpoint).await?;
+            CoinGeckoError::decode(&headers, &body, &self.get_url(endpoint))
+        }))
+        .await
+    }
+
+    /// Like `get` for responses that never change, kept in the history cache if one is set
+    ///
+    /// Cached responses are answered even while the circuit breaker is open.
+    pub(crate) async fn get_immutable<R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> Result<R, CoinGeckoError> {
+        #[cfg(feature = "cache")]
+        if let Some(history) = &self.history_cache {
+            let url = self.get_url(endpoint);
+            if let Some(cached) = history.get(&url) {
+                return Ok(cached);
+            }
+
+            return self
+                .cancellable(self.with_circuit(async {
+                    let (headers, body) = self.send(endpoint).await?;
+                    let res = CoinGeckoError::decode(&headers, &body, &url)?;
+                    history.store(&url, &body);
+                    Ok(res)
+                }))
+                .await;
+        }
+
+        self.get(endpoint).await
+    }
+
+    /// Body of a successful response, checked to be a JSON array without parsing its items
+    #[cfg(feature = "stream")]
+    pub(crate) async fn get_array(&self, endpoint: &str) -> Result<Vec<u8>, CoinGeckoError> {
+        self.cancellable(self.with_circuit(async {
+            let (headers, body) = self.send(endpoint).await?;
+            // the items are ignored while checking, so no memory is allocated for them
+            CoinGeckoError::decode::<Vec<serde::de::IgnoredAny>>(
+                &headers,
+                &body,
+                &self.get_url(endpoint),
+            )?;
+            Ok(body)
+        }))
+        .await
+    }
+
+    /// Aborts a request with `CoinGeckoError::Cancelled` once the cancellation token fires
+    ///
+    /// Wraps the circuit breaker, so cancelled requests are not counted as its successes or failures.
+    async fn cancellable<T>(
+        &self,
+        request: impl Future<Output = Result<T, CoinGeckoError>>,
+    ) -> Result<T, CoinGeckoError> {
+        #[cfg(feature = "cancellation")]
+        if let Some(token) = &self.cancellation_token {
+            return token
+                .run_until_cancelled(request)
+                .await
+                .unwrap_or(Err(CoinGeckoError::Cancelled));
+        }
+
+        request.await
+    }
+
+    /// Runs a request through the circuit breaker if one is configured
+    async fn with_circuit<T>(
+        &self,
+        request: impl Future<Output = Result<T, CoinGeckoError>>,
+    ) -> Result<T, CoinGeckoError> {
+        let circuit = match &self.circuit {
+            Some(circuit) => circuit,
+            None => return request.await,
+        };
+
+        circuit.check()?;
+        let res = request.await;
+        circuit.record(res.as_ref().err());
+        res
+    }
+
+    /// Headers and body of a successful response, shared with identical requests in flight if coalescing is enabled
+    async fn send(&self, endpoint: &str) -> Result<(HeaderMap, Vec<u8>), CoinGeckoError> {
+        let flights = match &self.single_flight {
+            Some(flights) => flights,
+            None => return self.fetch(endpoint).await,
+        };
+
+        match flights.join(&sanitize_url(&self.get_url(endpoint))) {
+            Join::Leader(leader) => {
+                let res = self.fetch(endpoint).await;
+                leader.finish(&res);
+                res
+            }
+            Join::Follower(follower) => match follower.await {
+                Some(shared) => shared,
+                // the first request was cancelled or failed with an error that cannot be shared
+                None => self.fetch(endpoint).await,
+            },
+        }
+    }
+
+    /// Headers and body of a successful response
+    async fn fetch(&self, endpoint: &str) -> Result<(HeaderMap, Vec<u8>), CoinGeckoError> {
+        let url = self.get_url(endpoint);
+
+        let mut headers = self.headers.clone();
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            cache.revalidate(&url, &mut headers);
+        }
+
+        let mut attempt = 0;
+        let mut waited = Duration::ZERO;
+        let mut rotations = 0;
+        let response = loop {
+            let key = self.keys.as_ref().map(|keys| keys.select(&mut headers));
+
+            if let Some(throttle) = &self.throttle {
+                crate::retry::sleep(throttle.reserve()).await;
+            }
+
+            // std's clock panics on wasm32, where no elapsed time is recorded
+            #[cfg(feature = "tracing")]
+            let started = (!cfg!(target_arch = "wasm32")).then(std::time::Instant::now);
+
+            let response = self
+                .transport
+                .get(&url, headers.clone())
+                .await
+                .map_err(|e| e.with_url(&url))?;
+
+            // the default transport already enforces the limit while reading, custom ones may not
+            check_response_size(response.body.len() as u64, self.max_response_bytes)?;
+
+            // the endpoint never contains the API key, which is only sent as a header
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                endpoint,
+                status = response.status.as_u16(),
+                attempt,
+                elapsed_ms = started.map(|started| started.elapsed().as_millis() as u64),
+                "received CoinGecko response"
+            );
+
+            if let Some(rate_limit) = RateLimit::from_headers(&response.headers) {
+                *self.rate_limit.lock().unwrap() = Some(rate_limit);
+            }
+
+            if response.status != StatusCode::TOO_MANY_REQUESTS {
+                break response;
+            }
+
+            // try each other key once before falling back to the retry policy
+            if let (Some(keys), Some(key)) = (&self.keys, key) {
+                if keys.rate_limited(key, retry_after(&response.headers))
+                    && rotations + 1 < keys.len()
+                {
+                    rotations += 1;
+                    continue;
+                }
+            }
+
+            match self
+                .retry
+                .delay(attempt, waited, retry_after(&response.headers))
+            {
+                Some(delay) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        endpoint,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "rate limited by CoinGecko, retrying"
+                    );
+
+                    crate::retry::sleep(delay).await;
+                    attempt += 1;
+                    waited += delay;
+                }
+                None => break response,
+            }
+        };
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            if response.status == StatusCode::NOT_MODIFIED {
+                if let Some(cached) = cache.get(&url) {
+                    return Ok(cached);
+                }
+            }
+        }
+
+        if !response.status.is_success() {
+            let body = String::from_utf8_lossy(&response.body).into_owned();
+            return Err(CoinGeckoError::from_status(
+                response.status,
+                &response.headers,
+                body,
+                &url,
+            ));
+        }
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            cache.store(&url, &response.headers, &response.body);
+        }
+
+        Ok((response.headers, response.body))
     }
 
     /// Check API server status
@@ -85,10 +620,29 @@ impl CoinGeckoClient {
     ///     client.ping().await;
     /// }
     /// ```
-    pub async fn ping(&self) -> Result<SimplePing, Error> {
+    pub async fn ping(&self) -> Result<SimplePing, CoinGeckoError> {
         self.get("/ping").await
     }
 
+    /// Whether the API server answers `ping`, any error is reported as `false`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     if client.is_up().await {
+    ///         println!("CoinGecko is up");
+    ///     }
+    /// }
+    /// ```
+    pub async fn is_up(&self) -> bool {
+        self.ping().await.is_ok()
+    }
+
     /// Get the current price of any cryptocurrencies in any other supported currencies that you need
     ///
     /// # Examples
@@ -99,7 +653,7 @@ impl CoinGeckoClient {
     ///     use coingecko::CoinGeckoClient;
     ///     let client = CoinGeckoClient::default();
     ///
-    ///     client.price(&["bitcoin", "ethereum"], &["usd"], true, true, true, true).await;
+    ///     client.price(&["bitcoin", "ethereum"], &["usd"], true, true, true, true, None).await;
     /// }
     /// ```
     pub async fn price<Id: AsRef<str>, Curr: AsRef<str>>(
@@ -110,14 +664,75 @@ impl CoinGeckoClient {
         include_24hr_vol: bool,
         include_24hr_change: bool,
         include_last_updated_at: bool,
-    ) -> Result<HashMap<String, Price>, Error> {
-        let ids = ids.iter().map(AsRef::as_ref).collect::<Vec<_>>();
-        let vs_currencies = vs_currencies.iter().map(AsRef::as_ref).collect::<Vec<_>>();
-        let req = format!("/simple/price?ids={}&vs_currencies={}&include_market_cap={}&include_24hr_vol={}&include_24hr_change={}&include_last_updated_at={}", ids.join("%2C"), vs_currencies.join("%2C"), include_market_cap, include_24hr_vol, include_24hr_change, include_last_updated_at);
-        self.get(&req).await
+        precision: Option<Precision>,
+    ) -> Result<HashMap<String, Price>, CoinGeckoError> {
+        self.check_currencies(vs_currencies).await?;
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::price(
+            ids,
+            vs_currencies,
+            include_market_cap,
+            include_24hr_vol,
+            include_24hr_change,
+            include_last_updated_at,
+            precision.as_deref(),
+        ))
+        .await
     }
 
-    /// Get current price of tokens (using contract addresses) for a given platform in any other currency that you need
+    /// Same as `price`, with the prices keyed by [`CoinId`]
+    ///
+    /// Use `String::from` on the keys to get back the ids as strings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::CoinId, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let bitcoin = CoinId::new("bitcoin");
+    ///     if let Ok(prices) = client.price_by_id(&[&bitcoin], &["usd"], false, false, false, false, None).await {
+    ///         println!("{:?}", prices.get(&bitcoin).and_then(|price| price.get("usd")));
+    ///     }
+    /// }
+    /// ```
+    pub async fn price_by_id<Id: AsRef<str>, Curr: AsRef<str>>(
+        &self,
+        ids: &[Id],
+        vs_currencies: &[Curr],
+        include_market_cap: bool,
+        include_24hr_vol: bool,
+        include_24hr_change: bool,
+        include_last_updated_at: bool,
+        precision: Option<Precision>,
+    ) -> Result<HashMap<CoinId, Price>, CoinGeckoError> {
+        let prices = self
+            .price(
+                ids,
+                vs_currencies,
+                include_market_cap,
+                include_24hr_vol,
+                include_24hr_change,
+                include_last_updated_at,
+                precision,
+            )
+            .await?;
+
+        Ok(prices
+            .into_iter()
+            .map(|(id, price)| (CoinId::from(id), price))
+            .collect())
+    }
+
+    /// Get the current price of any number of cryptocurrencies, split into several requests to stay within url length limits
+    ///
+    /// Duplicate ids are requested once. Each request asks for up to `chunk_size` ids (defaults to [`DEFAULT_PRICE_CHUNK_SIZE`])
+    /// and at most `concurrency` requests are in flight at a time (defaults to 5), `Some(1)` sending them one after another.
+    /// Prices of all requests are merged, a coin returned by several requests keeps its last price.
+    /// Fails with the first error of any request.
     ///
     /// # Examples
     ///
@@ -126,6 +741,68 @@ impl CoinGeckoClient {
     /// async fn main() {
     ///     use coingecko::CoinGeckoClient;
     ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.prices_chunked(&["bitcoin", "ethereum"], &["usd"], true, true, true, true, None, None, None).await;
+    /// }
+    /// ```
+    pub async fn prices_chunked<Id: AsRef<str>, Curr: AsRef<str>>(
+        &self,
+        ids: &[Id],
+        vs_currencies: &[Curr],
+        include_market_cap: bool,
+        include_24hr_vol: bool,
+        include_24hr_change: bool,
+        include_last_updated_at: bool,
+        precision: Option<Precision>,
+        chunk_size: Option<usize>,
+        concurrency: Option<usize>,
+    ) -> Result<HashMap<String, Price>, CoinGeckoError> {
+        self.check_currencies(vs_currencies).await?;
+        let precision = precision.map(Precision::param).transpose()?;
+
+        let endpoints = endpoints::chunk_ids(ids, chunk_size.unwrap_or(DEFAULT_PRICE_CHUNK_SIZE))
+            .iter()
+            .map(|ids| {
+                endpoints::price(
+                    ids,
+                    vs_currencies,
+                    include_market_cap,
+                    include_24hr_vol,
+                    include_24hr_change,
+                    include_last_updated_at,
+                    precision.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let responses = join_bounded(
+            endpoints
+                .iter()
+                .map(|endpoint| self.get::<HashMap<String, Price>>(endpoint)),
+            concurrency.unwrap_or(BATCH_CONCURRENCY),
+        )
+        .await;
+
+        let mut prices = HashMap::new();
+        for response in responses {
+            prices.extend(response?);
+        }
+
+        Ok(prices)
+    }
+
+    /// Get current price of tokens (using contract addresses) for a given platform in any other currency that you need
+    ///
+    /// Pass `IncludeTokens::Top` for large batches of contract addresses to only get the top token of each address.
+    /// CoinGecko's default applies with `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::{IncludeTokens, Precision}, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
     ///     let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
     ///
     ///     client.token_price(
@@ -136,26 +813,38 @@ impl CoinGeckoClient {
     ///         true,
     ///         true,
     ///         true,
+    ///         Some(Precision::Full),
+    ///         Some(IncludeTokens::Top),
     ///     ).await;
     /// }
     /// ```
     pub async fn token_price<Addr: AsRef<str>, Curr: AsRef<str>>(
         &self,
-        id: &str,
+        id: impl Into<AssetPlatformId>,
         contract_addresses: &[Addr],
         vs_currencies: &[Curr],
         include_market_cap: bool,
         include_24hr_vol: bool,
         include_24hr_change: bool,
         include_last_updated_at: bool,
-    ) -> Result<HashMap<String, Price>, Error> {
-        let contract_addresses = contract_addresses
-            .iter()
-            .map(AsRef::as_ref)
-            .collect::<Vec<_>>();
-        let vs_currencies = vs_currencies.iter().map(AsRef::as_ref).collect::<Vec<_>>();
-        let req = format!("/simple/token_price/{}?contract_addresses={}&vs_currencies={}&include_market_cap={}&include_24hr_vol={}&include_24hr_change={}&include_last_updated_at={}", id, contract_addresses.join("%2C"), vs_currencies.join("%2C"), include_market_cap, include_24hr_vol, include_24hr_change, include_last_updated_at);
-        self.get(&req).await
+        precision: Option<Precision>,
+        include_tokens: Option<IncludeTokens>,
+    ) -> Result<HashMap<String, Price>, CoinGeckoError> {
+        self.check_currencies(vs_currencies).await?;
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::token_price(
+            id.into().as_ref(),
+            contract_addresses,
+            vs_currencies,
+            include_market_cap,
+            include_24hr_vol,
+            include_24hr_change,
+            include_last_updated_at,
+            precision.as_deref(),
+            include_tokens,
+        ))
+        .await
     }
 
     /// Get list of supported_vs_currencies
@@ -171,8 +860,77 @@ impl CoinGeckoClient {
     ///     client.supported_vs_currencies().await;
     /// }
     /// ```
-    pub async fn supported_vs_currencies(&self) -> Result<SupportedVsCurrencies, Error> {
-        self.get("/simple/supported_vs_currencies").await
+    pub async fn supported_vs_currencies(&self) -> Result<SupportedVsCurrencies, CoinGeckoError> {
+        let cache = self.reference_cache.as_ref();
+        if let Some(cached) = cache.and_then(|cache| cache.supported_vs_currencies.get()) {
+            return Ok(cached);
+        }
+
+        let supported: SupportedVsCurrencies = self.get("/simple/supported_vs_currencies").await?;
+        if let Some(cache) = cache {
+            cache.supported_vs_currencies.set(supported.clone());
+        }
+
+        Ok(supported)
+    }
+
+    /// Checks that CoinGecko supports every currency, failing with `CoinGeckoError::UnsupportedCurrencies` listing the others
+    ///
+    /// CoinGecko silently leaves unsupported currencies out of responses. The supported currencies are fetched once
+    /// and cached, shared between clones of this client.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.validate_currencies(&["usd", "eur"]).await;
+    /// }
+    /// ```
+    pub async fn validate_currencies<Curr: AsRef<str>>(
+        &self,
+        currencies: &[Curr],
+    ) -> Result<(), CoinGeckoError> {
+        let cached = self.supported_vs_currencies.lock().unwrap().clone();
+        let supported = match cached {
+            Some(supported) => supported,
+            None => {
+                let supported = self.supported_vs_currencies().await?;
+                *self.supported_vs_currencies.lock().unwrap() = Some(supported.clone());
+                supported
+            }
+        };
+
+        let unsupported: Vec<_> = currencies
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|currency| {
+                !supported
+                    .iter()
+                    .any(|supported| supported.eq_ignore_ascii_case(currency))
+            })
+            .map(String::from)
+            .collect();
+
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(CoinGeckoError::UnsupportedCurrencies(unsupported))
+        }
+    }
+
+    /// Validates the currencies if the client was built with `strict_currencies`
+    pub(crate) async fn check_currencies<Curr: AsRef<str>>(
+        &self,
+        currencies: &[Curr],
+    ) -> Result<(), CoinGeckoError> {
+        if self.strict_currencies {
+            self.validate_currencies(currencies).await?;
+        }
+        Ok(())
     }
 
     /// List all supported coins id, name and symbol (no pagination required)
@@ -190,15 +948,92 @@ impl CoinGeckoClient {
     ///     client.coins_list(true).await;
     /// }
     /// ```
-    pub async fn coins_list(&self, include_platform: bool) -> Result<Vec<CoinsListItem>, Error> {
-        let req = format!("/coins/list?include_platform={}", include_platform);
-        self.get(&req).await
+    pub async fn coins_list(
+        &self,
+        include_platform: bool,
+    ) -> Result<Vec<CoinsListItem>, CoinGeckoError> {
+        self.get(&endpoints::coins_list(include_platform)).await
+    }
+
+    /// List the 200 coins most recently listed on CoinGecko (Pro API only)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     client.coins_list_new().await;
+    /// }
+    /// ```
+    pub async fn coins_list_new(&self) -> Result<Vec<NewCoin>, CoinGeckoError> {
+        self.get("/coins/list/new").await
+    }
+
+    /// Get the top 30 coins with the largest price gain and loss over a duration (Pro API only)
+    ///
+    /// `top_coins` limits the ranking to coins by market cap rank, CoinGecko considers the top 1000 with `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::TopGainersLosersDuration, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     client.top_gainers_losers("usd", TopGainersLosersDuration::TwentyFourHours, None).await;
+    /// }
+    /// ```
+    pub async fn top_gainers_losers(
+        &self,
+        vs_currency: impl Into<VsCurrency>,
+        duration: TopGainersLosersDuration,
+        top_coins: Option<TopCoins>,
+    ) -> Result<TopGainersLosers, CoinGeckoError> {
+        self.get(&endpoints::top_gainers_losers(
+            vs_currency.into().as_ref(),
+            duration,
+            top_coins,
+        ))
+        .await
+    }
+
+    /// Ids of all coins with the given symbol, matched case-insensitively
+    ///
+    /// CoinGecko expects ids like `bitcoin` rather than symbols like `btc`. Several coins can share a symbol,
+    /// so every candidate is returned, in the order of `coins_list`. Fetches the full coins list on every call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.resolve_coin_id("btc").await;
+    /// }
+    /// ```
+    pub async fn resolve_coin_id(&self, symbol: &str) -> Result<Vec<CoinId>, CoinGeckoError> {
+        let coins = self.coins_list(false).await?;
+
+        Ok(coins
+            .into_iter()
+            .filter(|coin| coin.symbol.eq_ignore_ascii_case(symbol))
+            .map(|coin| CoinId::from(coin.id))
+            .collect())
     }
 
     /// List all supported coins price, market cap, volume, and market related data
     ///
     /// Use this to obtain all the coins market data (price, market cap, volume)
     ///
+    /// Prefer [`CoinsMarketsRequest`](crate::requests::CoinsMarketsRequest), which names every parameter and defaults the optional ones.
+    /// Non-empty `ids` and a `category` fail with `CoinGeckoError::InvalidParam`, as CoinGecko would ignore the ids.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -216,7 +1051,7 @@ impl CoinGeckoClient {
     ///         None,
     ///         MarketsOrder::GeckoDesc,
     ///         1,
-    ///         0,
+    ///         1,
     ///         true,
     ///         &[
     ///             PriceChangePercentage::OneHour,
@@ -231,7 +1066,7 @@ impl CoinGeckoClient {
     /// ```
     pub async fn coins_markets<Id: AsRef<str>>(
         &self,
-        vs_currency: &str,
+        vs_currency: impl Into<VsCurrency>,
         ids: &[Id],
         category: Option<&str>,
         order: MarketsOrder,
@@ -239,45 +1074,27 @@ impl CoinGeckoClient {
         page: i64,
         sparkline: bool,
         price_change_percentage: &[PriceChangePercentage],
-    ) -> Result<Vec<CoinsMarketItem>, Error> {
-        let ids = ids.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    ) -> Result<Vec<CoinsMarketItem>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
 
-        let category = match category {
-            Some(c) => format!("&category={}", c),
-            _ => String::from(""),
-        };
+        let filters = MarketsFilter::from_args(ids, category);
+        let filter = single_filter(&filters)?;
 
-        let order = match order {
-            MarketsOrder::MarketCapDesc => "market_cap_desc",
-            MarketsOrder::MarketCapAsc => "market_cap_asc",
-            MarketsOrder::GeckoDesc => "gecko_desc",
-            MarketsOrder::GeckoAsc => "gecko_asc",
-            MarketsOrder::VolumeDesc => "volume_desc",
-            MarketsOrder::VolumeAsc => "volume_asc",
-            MarketsOrder::IdDesc => "id_desc",
-            MarketsOrder::IdAsc => "id_asc",
-        };
-
-        let price_change_percentage = price_change_percentage.iter().fold(
-            Vec::with_capacity(price_change_percentage.len()),
-            |mut acc, x| {
-                let current = match *x {
-                    PriceChangePercentage::OneHour => "1h",
-                    PriceChangePercentage::TwentyFourHours => "24h",
-                    PriceChangePercentage::SevenDays => "7d",
-                    PriceChangePercentage::FourteenDays => "14d",
-                    PriceChangePercentage::ThirtyDays => "30d",
-                    PriceChangePercentage::TwoHundredDays => "200d",
-                    PriceChangePercentage::OneYear => "1y",
-                };
-
-                acc.push(current);
-                acc
-            },
-        );
+        let vs_currency = vs_currency.into();
+        self.check_currencies(&[&vs_currency]).await?;
 
-        let req = format!("/coins/markets?vs_currency={}&ids={}{}&order={}&per_page={}&page={}&sparkline={}&price_change_percentage={}", vs_currency, ids.join("%2C"), category, order, per_page, page, sparkline, price_change_percentage.join("%2C"));
-        self.get(&req).await
+        self.get(&endpoints::coins_markets(
+            vs_currency.as_ref(),
+            filter,
+            order,
+            per_page,
+            page,
+            sparkline,
+            price_change_percentage,
+            self.locale.as_deref(),
+        ))
+        .await
     }
 
     /// Get current data (name, price, market, ... including exchange tickers) for a coin
@@ -301,16 +1118,24 @@ impl CoinGeckoClient {
     /// ```
     pub async fn coin(
         &self,
-        id: &str,
+        id: impl Into<CoinId>,
         localization: bool,
         tickers: bool,
         market_data: bool,
         community_data: bool,
         developer_data: bool,
         sparkline: bool,
-    ) -> Result<CoinsItem, Error> {
-        let req = format!("/coins/{}?localization={}&tickers={}&market_data={}&community_data={}&developer_data={}&sparkline={}", id, localization, tickers, market_data, community_data, developer_data, sparkline);
-        self.get(&req).await
+    ) -> Result<CoinsItem, CoinGeckoError> {
+        self.get(&endpoints::coin(
+            id.into().as_ref(),
+            localization,
+            tickers,
+            market_data,
+            community_data,
+            developer_data,
+            sparkline,
+        ))
+        .await
     }
 
     /// Get coin tickers (paginated to 100 items)
@@ -333,31 +1158,24 @@ impl CoinGeckoClient {
     /// ```
     pub async fn coin_tickers<Ex: AsRef<str>>(
         &self,
-        id: &str,
+        id: impl Into<CoinId>,
         exchange_ids: Option<&[Ex]>,
         include_exchange_logo: bool,
         page: i64,
         order: TickersOrder,
         depth: bool,
-    ) -> Result<Tickers, Error> {
-        let order = match order {
-            TickersOrder::TrustScoreAsc => "trust_score_asc",
-            TickersOrder::TrustScoreDesc => "trust_score_desc",
-            TickersOrder::VolumeDesc => "volume_desc",
-        };
+    ) -> Result<Tickers, CoinGeckoError> {
+        check_positive("page", page)?;
 
-        let req = match exchange_ids {
-            Some(e_ids) => {
-                let e_ids = e_ids.iter().map(AsRef::as_ref).collect::<Vec<_>>();
-                format!("/coins/{}/tickers?exchange_ids={}&include_exchange_logo={}&page={}&order={}&depth={}", id, e_ids.join("%2C"), include_exchange_logo, &page, order, depth)
-            }
-            None => format!(
-                "/coins/{}/tickers?include_exchange_logo={}&page={}&order={}&depth={}",
-                id, include_exchange_logo, &page, order, depth
-            ),
-        };
-
-        self.get(&req).await
+        self.get(&endpoints::coin_tickers(
+            id.into().as_ref(),
+            exchange_ids,
+            include_exchange_logo,
+            page,
+            order,
+            depth,
+        ))
+        .await
     }
 
     /// Get historical data (name, price, market, stats) at a given date for a coin
@@ -371,63 +1189,108 @@ impl CoinGeckoClient {
     ///     use coingecko::CoinGeckoClient;
     ///     let client = CoinGeckoClient::default();
     ///
-    ///     client.coin_history("bitcoin", NaiveDate::from_ymd(2017, 12, 30), true).await;
+    ///     client.coin_history("bitcoin", NaiveDate::from_ymd_opt(2017, 12, 30).unwrap(), true).await;
     /// }
     /// ```
     pub async fn coin_history(
         &self,
-        id: &str,
+        id: impl Into<CoinId>,
         date: NaiveDate,
         localization: bool,
-    ) -> Result<History, Error> {
-        let formatted_date = date.format("%d-%m-%Y").to_string();
-
-        let req = format!(
-            "/coins/{}/history?date={}&localization={}",
-            id, formatted_date, localization
-        );
-        self.get(&req).await
+    ) -> Result<History, CoinGeckoError> {
+        let endpoint = endpoints::coin_history(id.into().as_ref(), date, localization);
+
+        // the snapshot of a day is final once the day is over
+        if date < Utc::now().date_naive() {
+            self.get_immutable(&endpoint).await
+        } else {
+            self.get(&endpoint).await
+        }
     }
 
     /// Get historical market data include price, market cap, and 24h volume (granularity auto)
     ///
     /// **Minutely data will be used for duration within 1 day, Hourly data will be used for duration between 1 day and 90 days, Daily data will be used for duration above 90 days.**
     ///
+    /// Pass `use_daily_interval` to get daily data points regardless of the duration.
+    /// `days` is a number of days or `ChartDays::Max` for the full history.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// #[tokio::main]
     /// async fn main() {
-    ///     use coingecko::CoinGeckoClient;
+    ///     use coingecko::{params::ChartDays, CoinGeckoClient};
     ///     let client = CoinGeckoClient::default();
     ///
-    ///     client.coin_market_chart("bitcoin", "usd", 1, true).await;
+    ///     client.coin_market_chart("bitcoin", "usd", 1, true, None).await;
+    ///     client.coin_market_chart("bitcoin", "usd", ChartDays::Max, true, None).await;
     /// }
     /// ```
     pub async fn coin_market_chart(
         &self,
-        id: &str,
-        vs_currency: &str,
-        days: i64,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        days: impl Into<ChartDays>,
         use_daily_interval: bool,
-    ) -> Result<MarketChart, Error> {
-        let req = match use_daily_interval {
-            true => format!(
-                "/coins/{}/market_chart?vs_currency={}&days={}",
-                id, vs_currency, days
-            ),
-            false => format!(
-                "/coins/{}/market_chart?vs_currency={}&days={}&interval=daily",
-                id, vs_currency, days
-            ),
-        };
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::coin_market_chart(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            days,
+            use_daily_interval,
+            precision.as_deref(),
+        ))
+        .await
+    }
 
-        self.get(&req).await
+    /// Get historical market data include price, market cap, and 24h volume with an explicit granularity
+    ///
+    /// `Interval::Auto` leaves the granularity to CoinGecko, see [`Interval`] for which plans support which interval.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::Interval, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.coin_market_chart_with_interval("bitcoin", "usd", 30, Interval::Daily, None).await;
+    /// }
+    /// ```
+    pub async fn coin_market_chart_with_interval(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        days: impl Into<ChartDays>,
+        interval: Interval,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::coin_market_chart_with_interval(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            days,
+            interval,
+            precision.as_deref(),
+        ))
+        .await
     }
 
-    /// Get historical market data include price, market cap, and 24h volume within a range of timestamp (granularity auto)
+    /// Get historical market data include price, market cap, and 24h volume within a range of timestamp
     ///
-    /// - **Data granularity is automatic (cannot be adjusted)**
+    /// Without an `interval` the granularity is picked from the range:
     /// - **1 day from query time = 5 minute interval data**
     /// - **1 - 90 days from query time = hourly data**
     /// - **above 90 days from query time = daily data (00:00 UTC)**
@@ -441,27 +1304,94 @@ impl CoinGeckoClient {
     ///     use coingecko::CoinGeckoClient;
     ///     let client = CoinGeckoClient::default();
     ///
-    ///     let from = NaiveDate::from_ymd(2014, 2, 16).and_hms(19, 0, 32);
-    ///     let to = NaiveDate::from_ymd(2015, 1, 30).and_hms(0, 20, 32);
+    ///     let from = NaiveDate::from_ymd_opt(2014, 2, 16).unwrap().and_hms_opt(19, 0, 32).unwrap();
+    ///     let to = NaiveDate::from_ymd_opt(2015, 1, 30).unwrap().and_hms_opt(0, 20, 32).unwrap();
     ///
-    ///     client.coin_market_chart_range("bitcoin", "usd", from, to).await;
+    ///     client.coin_market_chart_range("bitcoin", "usd", from, to, None, None).await;
     /// }
     /// ```
     pub async fn coin_market_chart_range(
         &self,
-        id: &str,
-        vs_currency: &str,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
         from: NaiveDateTime,
         to: NaiveDateTime,
-    ) -> Result<MarketChart, Error> {
-        let from_unix_timestamp = from.timestamp();
-        let to_unix_timestamp = to.timestamp();
+        interval: Option<Interval>,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        check_range(from, to)?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::coin_market_chart_range(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            from,
+            to,
+            interval,
+            precision.as_deref(),
+        ))
+        .await
+    }
 
-        let req = format!(
-            "/coins/{}/market_chart/range?vs_currency={}&from={}&to={}",
-            id, vs_currency, from_unix_timestamp, to_unix_timestamp
-        );
-        self.get(&req).await
+    /// Get the historical circulating supply of a coin (Pro API only)
+    ///
+    /// `days` is a number of days or `ChartDays::Max` for the full history.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     client.coin_circulating_supply_chart("bitcoin", 30).await;
+    /// }
+    /// ```
+    pub async fn coin_circulating_supply_chart(
+        &self,
+        id: impl Into<CoinId>,
+        days: impl Into<ChartDays>,
+    ) -> Result<CirculatingSupplyChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        self.get(&endpoints::coin_circulating_supply_chart(
+            id.into().as_ref(),
+            days,
+        ))
+        .await
+    }
+
+    /// Get the historical total supply of a coin (Pro API only)
+    ///
+    /// `days` is a number of days or `ChartDays::Max` for the full history.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::ChartDays, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     client.coin_total_supply_chart("bitcoin", ChartDays::Max).await;
+    /// }
+    /// ```
+    pub async fn coin_total_supply_chart(
+        &self,
+        id: impl Into<CoinId>,
+        days: impl Into<ChartDays>,
+    ) -> Result<TotalSupplyChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        self.get(&endpoints::coin_total_supply_chart(
+            id.into().as_ref(),
+            days,
+        ))
+        .await
     }
 
     /// Get coin's OHLC
@@ -471,6 +1401,8 @@ impl CoinGeckoClient {
     /// 3 - 30 days: 4 hours
     /// 31 and before: 4 days
     ///
+    /// `OhlcDays::Max` and custom days require a Pro API key.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -483,25 +1415,60 @@ impl CoinGeckoClient {
     /// ```
     pub async fn coin_ohlc(
         &self,
-        id: &str,
-        vs_currency: &str,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
         days: OhlcDays,
-    ) -> Result<Vec<Vec<f64>>, Error> {
-        let days = match days {
-            OhlcDays::OneDay => 1,
-            OhlcDays::SevenDays => 7,
-            OhlcDays::FourteenDays => 14,
-            OhlcDays::ThirtyDays => 30,
-            OhlcDays::NinetyDays => 90,
-            OhlcDays::OneHundredEightyDays => 180,
-            OhlcDays::ThreeHundredSixtyFiveDays => 365,
-        };
+    ) -> Result<Vec<Ohlc>, CoinGeckoError> {
+        if !self.is_pro() {
+            days.check_free_tier()?;
+        }
 
-        let req = format!(
-            "/coins/{}/ohlc?vs_currency={}&days={}",
-            id, vs_currency, days
-        );
-        self.get(&req).await
+        self.get(&endpoints::coin_ohlc(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            days,
+        ))
+        .await
+    }
+
+    /// Get coin's OHLC between two dates (Pro API only)
+    ///
+    /// `from` and `to` may be at most 180 days apart for daily candles and 31 days for hourly ones.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use chrono::NaiveDate;
+    ///     use coingecko::{params::OhlcInterval, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    ///     let to = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    ///
+    ///     client.coin_ohlc_range("bitcoin", "usd", from, to, OhlcInterval::Daily).await;
+    /// }
+    /// ```
+    pub async fn coin_ohlc_range(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currency: impl Into<VsCurrency>,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        interval: OhlcInterval,
+    ) -> Result<Vec<Ohlc>, CoinGeckoError> {
+        check_range(from, to)?;
+        interval.check_span(from, to)?;
+
+        self.get(&endpoints::coin_ohlc_range(
+            id.into().as_ref(),
+            vs_currency.into().as_ref(),
+            from,
+            to,
+            interval,
+        ))
+        .await
     }
 
     /// Get coin info from contract address
@@ -511,16 +1478,20 @@ impl CoinGeckoClient {
     /// ```rust
     /// #[tokio::main]
     /// async fn main() {
-    ///     use coingecko::CoinGeckoClient;
+    ///     use coingecko::{params::AssetPlatformId, CoinGeckoClient};
     ///     let client = CoinGeckoClient::default();
     ///     let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
     ///
-    ///     client.contract("ethereum", &uniswap_contract).await;
+    ///     client.contract(AssetPlatformId::Ethereum, &uniswap_contract).await;
     /// }
     /// ```
-    pub async fn contract(&self, id: &str, contract_address: &str) -> Result<Contract, Error> {
-        let req = format!("/coins/{}/contract/{}", id, contract_address);
-        self.get(&req).await
+    pub async fn contract(
+        &self,
+        id: impl Into<AssetPlatformId>,
+        contract_address: &str,
+    ) -> Result<Contract, CoinGeckoError> {
+        self.get(&endpoints::contract(id.into().as_ref(), contract_address))
+            .await
     }
 
     /// Get historical market data include price, market cap, and 24h volume (granularity auto)
@@ -530,28 +1501,39 @@ impl CoinGeckoClient {
     /// ```rust
     /// #[tokio::main]
     /// async fn main() {
-    ///     use coingecko::CoinGeckoClient;
+    ///     use coingecko::{params::Precision, CoinGeckoClient};
     ///     let client = CoinGeckoClient::default();
     ///     let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
     ///
-    ///     client.contract_market_chart("ethereum", &uniswap_contract, "usd", 1).await;
+    ///     client.contract_market_chart("ethereum", &uniswap_contract, "usd", 1, Some(Precision::Full)).await;
     /// }
     /// ```
     pub async fn contract_market_chart(
         &self,
-        id: &str,
+        id: impl Into<AssetPlatformId>,
         contract_address: &str,
-        vs_currency: &str,
-        days: i64,
-    ) -> Result<MarketChart, Error> {
-        let req = format!(
-            "/coins/{}/contract/{}/market_chart/?vs_currency={}&days={}",
-            id, contract_address, vs_currency, days
-        );
-        self.get(&req).await
+        vs_currency: impl Into<VsCurrency>,
+        days: impl Into<ChartDays>,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        let days = days.into();
+        days.check()?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::contract_market_chart(
+            id.into().as_ref(),
+            contract_address,
+            vs_currency.into().as_ref(),
+            days,
+            precision.as_deref(),
+        ))
+        .await
     }
 
-    /// Get historical market data include price, market cap, and 24h volume within a range of timestamp (granularity auto)
+    /// Get historical market data include price, market cap, and 24h volume within a range of timestamp
+    ///
+    /// Without an `interval` the granularity is picked from the range, see `coin_market_chart_range`.
     ///
     /// # Examples
     ///
@@ -559,32 +1541,40 @@ impl CoinGeckoClient {
     /// #[tokio::main]
     /// async fn main() {
     ///     use chrono::NaiveDate;
-    ///     use coingecko::CoinGeckoClient;
+    ///     use coingecko::{params::Precision, CoinGeckoClient};
     ///     let client = CoinGeckoClient::default();
     ///     let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
     ///
-    ///     let from = NaiveDate::from_ymd(2014, 2, 16).and_hms(19, 0, 32);
-    ///     let to = NaiveDate::from_ymd(2015, 1, 30).and_hms(0, 20, 32);
+    ///     let from = NaiveDate::from_ymd_opt(2014, 2, 16).unwrap().and_hms_opt(19, 0, 32).unwrap();
+    ///     let to = NaiveDate::from_ymd_opt(2015, 1, 30).unwrap().and_hms_opt(0, 20, 32).unwrap();
     ///
-    ///     client.contract_market_chart_range("ethereum", &uniswap_contract, "usd", from, to).await;
+    ///     client.contract_market_chart_range("ethereum", &uniswap_contract, "usd", from, to, None, Some(Precision::Full)).await;
     /// }
     /// ```
     pub async fn contract_market_chart_range(
         &self,
-        id: &str,
+        id: impl Into<AssetPlatformId>,
         contract_address: &str,
-        vs_currency: &str,
+        vs_currency: impl Into<VsCurrency>,
         from: NaiveDateTime,
         to: NaiveDateTime,
-    ) -> Result<MarketChart, Error> {
-        let from_unix_timestamp = from.timestamp();
-        let to_unix_timestamp = to.timestamp();
-
-        let req = format!(
-            "/coins/{}/contract/{}/market_chart/range?vs_currency={}&from={}&to={}",
-            id, contract_address, vs_currency, from_unix_timestamp, to_unix_timestamp
-        );
-        self.get(&req).await
+        interval: Option<Interval>,
+        precision: Option<Precision>,
+    ) -> Result<MarketChart, CoinGeckoError> {
+        check_range(from, to)?;
+
+        let precision = precision.map(Precision::param).transpose()?;
+
+        self.get(&endpoints::contract_market_chart_range(
+            id.into().as_ref(),
+            contract_address,
+            vs_currency.into().as_ref(),
+            from,
+            to,
+            interval,
+            precision.as_deref(),
+        ))
+        .await
     }
 
     /// List all asset platforms (Blockchain networks)
@@ -600,8 +1590,28 @@ impl CoinGeckoClient {
     ///     client.asset_platforms().await;
     /// }
     /// ```
-    pub async fn asset_platforms(&self) -> Result<Vec<AssetPlatform>, Error> {
-        self.get("/asset_platforms").await
+    pub async fn asset_platforms(&self) -> Result<Vec<AssetPlatform>, CoinGeckoError> {
+        self.asset_platforms_filtered(None).await
+    }
+
+    /// List asset platforms (Blockchain networks), e.g. only the ones supporting NFTs
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::AssetPlatformFilter, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.asset_platforms_filtered(Some(AssetPlatformFilter::Nft)).await;
+    /// }
+    /// ```
+    pub async fn asset_platforms_filtered(
+        &self,
+        filter: Option<AssetPlatformFilter>,
+    ) -> Result<Vec<AssetPlatform>, CoinGeckoError> {
+        self.get(&endpoints::asset_platforms(filter)).await
     }
 
     /// List all categories
@@ -617,7 +1627,7 @@ impl CoinGeckoClient {
     ///     client.categories_list().await;
     /// }
     /// ```
-    pub async fn categories_list(&self) -> Result<Vec<CategoryId>, Error> {
+    pub async fn categories_list(&self) -> Result<Vec<CategoryId>, CoinGeckoError> {
         self.get("/coins/categories/list").await
     }
 
@@ -634,12 +1644,15 @@ impl CoinGeckoClient {
     ///     client.categories().await;
     /// }
     /// ```
-    pub async fn categories(&self) -> Result<Vec<Category>, Error> {
+    pub async fn categories(&self) -> Result<Vec<Category>, CoinGeckoError> {
         self.get("/coins/categories").await
     }
 
     /// List all exchanges
     ///
+    /// Exchanges are ranked by `trust_score_rank`, the endpoint takes no parameter to order them differently,
+    /// see `exchanges_by_volume` for a ranking by volume.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -651,9 +1664,46 @@ impl CoinGeckoClient {
     ///     client.exchanges(10, 1).await;
     /// }
     /// ```
-    pub async fn exchanges(&self, per_page: i64, page: i64) -> Result<Vec<Exchange>, Error> {
-        let req = format!("/exchanges?per_page={}&page={}", per_page, page);
-        self.get(&req).await
+    pub async fn exchanges(
+        &self,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<Exchange>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::exchanges(per_page, page)).await
+    }
+
+    /// List all exchanges ranked by `trade_volume24_h_btc`, the highest first
+    ///
+    /// `/exchanges` has no parameter to order by volume, so this fetches every page, 250 exchanges at a time,
+    /// and sorts them. Exchanges without a volume come last. Use `exchanges` for the ranking by trust score.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.exchanges_by_volume().await;
+    /// }
+    /// ```
+    pub async fn exchanges_by_volume(&self) -> Result<Vec<Exchange>, CoinGeckoError> {
+        let mut exchanges = Vec::new();
+        for page in 1.. {
+            let items = self.exchanges(EXCHANGES_PER_PAGE, page).await?;
+            let last = items.len() < EXCHANGES_PER_PAGE as usize;
+            exchanges.extend(items);
+            if last {
+                break;
+            }
+        }
+
+        sort_by_volume(&mut exchanges);
+        Ok(exchanges)
     }
 
     /// List all supported markets id and name (no pagination required)
@@ -671,8 +1721,30 @@ impl CoinGeckoClient {
     ///     client.exchanges_list().await;
     /// }
     /// ```
-    pub async fn exchanges_list(&self) -> Result<Vec<ExchangeId>, Error> {
-        self.get("/exchanges/list").await
+    pub async fn exchanges_list(&self) -> Result<Vec<ExchangeId>, CoinGeckoError> {
+        self.exchanges_list_filtered(None).await
+    }
+
+    /// List the id and name of the markets with a status, e.g. only the active ones
+    ///
+    /// CoinGecko lists the active markets when no status is given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::ExchangeStatus, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.exchanges_list_filtered(Some(ExchangeStatus::Inactive)).await;
+    /// }
+    /// ```
+    pub async fn exchanges_list_filtered(
+        &self,
+        status: Option<ExchangeStatus>,
+    ) -> Result<Vec<ExchangeId>, CoinGeckoError> {
+        self.get(&endpoints::exchanges_list(status)).await
     }
 
     /// Get exchange volume in BTC and top 100 tickers only
@@ -694,9 +1766,8 @@ impl CoinGeckoClient {
     ///     client.exchange("binance").await;
     /// }
     /// ```
-    pub async fn exchange(&self, id: &str) -> Result<Exchange, Error> {
-        let req = format!("/exchanges/{}", id);
-        self.get(&req).await
+    pub async fn exchange(&self, id: &str) -> Result<Exchange, CoinGeckoError> {
+        self.get(&endpoints::exchange(id)).await
     }
 
     /// Get exchange tickers (paginated)
@@ -725,25 +1796,18 @@ impl CoinGeckoClient {
         page: i64,
         order: TickersOrder,
         depth: bool,
-    ) -> Result<Tickers, Error> {
-        let order = match order {
-            TickersOrder::TrustScoreAsc => "trust_score_asc",
-            TickersOrder::TrustScoreDesc => "trust_score_desc",
-            TickersOrder::VolumeDesc => "volume_desc",
-        };
-
-        let req = match coin_ids {
-            Some(c_ids) => {
-                let c_ids = c_ids.iter().map(AsRef::as_ref).collect::<Vec<_>>();
-                format!("/exchanges/{}/tickers?coin_ids={}&include_exchange_logo={}&page={}&order={}&depth={}", id, c_ids.join("%2C"), include_exchange_logo, &page, order, depth)
-            }
-            None => format!(
-                "/exchanges/{}/tickers?include_exchange_logo={}&page={}&order={}&depth={}",
-                id, include_exchange_logo, &page, order, depth
-            ),
-        };
+    ) -> Result<Tickers, CoinGeckoError> {
+        check_positive("page", page)?;
 
-        self.get(&req).await
+        self.get(&endpoints::exchange_tickers(
+            id,
+            coin_ids,
+            include_exchange_logo,
+            page,
+            order,
+            depth,
+        ))
+        .await
     }
 
     /// Get status updates for a given exchange
@@ -764,13 +1828,12 @@ impl CoinGeckoClient {
         id: &str,
         per_page: i64,
         page: i64,
-    ) -> Result<StatusUpdates, Error> {
-        let req = format!(
-            "/exchanges/{}/status_updates?per_page={}&page={}",
-            id, per_page, page,
-        );
+    ) -> Result<StatusUpdates, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
 
-        self.get(&req).await
+        self.get(&endpoints::exchange_status_updates(id, per_page, page))
+            .await
     }
 
     /// Get volume_chart data for a given exchange
@@ -790,9 +1853,39 @@ impl CoinGeckoClient {
         &self,
         id: &str,
         days: i64,
-    ) -> Result<Vec<VolumeChartData>, Error> {
-        let req = format!("/exchanges/{}/volume_chart?days={}", id, days);
-        self.get(&req).await
+    ) -> Result<Vec<VolumeChartData>, CoinGeckoError> {
+        check_positive("days", days)?;
+
+        self.get(&endpoints::exchange_volume_chart(id, days)).await
+    }
+
+    /// Get volume_chart data for a given exchange within a range of timestamp (Pro API only)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use chrono::NaiveDate;
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    ///     let to = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    ///
+    ///     client.exchange_volume_chart_range("binance", from, to).await;
+    /// }
+    /// ```
+    pub async fn exchange_volume_chart_range(
+        &self,
+        id: &str,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<VolumeChartData>, CoinGeckoError> {
+        check_range(from, to)?;
+
+        self.get(&endpoints::exchange_volume_chart_range(id, from, to))
+            .await
     }
 
     /// List all finance platforms
@@ -812,10 +1905,12 @@ impl CoinGeckoClient {
         &self,
         per_page: i64,
         page: i64,
-    ) -> Result<Vec<FinancePlatform>, Error> {
-        let req = format!("/finance_platforms?per_page={}&page={}", per_page, page,);
+    ) -> Result<Vec<FinancePlatform>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
 
-        self.get(&req).await
+        self.get(&endpoints::finance_platforms(per_page, page))
+            .await
     }
 
     /// List all finance products
@@ -835,10 +1930,11 @@ impl CoinGeckoClient {
         &self,
         per_page: i64,
         page: i64,
-    ) -> Result<Vec<FinanceProduct>, Error> {
-        let req = format!("/finance_products?per_page={}&page={}", per_page, page,);
+    ) -> Result<Vec<FinanceProduct>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
 
-        self.get(&req).await
+        self.get(&endpoints::finance_products(per_page, page)).await
     }
 
     /// List all market indexes
@@ -854,10 +1950,11 @@ impl CoinGeckoClient {
     ///     client.indexes(10, 1).await;
     /// }
     /// ```
-    pub async fn indexes(&self, per_page: i64, page: i64) -> Result<Vec<Index>, Error> {
-        let req = format!("/indexes?per_page={}&page={}", per_page, page,);
+    pub async fn indexes(&self, per_page: i64, page: i64) -> Result<Vec<Index>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
 
-        self.get(&req).await
+        self.get(&endpoints::indexes(per_page, page)).await
     }
 
     /// Get market index by market id and index id
@@ -873,9 +1970,12 @@ impl CoinGeckoClient {
     ///     client.indexes_market_id("binance_futures", "BTC").await;
     /// }
     /// ```
-    pub async fn indexes_market_id(&self, market_id: &str, id: &str) -> Result<MarketIndex, Error> {
-        let req = format!("/indexes/{}/{}", market_id, id);
-        self.get(&req).await
+    pub async fn indexes_market_id(
+        &self,
+        market_id: &str,
+        id: &str,
+    ) -> Result<MarketIndex, CoinGeckoError> {
+        self.get(&endpoints::indexes_market_id(market_id, id)).await
     }
 
     /// List market indexes id and name
@@ -891,7 +1991,7 @@ impl CoinGeckoClient {
     ///     client.indexes_list().await;
     /// }
     /// ```
-    pub async fn indexes_list(&self) -> Result<Vec<IndexId>, Error> {
+    pub async fn indexes_list(&self) -> Result<Vec<IndexId>, CoinGeckoError> {
         self.get("/indexes/list").await
     }
 
@@ -911,17 +2011,8 @@ impl CoinGeckoClient {
     pub async fn derivatives(
         &self,
         include_tickers: Option<DerivativesIncludeTickers>,
-    ) -> Result<Vec<Derivative>, Error> {
-        let include_tickers = match include_tickers {
-            Some(ic_enum) => match ic_enum {
-                DerivativesIncludeTickers::All => "all",
-                DerivativesIncludeTickers::Unexpired => "unexpired",
-            },
-            None => "unexpired",
-        };
-
-        let req = format!("/derivatives?include_tickers={}", include_tickers);
-        self.get(&req).await
+    ) -> Result<Vec<Derivative>, CoinGeckoError> {
+        self.get(&endpoints::derivatives(include_tickers)).await
     }
 
     /// List all derivative exchanges
@@ -942,21 +2033,12 @@ impl CoinGeckoClient {
         order: DerivativeExchangeOrder,
         per_page: i64,
         page: i64,
-    ) -> Result<Vec<Derivative>, Error> {
-        let order = match order {
-            DerivativeExchangeOrder::NameAsc => "name_asc",
-            DerivativeExchangeOrder::NameDesc => "name_desc",
-            DerivativeExchangeOrder::OpenInterestBtcAsc => "open_interest_btc_asc",
-            DerivativeExchangeOrder::OpenInterestBtcDesc => "open_interest_btc_desc",
-            DerivativeExchangeOrder::TradeVolume24hBtcAsc => "trade_volume_24h_btc_asc",
-            DerivativeExchangeOrder::TradeVolume24hBtcDesc => "trade_volume_24h_btc_desc",
-        };
+    ) -> Result<Vec<Derivative>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
 
-        let req = format!(
-            "/derivatives/exchanges?order={}&per_page={}&page={}",
-            order, per_page, page
-        );
-        self.get(&req).await
+        self.get(&endpoints::derivative_exchanges(order, per_page, page))
+            .await
     }
 
     /// Show derivative exchange data
@@ -976,20 +2058,9 @@ impl CoinGeckoClient {
         &self,
         id: &str,
         include_tickers: Option<DerivativesIncludeTickers>,
-    ) -> Result<Vec<Derivative>, Error> {
-        let include_tickers = match include_tickers {
-            Some(ic_enum) => match ic_enum {
-                DerivativesIncludeTickers::All => "all",
-                DerivativesIncludeTickers::Unexpired => "unexpired",
-            },
-            None => "unexpired",
-        };
-
-        let req = format!(
-            "/derivatives/exchanges/{}?include_tickers={}",
-            id, include_tickers
-        );
-        self.get(&req).await
+    ) -> Result<DerivativeExchangeDetail, CoinGeckoError> {
+        self.get(&endpoints::derivatives_exchange(id, include_tickers))
+            .await
     }
 
     /// List all derivative exchanges name and identifier
@@ -1005,10 +2076,136 @@ impl CoinGeckoClient {
     ///     client.derivative_exchanges_list().await;
     /// }
     /// ```
-    pub async fn derivative_exchanges_list(&self) -> Result<Vec<DerivativeExchangeId>, Error> {
+    pub async fn derivative_exchanges_list(
+        &self,
+    ) -> Result<Vec<DerivativeExchangeId>, CoinGeckoError> {
         self.get("/derivatives/exchanges/list").await
     }
 
+    /// List all supported NFT ids with their contract address and asset platform
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::NftsOrder, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.nfts_list(Some(NftsOrder::MarketCapUsdDesc), 100, 1).await;
+    /// }
+    /// ```
+    pub async fn nfts_list(
+        &self,
+        order: Option<NftsOrder>,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<NftId>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::nfts_list(order, per_page, page)).await
+    }
+
+    /// List NFT collections with their floor price, market cap and 24h volume, ranked by `order` (Pro API only)
+    ///
+    /// Prices are given in the native currency of each collection's platform and in usd.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::NftsOrder, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     client.nfts_markets(None, Some(NftsOrder::MarketCapUsdDesc), 100, 1).await;
+    /// }
+    /// ```
+    pub async fn nfts_markets(
+        &self,
+        asset_platform_id: Option<AssetPlatformId>,
+        order: Option<NftsOrder>,
+        per_page: i64,
+        page: i64,
+    ) -> Result<Vec<NftMarketItem>, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::nfts_markets(
+            asset_platform_id.as_ref().map(AsRef::as_ref),
+            order,
+            per_page,
+            page,
+        ))
+        .await
+    }
+
+    /// Get current data (name, floor price, 24h volume, etc.) for an NFT collection, floor prices are given in the native currency and usd
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.nft("pudgy-penguins").await;
+    /// }
+    /// ```
+    pub async fn nft(&self, id: &str) -> Result<Nft, CoinGeckoError> {
+        self.get(&endpoints::nft(id)).await
+    }
+
+    /// Get current data (name, floor price, 24h volume, etc.) for an NFT collection by its contract address
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.nft_contract("ethereum", "0xbd3531da5cf5857e7cfaa92426877b022e612cf8").await;
+    /// }
+    /// ```
+    pub async fn nft_contract(
+        &self,
+        asset_platform_id: impl Into<AssetPlatformId>,
+        contract_address: &str,
+    ) -> Result<Nft, CoinGeckoError> {
+        self.get(&endpoints::nft_contract(
+            asset_platform_id.into().as_ref(),
+            contract_address,
+        ))
+        .await
+    }
+
+    /// Get historical floor price, market cap and 24h volume of an NFT collection in the native currency and usd (Pro API only)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.nft_market_chart("pudgy-penguins", 30).await;
+    /// }
+    /// ```
+    pub async fn nft_market_chart(
+        &self,
+        id: &str,
+        days: i64,
+    ) -> Result<NftMarketChart, CoinGeckoError> {
+        check_positive("days", days)?;
+
+        self.get(&endpoints::nft_market_chart(id, days)).await
+    }
+
     /// List all status_updates with data (description, category, created_at, user, user_title and pin)
     ///
     /// # Examples
@@ -1028,23 +2225,17 @@ impl CoinGeckoClient {
         project_type: Option<&str>,
         per_page: i64,
         page: i64,
-    ) -> Result<StatusUpdates, Error> {
-        let mut params: Vec<String> = Vec::with_capacity(4);
-
-        if let Some(c) = category {
-            params.push(format!("category={}", c));
-        }
-
-        if let Some(t) = project_type {
-            params.push(format!("project_type={}", t));
-        }
-
-        params.push(per_page.to_string());
-        params.push(page.to_string());
-
-        let req = format!("/status_updates?{}", params.join("&"));
-
-        self.get(&req).await
+    ) -> Result<StatusUpdates, CoinGeckoError> {
+        check_positive("per_page", per_page)?;
+        check_positive("page", page)?;
+
+        self.get(&endpoints::status_updates(
+            category,
+            project_type,
+            per_page,
+            page,
+        ))
+        .await
     }
 
     /// Get events, paginated by 100
@@ -1058,8 +2249,8 @@ impl CoinGeckoClient {
     ///     use coingecko::CoinGeckoClient;
     ///     let client = CoinGeckoClient::default();
     ///
-    ///     let from = NaiveDate::from_ymd(2021, 10, 7);
-    ///     let to = NaiveDate::from_ymd(2022, 10, 7);
+    ///     let from = NaiveDate::from_ymd_opt(2021, 10, 7).unwrap();
+    ///     let to = NaiveDate::from_ymd_opt(2022, 10, 7).unwrap();
     ///
     ///     client.events(Some("HK"), Some("Event"), 1, true, from, to).await;
     /// }
@@ -1072,30 +2263,18 @@ impl CoinGeckoClient {
         upcoming_events_only: bool,
         from_date: NaiveDate,
         to_date: NaiveDate,
-    ) -> Result<Events, Error> {
-        let mut params: Vec<String> = Vec::with_capacity(2);
-
-        if let Some(c) = country_code {
-            params.push(format!("country_code={}", c));
-        }
+    ) -> Result<Events, CoinGeckoError> {
+        check_positive("page", page)?;
 
-        if let Some(t) = event_type {
-            params.push(format!("type={}", t));
-        }
-
-        let from_date = from_date.format("%Y-%m-%d").to_string();
-        let to_date = to_date.format("%Y-%m-%d").to_string();
-
-        let req = format!(
-            "/events?{}&page={}&upcoming_events_only={}&from_date={}&to_date={}",
-            params.join("&"),
+        self.get(&endpoints::events(
+            country_code,
+            event_type,
             page,
             upcoming_events_only,
             from_date,
             to_date,
-        );
-
-        self.get(&req).await
+        ))
+        .await
     }
 
     /// Get list of event countries
@@ -1111,8 +2290,8 @@ impl CoinGeckoClient {
     ///     client.event_countries().await;
     /// }
     /// ```
-    pub async fn event_countries(&self) -> Result<EventCountries, Error> {
-        self.get("/events/types").await
+    pub async fn event_countries(&self) -> Result<EventCountries, CoinGeckoError> {
+        self.get("/events/countries").await
     }
 
     /// Get list of event types
@@ -1128,7 +2307,7 @@ impl CoinGeckoClient {
     ///     client.event_types().await;
     /// }
     /// ```
-    pub async fn event_types(&self) -> Result<EventTypes, Error> {
+    pub async fn event_types(&self) -> Result<EventTypes, CoinGeckoError> {
         self.get("/events/types").await
     }
 
@@ -1145,8 +2324,35 @@ impl CoinGeckoClient {
     ///     client.exchange_rates().await;
     /// }
     /// ```
-    pub async fn exchange_rates(&self) -> Result<ExchangeRates, Error> {
-        self.get("/exchange_rates").await
+    pub async fn exchange_rates(&self) -> Result<ExchangeRates, CoinGeckoError> {
+        let cache = self.reference_cache.as_ref();
+        if let Some(cached) = cache.and_then(|cache| cache.exchange_rates.get()) {
+            return Ok(cached);
+        }
+
+        let rates: ExchangeRates = self.get("/exchange_rates").await?;
+        if let Some(cache) = cache {
+            cache.exchange_rates.set(rates.clone());
+        }
+
+        Ok(rates)
+    }
+
+    /// Search for coins, categories, exchanges and NFTs matching a query
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.search("wrapped bitcoin").await;
+    /// }
+    /// ```
+    pub async fn search(&self, query: &str) -> Result<SearchResults, CoinGeckoError> {
+        self.get(&endpoints::search(query)).await
     }
 
     /// Top-7 trending coins on CoinGecko as searched by users in the last 24 hours (Ordered by most popular first)
@@ -1162,7 +2368,7 @@ impl CoinGeckoClient {
     ///     client.trending().await;
     /// }
     /// ```
-    pub async fn trending(&self) -> Result<Trending, Error> {
+    pub async fn trending(&self) -> Result<Trending, CoinGeckoError> {
         self.get("/search/trending").await
     }
 
@@ -1179,7 +2385,7 @@ impl CoinGeckoClient {
     ///     client.global().await;
     /// }
     /// ```
-    pub async fn global(&self) -> Result<Global, Error> {
+    pub async fn global(&self) -> Result<Global, CoinGeckoError> {
         self.get("/global").await
     }
 
@@ -1196,10 +2402,39 @@ impl CoinGeckoClient {
     ///     client.global_defi().await;
     /// }
     /// ```
-    pub async fn global_defi(&self) -> Result<GlobalDefi, Error> {
+    pub async fn global_defi(&self) -> Result<GlobalDefi, CoinGeckoError> {
         self.get("/global/decentralized_finance_defi").await
     }
 
+    /// Get historical global market cap and volume data (Pro API only)
+    ///
+    /// Without a Pro API key CoinGecko rejects the request, which fails with `CoinGeckoError::Unauthorized`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::new_with_pro_key("CG-xxxx");
+    ///
+    ///     client.global_market_cap_chart(30, "usd").await;
+    /// }
+    /// ```
+    pub async fn global_market_cap_chart(
+        &self,
+        days: i64,
+        vs_currency: impl Into<VsCurrency>,
+    ) -> Result<GlobalMarketCapChart, CoinGeckoError> {
+        check_positive("days", days)?;
+
+        self.get(&endpoints::global_market_cap_chart(
+            days,
+            vs_currency.into().as_ref(),
+        ))
+        .await
+    }
+
     /// Get public companies bitcoin or ethereum holdings (Ordered by total holdings descending)
     ///
     /// # Examples
@@ -1216,12 +2451,83 @@ impl CoinGeckoClient {
     pub async fn companies(
         &self,
         coin_id: CompaniesCoinId,
-    ) -> Result<CompaniesPublicTreasury, Error> {
-        let req = match coin_id {
-            CompaniesCoinId::Bitcoin => "/companies/public_treasury/bitcoin".to_string(),
-            CompaniesCoinId::Ethereum => "/companies/public_treasury/ethereum".to_string(),
-        };
+    ) -> Result<CompaniesPublicTreasury, CoinGeckoError> {
+        self.get(&endpoints::companies(coin_id)).await
+    }
 
-        self.get(&req).await
+    /// Get public companies holdings of any coin CoinGecko tracks treasuries for (Ordered by total holdings descending)
+    ///
+    /// Prefer `companies` for bitcoin and ethereum, this takes the coin id of coins supported later on.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     client.companies_by_id("bitcoin").await;
+    /// }
+    /// ```
+    pub async fn companies_by_id(
+        &self,
+        coin_id: impl Into<CoinId>,
+    ) -> Result<CompaniesPublicTreasury, CoinGeckoError> {
+        self.get(&endpoints::companies_by_id(coin_id.into().as_ref()))
+            .await
     }
 }
+
+/// Runs the futures with at most `limit` in flight at a time, returning their outputs in order
+///
+/// Works like `StreamExt::buffered`, which the batch streams use, but without `futures`,
+/// a dependency only with the `stream` feature.
+pub(crate) async fn join_bounded<F: Future>(
+    futures: impl IntoIterator<Item = F>,
+    limit: usize,
+) -> Vec<F::Output> {
+    let limit = limit.max(1);
+    let mut futures = futures
+        .into_iter()
+        .map(|future| Some(Box::pin(future)))
+        .collect::<Vec<_>>();
+    let mut outputs = futures.iter().map(|_| None).collect::<Vec<_>>();
+
+    future::poll_fn(|cx| {
+        // futures are only started once polled, so skipping those past the limit keeps them waiting
+        let mut in_flight = 0;
+        for (slot, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if in_flight == limit {
+                break;
+            }
+            if let Some(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(value) => {
+                        *output = Some(value);
+                        *slot = None;
+                    }
+                    Poll::Pending => in_flight += 1,
+                }
+            }
+        }
+
+        if in_flight == 0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    outputs.into_iter().flatten().collect()
+}
+
+/// Sorts exchanges by `trade_volume24_h_btc`, the highest first and those without a volume last
+pub(crate) fn sort_by_volume(exchanges: &mut [Exchange]) {
+    exchanges.sort_by(|a, b| {
+        b.trade_volume24_h_btc
+            .partial_cmp(&a.trade_volume24_h_btc)
+            .unwrap_or(Ordering::Equal)
+    });
+}
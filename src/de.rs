@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use serde::{de, Deserialize, Deserializer};
+
+use crate::response::common::Amount;
+
+/// Amount sent either as a JSON number or as a numeric string
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum StringOrNumber {
+    Number(Amount),
+    String(String),
+}
+
+impl StringOrNumber {
+    pub(crate) fn into_amount<E: de::Error>(self) -> Result<Amount, E> {
+        match self {
+            StringOrNumber::Number(amount) => Ok(amount),
+            StringOrNumber::String(amount) => amount.trim().parse().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// Deserializes an amount sent either as a JSON number or as a numeric string
+pub(crate) fn amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+    StringOrNumber::deserialize(deserializer)?.into_amount()
+}
+
+/// Deserializes an optional amount sent either as a JSON number or as a numeric string
+pub(crate) fn optional_amount<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Amount>, D::Error> {
+    Option::<StringOrNumber>::deserialize(deserializer)?
+        .map(StringOrNumber::into_amount)
+        .transpose()
+}
+
+/// Deserializes a map of optional amounts, each sent either as a JSON number or as a numeric string
+pub(crate) fn optional_amounts<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<String, Option<Amount>>, D::Error> {
+    HashMap::<String, Option<StringOrNumber>>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, amount)| Ok((key, amount.map(StringOrNumber::into_amount).transpose()?)))
+        .collect()
+}
+
+/// Float sent either as a JSON number or as a numeric string, like percentages CoinGecko sends inconsistently
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FloatOrString {
+    Float(f64),
+    String(String),
+}
+
+impl FloatOrString {
+    fn into_float<E: de::Error>(self) -> Result<f64, E> {
+        match self {
+            FloatOrString::Float(float) => Ok(float),
+            FloatOrString::String(float) => float.trim().parse().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// Deserializes a float sent either as a JSON number or as a numeric string
+pub(crate) fn float<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    FloatOrString::deserialize(deserializer)?.into_float()
+}
+
+/// Deserializes an optional float sent either as a JSON number, as a numeric string or as null
+///
+/// Fields using it also need `#[serde(default)]` to stay optional when missing.
+pub(crate) fn optional_float<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    Option::<FloatOrString>::deserialize(deserializer)?
+        .map(FloatOrString::into_float)
+        .transpose()
+}
+
+/// Deserializes the fields caught by an `extra` field, rejecting any with the `strict-schema` feature
+///
+/// `deny_unknown_fields` has no effect next to a flattened map, so structs with `extra` check it here instead.
+#[cfg(feature = "extra-fields")]
+pub(crate) fn extra_fields<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<String, serde_json::Value>, D::Error> {
+    let extra = HashMap::<String, serde_json::Value>::deserialize(deserializer)?;
+
+    match extra.keys().next() {
+        Some(field) if cfg!(feature = "strict-schema") => {
+            Err(de::Error::custom(format!("unknown field `{}`", field)))
+        }
+        _ => Ok(extra),
+    }
+}
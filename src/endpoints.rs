@@ -0,0 +1,642 @@
+#![allow(clippy::too_many_arguments)]
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::params::{
+    AssetPlatformFilter, ChartDays, CompaniesCoinId, DerivativeExchangeOrder,
+    DerivativesIncludeTickers, ExchangeStatus, IncludeTokens, Interval, MarketsFilter,
+    MarketsOrder, NftsOrder, OhlcDays, OhlcInterval, PriceChangePercentage, TickersOrder, TopCoins,
+    TopGainersLosersDuration,
+};
+
+/// Percent-encodes a user supplied path segment, e.g. a coin id
+fn segment(value: &str) -> String {
+    // form encoding writes spaces as `+`, which is a literal plus within a path
+    query(value).replace('+', "%20")
+}
+
+/// Percent-encodes a user supplied query value
+fn query(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Percent-encodes each of a list of query values, joined by an encoded comma
+fn query_list<V: AsRef<str>>(values: &[V]) -> String {
+    values
+        .iter()
+        .map(|value| query(value.as_ref()))
+        .collect::<Vec<_>>()
+        .join("%2C")
+}
+
+/// `interval` query parameter, empty for `Interval::Auto` which is the default
+fn interval_param(interval: Interval) -> &'static str {
+    match interval {
+        Interval::Auto => "",
+        Interval::FiveMinutes => "&interval=5m",
+        Interval::Hourly => "&interval=hourly",
+        Interval::Daily => "&interval=daily",
+    }
+}
+
+/// Optional `precision` query parameter, empty when unset
+fn precision_param(precision: Option<&str>) -> String {
+    match precision {
+        Some(precision) => format!("&precision={}", query(precision)),
+        None => String::new(),
+    }
+}
+
+pub(crate) fn price<Id: AsRef<str>, Curr: AsRef<str>>(
+    ids: &[Id],
+    vs_currencies: &[Curr],
+    include_market_cap: bool,
+    include_24hr_vol: bool,
+    include_24hr_change: bool,
+    include_last_updated_at: bool,
+    precision: Option<&str>,
+) -> String {
+    let precision = precision_param(precision);
+
+    format!("/simple/price?ids={}&vs_currencies={}&include_market_cap={}&include_24hr_vol={}&include_24hr_change={}&include_last_updated_at={}{}", query_list(ids), query_list(vs_currencies), include_market_cap, include_24hr_vol, include_24hr_change, include_last_updated_at, precision)
+}
+
+/// Splits ids into chunks of at most `chunk_size` for separate `/simple/price` requests, dropping duplicates
+pub(crate) fn chunk_ids<Id: AsRef<str>>(ids: &[Id], chunk_size: usize) -> Vec<Vec<&str>> {
+    let mut unique = Vec::with_capacity(ids.len());
+
+    for id in ids.iter().map(AsRef::as_ref) {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+
+    unique
+        .chunks(chunk_size.max(1))
+        .map(<[&str]>::to_vec)
+        .collect()
+}
+
+pub(crate) fn token_price<Addr: AsRef<str>, Curr: AsRef<str>>(
+    id: &str,
+    contract_addresses: &[Addr],
+    vs_currencies: &[Curr],
+    include_market_cap: bool,
+    include_24hr_vol: bool,
+    include_24hr_change: bool,
+    include_last_updated_at: bool,
+    precision: Option<&str>,
+    include_tokens: Option<IncludeTokens>,
+) -> String {
+    let precision = precision_param(precision);
+
+    let include_tokens = match include_tokens {
+        Some(IncludeTokens::All) => "&include_tokens=all",
+        Some(IncludeTokens::Top) => "&include_tokens=top",
+        None => "",
+    };
+
+    format!("/simple/token_price/{}?contract_addresses={}&vs_currencies={}&include_market_cap={}&include_24hr_vol={}&include_24hr_change={}&include_last_updated_at={}{}{}", segment(id), query_list(contract_addresses), query_list(vs_currencies), include_market_cap, include_24hr_vol, include_24hr_change, include_last_updated_at, precision, include_tokens)
+}
+
+pub(crate) fn coins_list(include_platform: bool) -> String {
+    format!("/coins/list?include_platform={}", include_platform)
+}
+
+pub(crate) fn top_gainers_losers(
+    vs_currency: &str,
+    duration: TopGainersLosersDuration,
+    top_coins: Option<TopCoins>,
+) -> String {
+    let duration = match duration {
+        TopGainersLosersDuration::OneHour => "1h",
+        TopGainersLosersDuration::TwentyFourHours => "24h",
+        TopGainersLosersDuration::SevenDays => "7d",
+        TopGainersLosersDuration::FourteenDays => "14d",
+        TopGainersLosersDuration::ThirtyDays => "30d",
+        TopGainersLosersDuration::SixtyDays => "60d",
+        TopGainersLosersDuration::OneYear => "1y",
+    };
+    let top_coins = match top_coins {
+        Some(TopCoins::ThreeHundred) => "&top_coins=300",
+        Some(TopCoins::FiveHundred) => "&top_coins=500",
+        Some(TopCoins::OneThousand) => "&top_coins=1000",
+        Some(TopCoins::All) => "&top_coins=all",
+        None => "",
+    };
+
+    format!(
+        "/coins/top_gainers_losers?vs_currency={}&duration={}{}",
+        query(vs_currency),
+        duration,
+        top_coins
+    )
+}
+
+pub(crate) fn coins_markets(
+    vs_currency: &str,
+    filter: Option<&MarketsFilter>,
+    order: MarketsOrder,
+    per_page: i64,
+    page: i64,
+    sparkline: bool,
+    price_change_percentage: &[PriceChangePercentage],
+    locale: Option<&str>,
+) -> String {
+    // `ids` is always sent, empty unless filtering by ids
+    let (ids, filter) = match filter {
+        Some(MarketsFilter::Ids(ids)) => (query_list(ids), String::new()),
+        Some(MarketsFilter::Names(names)) => {
+            (String::new(), format!("&names={}", query_list(names)))
+        }
+        Some(MarketsFilter::Symbols(symbols)) => {
+            (String::new(), format!("&symbols={}", query_list(symbols)))
+        }
+        Some(MarketsFilter::Category(category)) => {
+            (String::new(), format!("&category={}", query(category)))
+        }
+        None => (String::new(), String::new()),
+    };
+
+    let price_change_percentage = price_change_percentage.iter().fold(
+        Vec::with_capacity(price_change_percentage.len()),
+        |mut acc, x| {
+            let current = match *x {
+                PriceChangePercentage::OneHour => "1h",
+                PriceChangePercentage::TwentyFourHours => "24h",
+                PriceChangePercentage::SevenDays => "7d",
+                PriceChangePercentage::FourteenDays => "14d",
+                PriceChangePercentage::ThirtyDays => "30d",
+                PriceChangePercentage::TwoHundredDays => "200d",
+                PriceChangePercentage::OneYear => "1y",
+            };
+
+            acc.push(current);
+            acc
+        },
+    );
+
+    let locale = match locale {
+        Some(l) => format!("&locale={}", query(l)),
+        None => String::new(),
+    };
+
+    format!("/coins/markets?vs_currency={}&ids={}{}&order={}&per_page={}&page={}&sparkline={}&price_change_percentage={}{}", query(vs_currency), ids, filter, order, per_page, page, sparkline, price_change_percentage.join("%2C"), locale)
+}
+
+pub(crate) fn coin(
+    id: &str,
+    localization: bool,
+    tickers: bool,
+    market_data: bool,
+    community_data: bool,
+    developer_data: bool,
+    sparkline: bool,
+) -> String {
+    format!("/coins/{}?localization={}&tickers={}&market_data={}&community_data={}&developer_data={}&sparkline={}", segment(id), localization, tickers, market_data, community_data, developer_data, sparkline)
+}
+
+pub(crate) fn coin_tickers<Ex: AsRef<str>>(
+    id: &str,
+    exchange_ids: Option<&[Ex]>,
+    include_exchange_logo: bool,
+    page: i64,
+    order: TickersOrder,
+    depth: bool,
+) -> String {
+    match exchange_ids {
+        Some(e_ids) => {
+            format!("/coins/{}/tickers?exchange_ids={}&include_exchange_logo={}&page={}&order={}&depth={}", segment(id), query_list(e_ids), include_exchange_logo, &page, order, depth)
+        }
+        None => format!(
+            "/coins/{}/tickers?include_exchange_logo={}&page={}&order={}&depth={}",
+            segment(id),
+            include_exchange_logo,
+            &page,
+            order,
+            depth
+        ),
+    }
+}
+
+pub(crate) fn coin_history(id: &str, date: NaiveDate, localization: bool) -> String {
+    let formatted_date = date.format("%d-%m-%Y").to_string();
+
+    format!(
+        "/coins/{}/history?date={}&localization={}",
+        segment(id),
+        formatted_date,
+        localization
+    )
+}
+
+pub(crate) fn coin_market_chart(
+    id: &str,
+    vs_currency: &str,
+    days: ChartDays,
+    use_daily_interval: bool,
+    precision: Option<&str>,
+) -> String {
+    let interval = match use_daily_interval {
+        true => Interval::Daily,
+        false => Interval::Auto,
+    };
+
+    coin_market_chart_with_interval(id, vs_currency, days, interval, precision)
+}
+
+pub(crate) fn coin_market_chart_with_interval(
+    id: &str,
+    vs_currency: &str,
+    days: ChartDays,
+    interval: Interval,
+    precision: Option<&str>,
+) -> String {
+    format!(
+        "/coins/{}/market_chart?vs_currency={}&days={}{}{}",
+        segment(id),
+        query(vs_currency),
+        days,
+        interval_param(interval),
+        precision_param(precision)
+    )
+}
+
+pub(crate) fn coin_market_chart_range(
+    id: &str,
+    vs_currency: &str,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    interval: Option<Interval>,
+    precision: Option<&str>,
+) -> String {
+    let from_unix_timestamp = from.and_utc().timestamp();
+    let to_unix_timestamp = to.and_utc().timestamp();
+
+    format!(
+        "/coins/{}/market_chart/range?vs_currency={}&from={}&to={}{}{}",
+        segment(id),
+        query(vs_currency),
+        from_unix_timestamp,
+        to_unix_timestamp,
+        interval.map_or("", interval_param),
+        precision_param(precision)
+    )
+}
+
+pub(crate) fn coin_circulating_supply_chart(id: &str, days: ChartDays) -> String {
+    format!(
+        "/coins/{}/circulating_supply_chart?days={}",
+        segment(id),
+        days
+    )
+}
+
+pub(crate) fn coin_total_supply_chart(id: &str, days: ChartDays) -> String {
+    format!("/coins/{}/total_supply_chart?days={}", segment(id), days)
+}
+
+pub(crate) fn coin_ohlc(id: &str, vs_currency: &str, days: OhlcDays) -> String {
+    format!(
+        "/coins/{}/ohlc?vs_currency={}&days={}",
+        segment(id),
+        query(vs_currency),
+        days
+    )
+}
+
+pub(crate) fn coin_ohlc_range(
+    id: &str,
+    vs_currency: &str,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    interval: OhlcInterval,
+) -> String {
+    format!(
+        "/coins/{}/ohlc/range?vs_currency={}&from={}&to={}&interval={}",
+        segment(id),
+        query(vs_currency),
+        from.and_utc().timestamp(),
+        to.and_utc().timestamp(),
+        interval
+    )
+}
+
+pub(crate) fn contract(id: &str, contract_address: &str) -> String {
+    format!(
+        "/coins/{}/contract/{}",
+        segment(id),
+        segment(contract_address)
+    )
+}
+
+pub(crate) fn contract_market_chart(
+    id: &str,
+    contract_address: &str,
+    vs_currency: &str,
+    days: ChartDays,
+    precision: Option<&str>,
+) -> String {
+    format!(
+        "/coins/{}/contract/{}/market_chart?vs_currency={}&days={}{}",
+        segment(id),
+        segment(contract_address),
+        query(vs_currency),
+        days,
+        precision_param(precision)
+    )
+}
+
+pub(crate) fn contract_market_chart_range(
+    id: &str,
+    contract_address: &str,
+    vs_currency: &str,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+    interval: Option<Interval>,
+    precision: Option<&str>,
+) -> String {
+    let from_unix_timestamp = from.and_utc().timestamp();
+    let to_unix_timestamp = to.and_utc().timestamp();
+
+    format!(
+        "/coins/{}/contract/{}/market_chart/range?vs_currency={}&from={}&to={}{}{}",
+        segment(id),
+        segment(contract_address),
+        query(vs_currency),
+        from_unix_timestamp,
+        to_unix_timestamp,
+        interval.map_or("", interval_param),
+        precision_param(precision)
+    )
+}
+
+pub(crate) fn asset_platforms(filter: Option<AssetPlatformFilter>) -> String {
+    match filter {
+        Some(AssetPlatformFilter::Nft) => "/asset_platforms?filter=nft".to_string(),
+        None => "/asset_platforms".to_string(),
+    }
+}
+
+pub(crate) fn exchanges_list(status: Option<ExchangeStatus>) -> String {
+    match status {
+        Some(ExchangeStatus::Active) => "/exchanges/list?status=active".to_string(),
+        Some(ExchangeStatus::Inactive) => "/exchanges/list?status=inactive".to_string(),
+        None => "/exchanges/list".to_string(),
+    }
+}
+
+pub(crate) fn exchanges(per_page: i64, page: i64) -> String {
+    format!("/exchanges?per_page={}&page={}", per_page, page)
+}
+
+pub(crate) fn exchange(id: &str) -> String {
+    format!("/exchanges/{}", segment(id))
+}
+
+pub(crate) fn exchange_tickers<CoinId: AsRef<str>>(
+    id: &str,
+    coin_ids: Option<&[CoinId]>,
+    include_exchange_logo: bool,
+    page: i64,
+    order: TickersOrder,
+    depth: bool,
+) -> String {
+    match coin_ids {
+        Some(c_ids) => {
+            format!("/exchanges/{}/tickers?coin_ids={}&include_exchange_logo={}&page={}&order={}&depth={}", segment(id), query_list(c_ids), include_exchange_logo, &page, order, depth)
+        }
+        None => format!(
+            "/exchanges/{}/tickers?include_exchange_logo={}&page={}&order={}&depth={}",
+            segment(id),
+            include_exchange_logo,
+            &page,
+            order,
+            depth
+        ),
+    }
+}
+
+pub(crate) fn exchange_status_updates(id: &str, per_page: i64, page: i64) -> String {
+    format!(
+        "/exchanges/{}/status_updates?per_page={}&page={}",
+        segment(id),
+        per_page,
+        page,
+    )
+}
+
+pub(crate) fn exchange_volume_chart(id: &str, days: i64) -> String {
+    format!("/exchanges/{}/volume_chart?days={}", segment(id), days)
+}
+
+pub(crate) fn exchange_volume_chart_range(
+    id: &str,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> String {
+    let from_unix_timestamp = from.and_utc().timestamp();
+    let to_unix_timestamp = to.and_utc().timestamp();
+
+    format!(
+        "/exchanges/{}/volume_chart/range?from={}&to={}",
+        segment(id),
+        from_unix_timestamp,
+        to_unix_timestamp
+    )
+}
+
+pub(crate) fn finance_platforms(per_page: i64, page: i64) -> String {
+    format!("/finance_platforms?per_page={}&page={}", per_page, page,)
+}
+
+pub(crate) fn finance_products(per_page: i64, page: i64) -> String {
+    format!("/finance_products?per_page={}&page={}", per_page, page,)
+}
+
+pub(crate) fn indexes(per_page: i64, page: i64) -> String {
+    format!("/indexes?per_page={}&page={}", per_page, page,)
+}
+
+pub(crate) fn indexes_market_id(market_id: &str, id: &str) -> String {
+    format!("/indexes/{}/{}", segment(market_id), segment(id))
+}
+
+pub(crate) fn derivatives(include_tickers: Option<DerivativesIncludeTickers>) -> String {
+    let include_tickers = include_tickers.unwrap_or(DerivativesIncludeTickers::Unexpired);
+
+    format!("/derivatives?include_tickers={}", include_tickers)
+}
+
+pub(crate) fn derivative_exchanges(
+    order: DerivativeExchangeOrder,
+    per_page: i64,
+    page: i64,
+) -> String {
+    format!(
+        "/derivatives/exchanges?order={}&per_page={}&page={}",
+        order, per_page, page
+    )
+}
+
+pub(crate) fn derivatives_exchange(
+    id: &str,
+    include_tickers: Option<DerivativesIncludeTickers>,
+) -> String {
+    let include_tickers = include_tickers.unwrap_or(DerivativesIncludeTickers::Unexpired);
+
+    format!(
+        "/derivatives/exchanges/{}?include_tickers={}",
+        segment(id),
+        include_tickers
+    )
+}
+
+/// `order` query param shared by `/nfts/list` and `/nfts/markets`
+fn nfts_order(order: Option<NftsOrder>) -> &'static str {
+    match order {
+        Some(NftsOrder::H24VolumeNativeAsc) => "&order=h24_volume_native_asc",
+        Some(NftsOrder::H24VolumeNativeDesc) => "&order=h24_volume_native_desc",
+        Some(NftsOrder::FloorPriceNativeAsc) => "&order=floor_price_native_asc",
+        Some(NftsOrder::FloorPriceNativeDesc) => "&order=floor_price_native_desc",
+        Some(NftsOrder::MarketCapNativeAsc) => "&order=market_cap_native_asc",
+        Some(NftsOrder::MarketCapNativeDesc) => "&order=market_cap_native_desc",
+        Some(NftsOrder::MarketCapUsdAsc) => "&order=market_cap_usd_asc",
+        Some(NftsOrder::MarketCapUsdDesc) => "&order=market_cap_usd_desc",
+        None => "",
+    }
+}
+
+pub(crate) fn nfts_list(order: Option<NftsOrder>, per_page: i64, page: i64) -> String {
+    format!(
+        "/nfts/list?per_page={}&page={}{}",
+        per_page,
+        page,
+        nfts_order(order)
+    )
+}
+
+pub(crate) fn nfts_markets(
+    asset_platform_id: Option<&str>,
+    order: Option<NftsOrder>,
+    per_page: i64,
+    page: i64,
+) -> String {
+    let asset_platform_id = match asset_platform_id {
+        Some(id) => format!("&asset_platform_id={}", query(id)),
+        None => String::new(),
+    };
+
+    format!(
+        "/nfts/markets?per_page={}&page={}{}{}",
+        per_page,
+        page,
+        asset_platform_id,
+        nfts_order(order)
+    )
+}
+
+pub(crate) fn nft(id: &str) -> String {
+    format!("/nfts/{}", segment(id))
+}
+
+pub(crate) fn nft_contract(asset_platform_id: &str, contract_address: &str) -> String {
+    format!(
+        "/nfts/{}/contract/{}",
+        segment(asset_platform_id),
+        segment(contract_address)
+    )
+}
+
+pub(crate) fn nft_market_chart(id: &str, days: i64) -> String {
+    format!("/nfts/{}/market_chart?days={}", segment(id), days)
+}
+
+pub(crate) fn status_updates(
+    category: Option<&str>,
+    project_type: Option<&str>,
+    per_page: i64,
+    page: i64,
+) -> String {
+    let mut params: Vec<String> = Vec::with_capacity(4);
+
+    if let Some(c) = category {
+        params.push(format!("category={}", query(c)));
+    }
+
+    if let Some(t) = project_type {
+        params.push(format!("project_type={}", query(t)));
+    }
+
+    params.push(format!("per_page={}", per_page));
+    params.push(format!("page={}", page));
+
+    format!("/status_updates?{}", params.join("&"))
+}
+
+pub(crate) fn events(
+    country_code: Option<&str>,
+    event_type: Option<&str>,
+    page: i64,
+    upcoming_events_only: bool,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+) -> String {
+    let mut params: Vec<String> = Vec::with_capacity(2);
+
+    if let Some(c) = country_code {
+        params.push(format!("country_code={}", query(c)));
+    }
+
+    if let Some(t) = event_type {
+        params.push(format!("type={}", query(t)));
+    }
+
+    let from_date = from_date.format("%Y-%m-%d").to_string();
+    let to_date = to_date.format("%Y-%m-%d").to_string();
+
+    format!(
+        "/events?{}&page={}&upcoming_events_only={}&from_date={}&to_date={}",
+        params.join("&"),
+        page,
+        upcoming_events_only,
+        from_date,
+        to_date,
+    )
+}
+
+pub(crate) fn global_market_cap_chart(days: i64, vs_currency: &str) -> String {
+    format!(
+        "/global/market_cap_chart?days={}&vs_currency={}",
+        days,
+        query(vs_currency)
+    )
+}
+
+pub(crate) fn companies(coin_id: CompaniesCoinId) -> String {
+    match coin_id {
+        CompaniesCoinId::Bitcoin => companies_by_id("bitcoin"),
+        CompaniesCoinId::Ethereum => companies_by_id("ethereum"),
+    }
+}
+
+pub(crate) fn companies_by_id(coin_id: &str) -> String {
+    format!("/companies/public_treasury/{}", segment(coin_id))
+}
+
+pub(crate) fn search(query: &str) -> String {
+    format!("/search?query={}", self::query(query))
+}
+
+pub(crate) fn raw(endpoint: &str, params: &[(&str, &str)]) -> String {
+    let endpoint = format!("/{}", endpoint.trim_start_matches('/'));
+
+    if params.is_empty() {
+        return endpoint;
+    }
+
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+
+    format!("{}?{}", endpoint, query)
+}
@@ -0,0 +1,458 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::params::OhlcDays;
+
+/// Length of the body included in `UnexpectedContentType`
+const BODY_SNIPPET_CHARS: usize = 200;
+
+/// Errors returned by CoinGeckoClient requests
+#[derive(Debug)]
+pub enum CoinGeckoError {
+    /// CoinGecko responded with 429 Too Many Requests
+    RateLimited {
+        /// Url of the request, without any API key
+        url: String,
+        /// Time to wait before retrying, from the `Retry-After` header
+        retry_after: Option<Duration>,
+        /// Message sent by CoinGecko, usually pointing to the plans with higher rate limits
+        message: Option<String>,
+    },
+    /// CoinGecko responded with 404 Not Found, e.g. for an unknown coin id
+    NotFound {
+        /// Url of the request, without any API key
+        url: String,
+    },
+    /// CoinGecko responded with 401 Unauthorized, e.g. for an invalid API key or a Pro-only endpoint without a Pro API key
+    Unauthorized {
+        /// Url of the request, without any API key
+        url: String,
+        /// Response status
+        status: StatusCode,
+        /// Message sent by CoinGecko, usually explaining which plan the endpoint requires
+        message: Option<String>,
+        /// Response body
+        body: String,
+    },
+    /// CoinGecko responded with 403 Forbidden, e.g. for an endpoint the plan of the API key does not include
+    Forbidden {
+        /// Url of the request, without any API key
+        url: String,
+        /// Message sent by CoinGecko, usually naming the plan the endpoint requires
+        plan_hint: Option<String>,
+        /// Response body
+        body: String,
+    },
+    /// CoinGecko responded with any other non-success status
+    Http {
+        /// Url of the request, without any API key
+        url: String,
+        /// Response status
+        status: StatusCode,
+        /// Response body
+        body: String,
+    },
+    /// These quote currencies are not supported by CoinGecko, see `validate_currencies`
+    UnsupportedCurrencies(Vec<String>),
+    /// A `Precision::Decimals` above 18 was given, which CoinGecko does not support
+    InvalidPrecision(u8),
+    /// CoinGecko answered with an error message like `{"error": "..."}` instead of the expected data
+    Api {
+        /// Url of the request, without any API key
+        url: String,
+        /// Error message sent by CoinGecko
+        message: String,
+    },
+    /// CoinGecko answered with a body that is not JSON, e.g. an HTML error page under heavy load
+    UnexpectedContentType {
+        /// `Content-Type` of the response
+        content_type: String,
+        /// Start of the response body
+        body_snippet: String,
+    },
+    /// These `coin_ohlc` days require a Pro API key
+    InvalidOhlcDays(OhlcDays),
+    /// A param has a value CoinGecko does not accept, e.g. 0 days or a page below 1, so the request was not sent
+    InvalidParam {
+        /// Name of the param
+        name: &'static str,
+        /// Why the value is not accepted
+        reason: String,
+    },
+    /// The circuit breaker is open after repeated failures, so the request was not sent
+    CircuitOpen {
+        /// Time until the circuit lets a request through again
+        retry_after: Duration,
+    },
+    /// The request was aborted by its cancellation token, see `CoinGeckoClientBuilder::cancellation_token`
+    Cancelled,
+    /// The response body exceeded the `max_response_bytes` set on the builder
+    ResponseTooLarge {
+        /// Maximum size of a response body in bytes
+        limit: u64,
+    },
+    /// A recorded response could not be read from or written to `path`, see `CoinGeckoClient::replaying`
+    Fixture {
+        /// Path of the recorded response
+        path: PathBuf,
+        /// Underlying error, `NotFound` when no response to this request was recorded
+        source: std::io::Error,
+    },
+    /// The response body could not be deserialized
+    Decode {
+        /// Url of the request, without any API key
+        url: String,
+        /// Underlying error
+        source: serde_json::Error,
+    },
+    /// The request could not be sent or its response could not be read
+    Transport {
+        /// Url of the request, without any API key
+        url: String,
+        /// Underlying error, stripped of its own copy of the url
+        source: reqwest::Error,
+    },
+}
+
+impl CoinGeckoError {
+    /// Maps a non-success response to `url` to its error variant
+    pub(crate) fn from_status(
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: String,
+        url: &str,
+    ) -> Self {
+        let url = sanitize_url(url);
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => CoinGeckoError::RateLimited {
+                url,
+                retry_after: crate::retry::retry_after(headers),
+                message: error_message(&body),
+            },
+            StatusCode::NOT_FOUND => CoinGeckoError::NotFound { url },
+            StatusCode::UNAUTHORIZED => CoinGeckoError::Unauthorized {
+                url,
+                status,
+                message: error_message(&body),
+                body,
+            },
+            StatusCode::FORBIDDEN => CoinGeckoError::Forbidden {
+                url,
+                plan_hint: error_message(&body),
+                body,
+            },
+            _ => CoinGeckoError::Http { url, status, body },
+        }
+    }
+
+    /// Copy of this error to hand to several callers, `None` for errors wrapping a non-cloneable source
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        Some(match self {
+            CoinGeckoError::RateLimited {
+                url,
+                retry_after,
+                message,
+            } => CoinGeckoError::RateLimited {
+                url: url.clone(),
+                retry_after: *retry_after,
+                message: message.clone(),
+            },
+            CoinGeckoError::NotFound { url } => CoinGeckoError::NotFound { url: url.clone() },
+            CoinGeckoError::Unauthorized {
+                url,
+                status,
+                message,
+                body,
+            } => CoinGeckoError::Unauthorized {
+                url: url.clone(),
+                status: *status,
+                message: message.clone(),
+                body: body.clone(),
+            },
+            CoinGeckoError::Forbidden {
+                url,
+                plan_hint,
+                body,
+            } => CoinGeckoError::Forbidden {
+                url: url.clone(),
+                plan_hint: plan_hint.clone(),
+                body: body.clone(),
+            },
+            CoinGeckoError::Http { url, status, body } => CoinGeckoError::Http {
+                url: url.clone(),
+                status: *status,
+                body: body.clone(),
+            },
+            CoinGeckoError::UnsupportedCurrencies(currencies) => {
+                CoinGeckoError::UnsupportedCurrencies(currencies.clone())
+            }
+            CoinGeckoError::InvalidPrecision(decimals) => {
+                CoinGeckoError::InvalidPrecision(*decimals)
+            }
+            CoinGeckoError::Api { url, message } => CoinGeckoError::Api {
+                url: url.clone(),
+                message: message.clone(),
+            },
+            CoinGeckoError::UnexpectedContentType {
+                content_type,
+                body_snippet,
+            } => CoinGeckoError::UnexpectedContentType {
+                content_type: content_type.clone(),
+                body_snippet: body_snippet.clone(),
+            },
+            CoinGeckoError::InvalidOhlcDays(days) => CoinGeckoError::InvalidOhlcDays(*days),
+            CoinGeckoError::InvalidParam { name, reason } => CoinGeckoError::InvalidParam {
+                name,
+                reason: reason.clone(),
+            },
+            CoinGeckoError::CircuitOpen { retry_after } => CoinGeckoError::CircuitOpen {
+                retry_after: *retry_after,
+            },
+            CoinGeckoError::Cancelled => CoinGeckoError::Cancelled,
+            CoinGeckoError::ResponseTooLarge { limit } => {
+                CoinGeckoError::ResponseTooLarge { limit: *limit }
+            }
+            CoinGeckoError::Fixture { .. }
+            | CoinGeckoError::Decode { .. }
+            | CoinGeckoError::Transport { .. } => return None,
+        })
+    }
+
+    /// Url of the failed request, so failures can be told apart e.g. within a batch
+    ///
+    /// `None` for errors raised before a request was sent, e.g. `InvalidParam`.
+    /// API keys passed as `x_cg_pro_api_key` or `x_cg_demo_api_key` query params are removed from the url.
+    pub fn url(&self) -> Option<&str> {
+        let url = match self {
+            CoinGeckoError::RateLimited { url, .. }
+            | CoinGeckoError::NotFound { url }
+            | CoinGeckoError::Unauthorized { url, .. }
+            | CoinGeckoError::Forbidden { url, .. }
+            | CoinGeckoError::Http { url, .. }
+            | CoinGeckoError::Api { url, .. }
+            | CoinGeckoError::Decode { url, .. }
+            | CoinGeckoError::Transport { url, .. } => url,
+            _ => return None,
+        };
+
+        // errors converted from `serde_json` or `reqwest` outside of a request have no url
+        Some(url.as_str()).filter(|url| !url.is_empty())
+    }
+
+    /// Sets the url of the request on an error returned by the transport, which may not know it
+    pub(crate) fn with_url(mut self, request_url: &str) -> Self {
+        match &mut self {
+            CoinGeckoError::RateLimited { url, .. }
+            | CoinGeckoError::NotFound { url }
+            | CoinGeckoError::Unauthorized { url, .. }
+            | CoinGeckoError::Forbidden { url, .. }
+            | CoinGeckoError::Http { url, .. }
+            | CoinGeckoError::Api { url, .. }
+            | CoinGeckoError::Decode { url, .. }
+            | CoinGeckoError::Transport { url, .. } => *url = sanitize_url(request_url),
+            _ => {}
+        }
+        self
+    }
+
+    /// Deserializes a successful response body of a request to `url`
+    ///
+    /// CoinGecko sometimes answers with a success status and an error body, e.g. for a contract address
+    /// it does not track. Such bodies map to `NotFound` or `Api` instead of a confusing `Decode` error,
+    /// and bodies with a content type other than JSON to `UnexpectedContentType`.
+    pub(crate) fn decode<R: DeserializeOwned>(
+        headers: &HeaderMap,
+        body: &[u8],
+        url: &str,
+    ) -> Result<R, CoinGeckoError> {
+        // responses without a content type are still parsed
+        if let Some(content_type) = headers.get(CONTENT_TYPE) {
+            let content_type = String::from_utf8_lossy(content_type.as_bytes()).into_owned();
+
+            if !content_type.to_lowercase().contains("json") {
+                let body = String::from_utf8_lossy(body);
+
+                return Err(CoinGeckoError::UnexpectedContentType {
+                    content_type,
+                    body_snippet: body.chars().take(BODY_SNIPPET_CHARS).collect(),
+                });
+            }
+        }
+
+        #[derive(Deserialize)]
+        struct ErrorBody {
+            error: String,
+        }
+
+        serde_json::from_slice(body).map_err(|source| {
+            let url = sanitize_url(url);
+            match serde_json::from_slice::<ErrorBody>(body) {
+                Ok(ErrorBody { error }) if error.to_lowercase().contains("not found") => {
+                    CoinGeckoError::NotFound { url }
+                }
+                Ok(ErrorBody { error }) => CoinGeckoError::Api {
+                    url,
+                    message: error,
+                },
+                Err(_) => CoinGeckoError::Decode { url, source },
+            }
+        })
+    }
+}
+
+/// Message of an error body, sent by CoinGecko as `{"status":{"error_message":"..."}}` or `{"error":"..."}`
+fn error_message(body: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ErrorBody {
+        Status { status: ErrorStatus },
+        Error { error: String },
+    }
+
+    #[derive(Deserialize)]
+    struct ErrorStatus {
+        error_message: String,
+    }
+
+    match serde_json::from_str(body).ok()? {
+        ErrorBody::Status { status } => Some(status.error_message),
+        ErrorBody::Error { error } => Some(error),
+    }
+}
+
+/// Removes API key query params from a url
+pub(crate) fn sanitize_url(url: &str) -> String {
+    let mut parsed = match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return url.to_string(),
+    };
+
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(name, _)| name != "x_cg_pro_api_key" && name != "x_cg_demo_api_key")
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+
+    if pairs.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    parsed.to_string()
+}
+
+impl fmt::Display for CoinGeckoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoinGeckoError::RateLimited { retry_after, .. } => {
+                write!(f, "rate limited by CoinGecko")?;
+                match retry_after {
+                    Some(retry_after) => write!(f, ", retry in {}s", retry_after.as_secs()),
+                    None => Ok(()),
+                }
+            }
+            CoinGeckoError::NotFound { .. } => write!(f, "resource not found"),
+            CoinGeckoError::Unauthorized {
+                status,
+                message,
+                body,
+                ..
+            } => write!(
+                f,
+                "unauthorized ({}), the API key is missing, invalid or its plan lacks access to this endpoint: {}",
+                status,
+                message.as_deref().unwrap_or(body)
+            ),
+            CoinGeckoError::Forbidden {
+                plan_hint, body, ..
+            } => write!(
+                f,
+                "forbidden, the plan of the API key lacks access to this endpoint: {}",
+                plan_hint.as_deref().unwrap_or(body)
+            ),
+            CoinGeckoError::Http { status, body, .. } => write!(f, "http error {}: {}", status, body),
+            CoinGeckoError::UnsupportedCurrencies(currencies) => {
+                write!(f, "unsupported currencies: {}", currencies.join(", "))
+            }
+            CoinGeckoError::InvalidPrecision(decimals) => {
+                write!(f, "invalid precision {}, must be between 0 and 18", decimals)
+            }
+            CoinGeckoError::Api { message, .. } => write!(f, "CoinGecko error: {}", message),
+            CoinGeckoError::UnexpectedContentType {
+                content_type,
+                body_snippet,
+            } => write!(
+                f,
+                "expected a JSON response but got {}: {}",
+                content_type, body_snippet
+            ),
+            CoinGeckoError::InvalidOhlcDays(days) => write!(
+                f,
+                "invalid ohlc days {}, without a Pro API key only 1, 7, 14, 30, 90, 180 and 365 are supported",
+                days
+            ),
+            CoinGeckoError::InvalidParam { name, reason } => {
+                write!(f, "invalid {}: {}", name, reason)
+            }
+            CoinGeckoError::CircuitOpen { retry_after } => write!(
+                f,
+                "circuit breaker open after repeated failures, retry in {}s",
+                retry_after.as_secs()
+            ),
+            CoinGeckoError::Cancelled => write!(f, "request cancelled"),
+            CoinGeckoError::ResponseTooLarge { limit } => {
+                write!(f, "response body exceeds the limit of {} bytes", limit)
+            }
+            CoinGeckoError::Fixture { path, source } => {
+                write!(f, "recorded response {} failed: {}", path.display(), source)
+            }
+            CoinGeckoError::Decode { source, .. } => {
+                write!(f, "failed to decode response: {}", source)
+            }
+            CoinGeckoError::Transport { url, source } => {
+                write!(f, "request to {} failed: {}", url, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoinGeckoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CoinGeckoError::Decode { source, .. } => Some(source),
+            CoinGeckoError::Fixture { source, .. } => Some(source),
+            CoinGeckoError::Transport { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Transport error with the url of the request, if `reqwest` knows it
+impl From<reqwest::Error> for CoinGeckoError {
+    fn from(source: reqwest::Error) -> Self {
+        CoinGeckoError::Transport {
+            url: source
+                .url()
+                .map(|url| sanitize_url(url.as_str()))
+                .unwrap_or_default(),
+            source: source.without_url(),
+        }
+    }
+}
+
+/// Decode error without a url, which the client sets when the error comes from one of its requests
+impl From<serde_json::Error> for CoinGeckoError {
+    fn from(source: serde_json::Error) -> Self {
+        CoinGeckoError::Decode {
+            url: String::new(),
+            source,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::sanitize_url;
+
+/// Longest file name kept as is, longer ones are shortened and suffixed with a hash
+const MAX_NAME_LEN: usize = 200;
+
+/// Path of the file stored in `dir` for a url, named after its path and query params without any API key
+///
+/// Names do not depend on the host, so files written for one host are found for any other.
+pub(crate) fn request_path(dir: &Path, url: &str) -> PathBuf {
+    let url = sanitize_url(url);
+    let request = match reqwest::Url::parse(&url) {
+        Ok(parsed) => match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        },
+        Err(_) => url,
+    };
+
+    // the path starts with the host's own path, e.g. `/api/v3`, which differs between hosts
+    let request = match request.find("//") {
+        Some(start) => &request[start..],
+        None => request.as_str(),
+    };
+
+    let mut name: String =
+        form_urlencoded::byte_serialize(request.trim_start_matches('/').as_bytes()).collect();
+    if name.len() > MAX_NAME_LEN {
+        name.truncate(MAX_NAME_LEN);
+        name.push_str(&format!("-{:016x}", fnv1a(request.as_bytes())));
+    }
+
+    dir.join(format!("{}.json", name))
+}
+
+/// 64-bit FNV-1a hash, stable across Rust versions unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
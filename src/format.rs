@@ -0,0 +1,110 @@
+use std::fmt;
+
+/// Number formatted rounded to two decimal places
+///
+/// `rust_decimal` truncates with a `{:.2}` precision where `f64` rounds, so both are rounded here.
+pub(crate) trait Rounded: fmt::Display {
+    fn two_decimals(&self) -> String;
+}
+
+impl Rounded for f64 {
+    fn two_decimals(&self) -> String {
+        format!("{:.2}", self)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl Rounded for rust_decimal::Decimal {
+    fn two_decimals(&self) -> String {
+        format!("{:.2}", self.round_dp(2))
+    }
+}
+
+/// Formats an amount with two decimal places and thousands separators, e.g. `1,234,567.50`
+///
+/// Amounts rounding to zero at two decimal places, e.g. prices of meme coins, keep all their decimals.
+pub(crate) fn number(value: impl Rounded) -> String {
+    let rounded = value.two_decimals();
+    let number = if rounded.trim_start_matches('-') == "0.00" {
+        value.to_string()
+    } else {
+        rounded
+    };
+
+    thousands(&number)
+}
+
+/// Formats a count with thousands separators, e.g. `14,321`
+pub(crate) fn count(value: f64) -> String {
+    thousands(&format!("{:.0}", value))
+}
+
+/// Formats an amount in a currency, e.g. `$1,234.50` for `usd`
+///
+/// Currencies without a symbol get the plain number, e.g. `1,234.50` for `chf`, callers write their code.
+pub(crate) fn money(value: impl Rounded, currency: &str) -> String {
+    let number = number(value);
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", number.as_str()),
+    };
+
+    match symbol(currency) {
+        Some(symbol) => format!("{}{}{}", sign, symbol, digits),
+        None => number,
+    }
+}
+
+/// Formats a change in percent with its sign, e.g. `+1.25%`
+pub(crate) fn percent(value: impl Rounded) -> String {
+    let rounded = value.two_decimals();
+    // tiny losses round to `-0.00`, which is no change either
+    let rounded = match rounded.as_str() {
+        "-0.00" => "0.00".to_string(),
+        _ => rounded,
+    };
+    match rounded.starts_with('-') {
+        true => format!("{}%", rounded),
+        false => format!("+{}%", rounded),
+    }
+}
+
+/// Symbol written before amounts in a currency, `None` for currencies written as a code after the amount
+fn symbol(currency: &str) -> Option<&'static str> {
+    Some(match currency.to_ascii_lowercase().as_str() {
+        "usd" => "$",
+        "eur" => "€",
+        "gbp" => "£",
+        "jpy" | "cny" => "¥",
+        "krw" => "₩",
+        "inr" => "₹",
+        "rub" => "₽",
+        "btc" => "₿",
+        "eth" => "Ξ",
+        _ => return None,
+    })
+}
+
+/// Inserts a comma between each group of three digits before the decimal point
+fn thousands(number: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(point) => unsigned.split_at(point),
+        None => (unsigned, ""),
+    };
+
+    let mut grouped = String::with_capacity(number.len() + integer.len() / 3);
+    grouped.push_str(sign);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(fraction);
+
+    grouped
+}
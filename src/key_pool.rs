@@ -0,0 +1,109 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+/// How long a key answered with 429 Too Many Requests is skipped when CoinGecko sends no `Retry-After`
+const RATE_LIMITED_FOR: Duration = Duration::from_secs(60);
+
+/// Several API keys taking turns, skipping keys CoinGecko recently rate limited
+///
+/// Keys are picked round-robin. A key answered with 429 Too Many Requests is skipped until its `Retry-After`
+/// has passed, or for a minute without one. When every key is rate limited, the one free again soonest is used.
+#[derive(Debug)]
+pub(crate) struct KeyPool {
+    keys: Vec<(HeaderName, HeaderValue)>,
+    state: Mutex<KeyPoolState>,
+}
+
+#[derive(Debug)]
+struct KeyPoolState {
+    /// Index of the key to try first for the next request
+    cursor: usize,
+    /// Until when each key is rate limited
+    limited_until: Vec<Option<DateTime<Utc>>>,
+}
+
+impl KeyPool {
+    /// Pool of the key headers, or `None` for fewer than two keys, which need no rotation
+    pub(crate) fn new(keys: Vec<(HeaderName, HeaderValue)>) -> Option<Self> {
+        if keys.len() < 2 {
+            return None;
+        }
+
+        Some(KeyPool {
+            state: Mutex::new(KeyPoolState {
+                cursor: 0,
+                limited_until: vec![None; keys.len()],
+            }),
+            keys,
+        })
+    }
+
+    /// Sets the header of the next key on `headers`, returning the key's index
+    pub(crate) fn select(&self, headers: &mut HeaderMap) -> usize {
+        let index = self.next_at(Utc::now());
+
+        // a retry may still carry the header of another kind of key
+        for (name, _) in &self.keys {
+            headers.remove(name);
+        }
+        let (name, value) = &self.keys[index];
+        headers.insert(name.clone(), value.clone());
+
+        index
+    }
+
+    pub(crate) fn next_at(&self, now: DateTime<Utc>) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let len = self.keys.len();
+
+        let index = (0..len)
+            .map(|offset| (state.cursor + offset) % len)
+            .find(|&index| state.limited_until[index].is_none_or(|until| until <= now))
+            .unwrap_or_else(|| {
+                (0..len)
+                    .min_by_key(|&index| state.limited_until[index])
+                    .unwrap_or_default()
+            });
+        state.cursor = (index + 1) % len;
+
+        index
+    }
+
+    /// Records that CoinGecko rate limited a key, returning whether another key can be used right away
+    pub(crate) fn rate_limited(&self, index: usize, retry_after: Option<Duration>) -> bool {
+        self.rate_limited_at(Utc::now(), index, retry_after)
+    }
+
+    pub(crate) fn rate_limited_at(
+        &self,
+        now: DateTime<Utc>,
+        index: usize,
+        retry_after: Option<Duration>,
+    ) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let limited_for = chrono::Duration::from_std(retry_after.unwrap_or(RATE_LIMITED_FOR))
+            .unwrap_or(chrono::Duration::MAX);
+        state.limited_until[index] = Some(
+            now.checked_add_signed(limited_for)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        );
+
+        state
+            .limited_until
+            .iter()
+            .any(|until| until.is_none_or(|until| until <= now))
+    }
+
+    /// Number of keys
+    pub(crate) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether any of the keys is a Pro API key
+    pub(crate) fn is_pro(&self) -> bool {
+        self.keys.iter().any(|(name, _)| name == "x-cg-pro-api-key")
+    }
+}
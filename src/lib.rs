@@ -13,22 +13,110 @@
 //! coingecko = "1.0.0"
 //! ```
 
+/// API keys for authenticated requests
+mod api_key;
+/// Blocking CoinGecko Client
+#[cfg(feature = "blocking")]
+pub mod blocking;
+/// Client builder module
+mod builder;
+/// ETag cache for conditional requests
+#[cfg(feature = "cache")]
+mod cache;
+/// Circuit breaker for CoinGecko outages
+mod circuit;
 /// Client module
 mod client;
+/// Deserialization helpers for response structs
+mod de;
+/// Request paths shared by the async and blocking clients
+mod endpoints;
+/// Error type for API requests
+mod error;
+/// File names of requests, shared by the VCR transports and the history cache
+#[cfg(any(feature = "cache", feature = "vcr"))]
+mod file_name;
+/// Human-readable numbers for the `Display` impls of response structs
+mod format;
+/// Rotation between several API keys
+mod key_pool;
 /// CoinGecko API Parameters
 pub mod params;
+/// Rate limit state reported by CoinGecko
+mod rate_limit;
+/// Short-lived cache of reference data like exchange rates
+mod reference_cache;
+/// Request builders for endpoints taking many parameters
+pub mod requests;
 /// Response structs for API requests
 pub mod response;
+/// Retry policy for rate limited requests
+mod retry;
+/// Sharing one response between identical concurrent requests
+mod single_flight;
+/// Paginated streams over list endpoints and concurrent batches
+#[cfg(feature = "stream")]
+mod stream;
+/// Client-side rate limiter
+mod throttle;
+/// HTTP transport used by CoinGeckoClient, replaceable for testing
+pub mod transport;
+/// Recording and replaying responses from disk
+#[cfg(feature = "vcr")]
+mod vcr;
+/// CoinGecko API Key
+pub use crate::api_key::ApiKey;
+/// CoinGecko Client Builder
+pub use crate::builder::CoinGeckoClientBuilder;
 /// CoinGecko Client
 pub use crate::client::CoinGeckoClient;
+/// Default chunk size of `prices_chunked`
+pub use crate::client::DEFAULT_PRICE_CHUNK_SIZE;
+/// CoinGecko API hosts
+pub use crate::client::{DEMO_HOST, PRO_HOST, PUBLIC_HOST};
+/// CoinGecko Error
+pub use crate::error::CoinGeckoError;
+/// CoinGecko Rate Limit
+pub use crate::rate_limit::RateLimit;
 
 #[cfg(test)]
 mod tests {
+    use crate::requests::CoinsMarketsRequest;
+    use crate::response::{
+        coins::{
+            CirculatingSupplyChart, CoinsMarketItem, Description, DetailPlatform, MarketChart,
+            MarketData, Ohlc,
+        },
+        common::{Amount, Ticker, TrustScore},
+        derivatives::{Derivative, DerivativeExchange, DerivativeExchangeDetail},
+        exchange_rates::{ExchangeRates, RateType},
+        exchanges::Exchange,
+        global::{Global, GlobalDefi},
+        simple::Price,
+        trending::Trending,
+    };
     use crate::{
-        params::{MarketsOrder, OhlcDays, PriceChangePercentage, TickersOrder},
-        CoinGeckoClient,
+        params::{
+            AssetPlatformFilter, AssetPlatformId, ChartDays, CoinId, CompaniesCoinId,
+            ExchangeStatus, IncludeTokens, Interval, MarketsFilter, MarketsOrder, NftsOrder,
+            OhlcDays, OhlcInterval, Precision, PriceChangePercentage, TickersOrder, TopCoins,
+            TopGainersLosersDuration, VsCurrency,
+        },
+        transport::{HttpResponse, HttpTransport, TransportFuture},
+        ApiKey, CoinGeckoClient, CoinGeckoError, RateLimit,
+    };
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use reqwest::{header::HeaderMap, StatusCode};
+    use std::{
+        collections::HashMap,
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{
+            mpsc::{self, Receiver},
+            Arc, Mutex,
+        },
+        time::Duration,
     };
-    use chrono::NaiveDate;
 
     macro_rules! aw {
         ($e:expr) => {
@@ -36,258 +124,3365 @@ mod tests {
         };
     }
 
-    // ---------------------------------------------
-    //  /ping
-    // ---------------------------------------------
-    #[test]
-    #[doc(hidden)]
-    fn ping() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
-        let res = aw!(client.ping());
-        assert!(res.is_ok(), "ping should resolve");
-        assert_eq!(res.unwrap().gecko_says, "(V3) To the Moon!");
+    /// Answers every connection on a local port with the given response and returns its host url
+    fn serve(status: &str, headers: &[&str], body: &str) -> String {
+        serve_recording(status, headers, body).0
+    }
+
+    /// Like `serve`, also passing on the raw text of every request received
+    fn serve_recording(status: &str, headers: &[&str], body: &str) -> (String, Receiver<String>) {
+        let mut response = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+        for header in headers {
+            response.push_str(&format!("{}\r\n", header));
+        }
+        response.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::channel();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0; 4096];
+                let len = stream.read(&mut request).unwrap_or(0);
+                let _ = requests.send(String::from_utf8_lossy(&request[..len]).into_owned());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (host, received)
+    }
+
+    /// Answers every request with the JSON `respond` returns for its url, recording the urls and Pro API key headers
+    struct FakeTransport {
+        respond: fn(&str) -> &'static str,
+        requests: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    impl FakeTransport {
+        fn new(respond: fn(&str) -> &'static str) -> Self {
+            FakeTransport {
+                respond,
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl HttpTransport for FakeTransport {
+        fn get<'a>(&'a self, url: &'a str, headers: HeaderMap) -> TransportFuture<'a> {
+            let api_key = headers
+                .get("x-cg-pro-api-key")
+                .map(|value| value.to_str().unwrap().to_string());
+            self.requests
+                .lock()
+                .unwrap()
+                .push((url.to_string(), api_key));
+
+            let body = (self.respond)(url).as_bytes().to_vec();
+            Box::pin(async {
+                Ok(HttpResponse {
+                    status: StatusCode::OK,
+                    headers: HeaderMap::new(),
+                    body,
+                })
+            })
+        }
     }
 
     // ---------------------------------------------
-    //  /simple
+    //  client
     // ---------------------------------------------
     #[test]
-    fn price() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
-        let res_1 = aw!(client.price(&["bitcoin"], &["usd"], true, true, true, true));
+    fn timeout() {
+        // accepts connections but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let _connections: Vec<_> = listener.incoming().collect();
+        });
+
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .timeout(Duration::from_millis(200))
+            .build();
+        let res = aw!(client.ping());
 
-        assert!(res_1.is_ok(), "price should resolve");
-        let price_1 = &res_1.unwrap()["bitcoin"];
-        assert!(price_1.usd.is_some(), "usd price should be defined");
-        assert!(
-            price_1.usd_market_cap.is_some(),
-            "usd price should be defined"
-        );
-        assert!(
-            price_1.usd24_h_vol.is_some(),
-            "usd 24h vol should be defined"
-        );
-        assert!(
-            price_1.usd24_h_change.is_some(),
-            "usd 24h change should be defined"
-        );
         assert!(
-            price_1.last_updated_at.is_some(),
-            "usd last update should be defined"
+            matches!(&res, Err(CoinGeckoError::Transport { source, .. }) if source.is_timeout()),
+            "ping should time out"
         );
+        assert!(res.unwrap_err().url().unwrap().ends_with("/ping"));
+    }
 
-        let res_2 = aw!(client.price(&["ethereum"], &["eur"], true, true, true, true));
+    #[test]
+    fn connection_pool() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"{"gecko_says":"(V3) To the Moon!"}"#);
 
-        assert!(res_2.is_ok(), "price should resolve");
-        let price_2 = &res_2.unwrap()["ethereum"];
-        assert!(price_2.eur.is_some(), "eur price should be defined");
-        assert!(
-            price_2.eur_market_cap.is_some(),
-            "eur price should be defined"
-        );
-        assert!(
-            price_2.eur24_h_vol.is_some(),
-            "eur 24h vol should be defined"
-        );
-        assert!(
-            price_2.eur24_h_change.is_some(),
-            "eur 24h change should be defined"
-        );
-        assert!(
-            price_2.last_updated_at.is_some(),
-            "eur last update should be defined"
-        );
+        let client = CoinGeckoClient::builder()
+            .host(host.clone())
+            .pool_max_idle_per_host(2)
+            .pool_idle_timeout(Duration::from_secs(60))
+            .build();
+        aw!(client.ping()).unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET //ping HTTP/1.1"));
+
+        // the test server only speaks HTTP/1
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .http2_prior_knowledge(true)
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::Transport { .. })
+        ));
+        assert!(requests.recv().unwrap().starts_with("PRI * HTTP/2.0"));
     }
 
     #[test]
-    fn token_price() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
-        let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
-        let res = aw!(client.token_price(
-            "ethereum",
-            &[uniswap_contract],
-            &["usd"],
-            true,
-            true,
-            true,
-            true
+    fn request_error_url() {
+        // nothing listens on the port once the listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let client = CoinGeckoClient::builder().host(host.clone()).build();
+        let res = aw!(client.get_json::<serde_json::Value>(
+            "/simple/price",
+            &[("ids", "bitcoin"), ("x_cg_demo_api_key", "CG-secret")],
         ));
 
-        assert!(res.is_ok(), "token price should resolve");
-        let token_price = &res.unwrap()[&uniswap_contract.to_string()];
-        assert!(token_price.usd.is_some(), "usd price should be defined");
-        assert!(
-            token_price.usd_market_cap.is_some(),
-            "usd price should be defined"
+        let err = res.unwrap_err();
+        assert_eq!(
+            err.url(),
+            Some(format!("{}//simple/price?ids=bitcoin", host).as_str())
         );
-        assert!(
-            token_price.usd24_h_vol.is_some(),
-            "usd 24h vol should be defined"
+        assert!(!err.to_string().contains("CG-secret"));
+        assert!(!format!("{:?}", err).contains("CG-secret"));
+    }
+
+    #[test]
+    fn max_response_bytes() {
+        let ping = r#"{"gecko_says":"(V3) To the Moon!"}"#;
+
+        let client = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], ping))
+            .max_response_bytes(16)
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::ResponseTooLarge { limit: 16 })
+        ));
+
+        let client = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], ping))
+            .max_response_bytes(ping.len() as u64)
+            .build();
+        assert!(aw!(client.ping()).is_ok());
+
+        // without a Content-Length the body is only known to be too large while reading it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let _ = stream.read(&mut [0; 4096]);
+                let _ = stream.write_all(
+                    b"HTTP/1.1 200 OK\r\nConnection: close\r\nTransfer-Encoding: chunked\r\n\r\n",
+                );
+                for _ in 0..4 {
+                    let _ = stream.write_all(b"10\r\n0123456789abcdef\r\n");
+                }
+                let _ = stream.write_all(b"0\r\n\r\n");
+            }
+        });
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .max_response_bytes(40)
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::ResponseTooLarge { limit: 40 })
+        ));
+
+        // bodies of custom transports are checked once read
+        let client = CoinGeckoClient::builder()
+            .transport(FakeTransport::new(
+                |_| r#"{"gecko_says":"(V3) To the Moon!"}"#,
+            ))
+            .max_response_bytes(16)
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::ResponseTooLarge { limit: 16 })
+        ));
+    }
+
+    #[cfg(feature = "vcr")]
+    #[test]
+    fn record_replay() {
+        use crate::transport::{RecordingTransport, ReplayTransport};
+
+        let dir = std::env::temp_dir().join(format!("coingecko-vcr-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let recording = CoinGeckoClient::builder()
+            .host("https://pro-api.coingecko.com/api/v3")
+            .transport(RecordingTransport::new(
+                &dir,
+                FakeTransport::new(|url| {
+                    if url.contains("/ping") {
+                        r#"{"gecko_says":"(V3) To the Moon!"}"#
+                    } else {
+                        r#"{"bitcoin":{"usd":19147.5}}"#
+                    }
+                }),
+            ))
+            .build();
+        aw!(recording.ping()).unwrap();
+        aw!(recording.get_json::<serde_json::Value>(
+            "/simple/price",
+            &[("ids", "bitcoin"), ("x_cg_demo_api_key", "CG-secret")],
+        ))
+        .unwrap();
+
+        let mut fixtures: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        fixtures.sort();
+        assert_eq!(
+            fixtures,
+            ["ping.json", "simple%2Fprice%3Fids%3Dbitcoin.json"],
+            "fixtures should be named without the host or API key"
         );
-        assert!(
-            token_price.usd24_h_change.is_some(),
-            "usd 24h change should be defined"
+
+        // replays against any host
+        let replaying = CoinGeckoClient::builder()
+            .transport(ReplayTransport::new(&dir))
+            .build();
+        assert_eq!(
+            aw!(replaying.ping()).unwrap().gecko_says,
+            "(V3) To the Moon!"
         );
-        assert!(
-            token_price.last_updated_at.is_some(),
-            "usd last update should be defined"
+        assert_eq!(
+            aw!(replaying.get_json::<serde_json::Value>("/simple/price", &[("ids", "bitcoin")]))
+                .unwrap()["bitcoin"]["usd"],
+            19147.5
         );
+        assert!(matches!(
+            aw!(replaying.get_json::<serde_json::Value>("/simple/price", &[("ids", "ethereum")])),
+            Err(CoinGeckoError::Fixture { source, .. }) if source.kind() == std::io::ErrorKind::NotFound
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
+    #[cfg(feature = "cache")]
     #[test]
-    fn supported_vs_currencies() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
-        let res = aw!(client.supported_vs_currencies());
+    fn history_cache() {
+        let dir = std::env::temp_dir().join(format!("coingecko-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
 
-        assert!(res.is_ok(), "supported_vs_currencies should resolve");
-        assert!(
-            !res.unwrap().is_empty(),
-            "should return at least one currency"
+        let respond = |_: &str| r#"{"id":"bitcoin","symbol":"btc","name":"Bitcoin","localization":null,"image":{"thumb":"https://example.com/thumb.png","small":"https://example.com/small.png"},"market_data":{"current_price":{"usd":16625.1},"market_cap":{"usd":320000000000},"total_volume":{"usd":9000000000}},"community_data":{"facebook_likes":null,"twitter_followers":null},"developer_data":{"forks":31000,"code_additions_deletions_4_weeks":{"additions":null,"deletions":null}},"public_interest_stats":{"alexa_rank":null,"bing_matches":null}}"#;
+        let past = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+        let today = Utc::now().date_naive();
+
+        let transport = Arc::new(FakeTransport::new(respond));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .history_cache_dir(&dir)
+            .build();
+        aw!(client.coin_history("bitcoin", past, false)).unwrap();
+        aw!(client.coin_history("bitcoin", past, false)).unwrap();
+        aw!(client.coin_history("bitcoin", today, false)).unwrap();
+        aw!(client.coin_history("bitcoin", today, false)).unwrap();
+        assert_eq!(
+            transport.requests.lock().unwrap().len(),
+            3,
+            "only the past date should be served from disk"
         );
+
+        // a later run reads the files written by an earlier one
+        let transport = Arc::new(FakeTransport::new(respond));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .history_cache_dir(&dir)
+            .build();
+        let history = aw!(client.coin_history("bitcoin", past, false)).unwrap();
+        assert_eq!(history.id, "bitcoin");
+        assert!(transport.requests.lock().unwrap().is_empty());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
-    // ---------------------------------------------
-    //  /coins
-    // ---------------------------------------------
     #[test]
-    fn coins_list() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
-        let res = aw!(client.coins_list(true));
-        assert!(res.is_ok(), "list should resolve");
-        assert!(!res.unwrap().is_empty(), "should return at least one coin");
+    fn demo_api_key() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"{"gecko_says":"(V3) To the Moon!"}"#);
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .api_key(ApiKey::Demo("CG-demo".to_string()))
+            .build();
+        aw!(client.ping()).unwrap();
+
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("x-cg-demo-api-key: cg-demo\r\n"));
+        assert!(request.contains(&format!(
+            "user-agent: coingecko-rs/{}\r\n",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(!request.contains("x_cg_pro_api_key"));
+        assert_eq!(
+            CoinGeckoClient::new_with_demo_key("CG-demo").host,
+            "https://api.coingecko.com/api/v3"
+        );
     }
 
     #[test]
-    fn coins_markets() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
+    fn builder_keys_user_agent() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"{"gecko_says":"(V3) To the Moon!"}"#);
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .pro_key("CG-pro")
+            .user_agent("my-app/1.0")
+            .build();
+        aw!(client.ping()).unwrap();
 
-        let res = aw!(client.coins_markets(
-            "usd",
-            &["bitcoin"],
-            None,
-            MarketsOrder::GeckoDesc,
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("x-cg-pro-api-key: cg-pro\r\n"));
+        assert!(request.contains("user-agent: my-app/1.0\r\n"));
+        assert_eq!(
+            request.matches("user-agent").count(),
             1,
-            0,
-            true,
-            &[
-                PriceChangePercentage::OneHour,
-                PriceChangePercentage::TwentyFourHours,
-                PriceChangePercentage::SevenDays,
-                PriceChangePercentage::FourteenDays,
-                PriceChangePercentage::ThirtyDays,
-                PriceChangePercentage::OneYear
-            ],
-        ));
-        assert!(res.is_ok(), "markets should resolve");
+            "the user agent should replace the default one"
+        );
 
-        let res2 = aw!(client.coins_markets(
-            "usd",
-            &[] as &[&str],
-            None,
-            MarketsOrder::MarketCapDesc,
-            250,
-            30,
-            false,
-            &[],
-        ));
-        assert!(
-            res2.is_ok(),
-            "markets should resolve for pages near the end"
+        assert_eq!(
+            CoinGeckoClient::builder().pro_key("CG-pro").build().host,
+            crate::PRO_HOST
+        );
+        assert_eq!(
+            CoinGeckoClient::builder().demo_key("CG-demo").build().host,
+            crate::DEMO_HOST
         );
     }
 
     #[test]
-    fn coin() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
+    fn debug_redacts_api_key() {
+        let client = CoinGeckoClient::new_with_pro_key("CG-secret-key");
+        let debug = format!("{:?}", client.clone());
+        assert!(!debug.contains("CG-secret-key"), "{}", debug);
+        assert!(debug.contains(r#"api_keys: ["***"]"#), "{}", debug);
+        assert!(debug.contains("https://pro-api.coingecko.com/api/v3"));
 
-        // the response of client.coin is huge, we need a bigger thread stack size to handle it
-        let runtime = tokio::runtime::Builder::new_multi_thread()
-            .worker_threads(2)
-            .enable_all()
-            .thread_stack_size(10 * 1024 * 1024)
-            .build()
-            .unwrap();
+        let client = CoinGeckoClient::builder()
+            .api_keys([
+                ApiKey::Demo("CG-first".to_string()),
+                ApiKey::Demo("CG-second".to_string()),
+            ])
+            .build();
+        let debug = format!("{:#?}", client);
+        assert!(!debug.contains("CG-first") && !debug.contains("CG-second"));
+        assert_eq!(debug.matches("\"***\"").count(), 2);
 
-        let res = runtime.block_on(client.coin("bitcoin", true, true, true, true, true, true));
+        assert!(format!("{:?}", CoinGeckoClient::default()).contains("api_keys: []"));
 
-        assert!(res.is_ok(), "coins should resolve");
+        #[cfg(feature = "blocking")]
+        {
+            let client = crate::blocking::CoinGeckoClient::new_with_demo_key("CG-secret-key");
+            let debug = format!("{:?}", client);
+            assert!(!debug.contains("CG-secret-key"));
+            assert!(debug.contains(r#"api_keys: ["***"]"#));
+        }
     }
 
     #[test]
-    fn coin_tickers() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
-
-        let res1 = aw!(client.coin_tickers::<&str>(
-            "bitcoin",
-            None,
-            true,
-            1,
-            TickersOrder::VolumeDesc,
-            true
+    fn api_keys() {
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"{"gecko_says":"(V3) To the Moon!"}"#,
         ));
+        let client = CoinGeckoClient::builder()
+            .api_keys([
+                ApiKey::Pro("CG-a".to_string()),
+                ApiKey::Pro("CG-b".to_string()),
+            ])
+            .transport(transport.clone())
+            .build();
+        assert_eq!(client.host, "https://pro-api.coingecko.com/api/v3");
+        for _ in 0..3 {
+            aw!(client.ping()).unwrap();
+        }
 
-        assert!(res1.is_ok(), "tickers without filter should resolve");
+        let keys: Vec<_> = transport
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, key)| key.clone().unwrap())
+            .collect();
+        assert_eq!(keys, ["CG-a", "CG-b", "CG-a"]);
 
-        let res2 = aw!(client.coin_tickers(
-            "bitcoin",
-            #[allow(clippy::useless_vec)]
-            Some(&vec![String::from("binance")]), // &Vec<String> should also work
-            true,
-            1,
-            TickersOrder::VolumeDesc,
-            true
+        let (host, requests) = serve_recording("429 Too Many Requests", &["Retry-After: 30"], "");
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .api_keys([
+                ApiKey::Demo("CG-a".to_string()),
+                ApiKey::Demo("CG-b".to_string()),
+            ])
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::RateLimited { .. })
         ));
 
-        assert!(res2.is_ok(), "tickers without page should resolve");
+        let first = requests.recv().unwrap().to_lowercase();
+        let second = requests.recv().unwrap().to_lowercase();
+        assert!(first.contains("x-cg-demo-api-key: cg-a\r\n"));
+        assert!(
+            second.contains("x-cg-demo-api-key: cg-b\r\n"),
+            "a rate limited request should be retried with the next key"
+        );
+        assert!(
+            requests.try_recv().is_err(),
+            "each key should be tried once"
+        );
+    }
 
-        let res3 = aw!(client.coin_tickers(
-            "bitcoin",
-            Some(&["binance"]),
-            true,
+    #[test]
+    fn key_pool() {
+        let header = |key: &'static str| {
+            (
+                reqwest::header::HeaderName::from_static("x-cg-demo-api-key"),
+                reqwest::header::HeaderValue::from_static(key),
+            )
+        };
+        let pool =
+            crate::key_pool::KeyPool::new(vec![header("a"), header("b"), header("c")]).unwrap();
+        let now = Utc::now();
+
+        assert_eq!(pool.next_at(now), 0);
+        assert!(pool.rate_limited_at(now, 1, Some(Duration::from_secs(30))));
+        assert_eq!(pool.next_at(now), 2, "rate limited keys should be skipped");
+        assert_eq!(pool.next_at(now), 0);
+
+        assert!(pool.rate_limited_at(now, 0, None));
+        assert!(!pool.rate_limited_at(now, 2, None));
+        assert_eq!(
+            pool.next_at(now),
             1,
-            TickersOrder::VolumeDesc,
-            true
-        ));
+            "the key free again soonest should be used when all are rate limited"
+        );
+        assert_eq!(pool.next_at(now + chrono::Duration::seconds(31)), 1);
 
-        assert!(res3.is_ok(), "tickers should resolve");
+        assert!(crate::key_pool::KeyPool::new(vec![header("a")]).is_none());
     }
 
     #[test]
-    fn coin_history() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
+    fn pro_api_key() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"{"gecko_says":"(V3) To the Moon!"}"#);
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .api_key(ApiKey::Pro("CG-pro".to_string()))
+            .build();
+        aw!(client.ping()).unwrap();
 
-        let res = aw!(client.coin_history("bitcoin", NaiveDate::from_ymd(2017, 12, 30), true));
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET //ping HTTP/1.1"));
+        assert!(request
+            .to_lowercase()
+            .contains("x-cg-pro-api-key: cg-pro\r\n"));
+        assert_eq!(
+            CoinGeckoClient::new_with_pro_key("CG-pro").host,
+            "https://pro-api.coingecko.com/api/v3"
+        );
+        assert_eq!(
+            format!("{:?}", ApiKey::Pro("CG-pro".to_string())),
+            "Pro(..)"
+        );
+        assert_eq!(
+            CoinGeckoClient::new_with_key("https://some.url", ApiKey::Pro("CG-pro".to_string()))
+                .host,
+            "https://some.url"
+        );
+    }
 
-        assert!(res.is_ok(), "history should resolve");
+    #[test]
+    fn hosts() {
+        assert_eq!(CoinGeckoClient::default().host, crate::PUBLIC_HOST);
+        assert_eq!(
+            CoinGeckoClient::new_with_demo_key("CG-demo").host,
+            crate::DEMO_HOST
+        );
+        assert_eq!(
+            CoinGeckoClient::new_with_pro_key("CG-pro").host,
+            crate::PRO_HOST
+        );
     }
 
+    #[cfg(feature = "gzip")]
     #[test]
-    fn coin_market_chart() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
+    fn accept_encoding() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"{"gecko_says":"(V3) To the Moon!"}"#);
+        let client = CoinGeckoClient::builder().host(host).build();
+        aw!(client.ping()).unwrap();
+
+        let request = requests.recv().unwrap().to_lowercase();
+        let accept_encoding = request
+            .lines()
+            .find_map(|line| line.strip_prefix("accept-encoding: "))
+            .unwrap();
+        assert!(accept_encoding.contains("gzip"));
+    }
 
-        let res = aw!(client.coin_market_chart("bitcoin", "usd", 1, true));
+    #[test]
+    fn with_client() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"{"gecko_says":"(V3) To the Moon!"}"#);
+        let client = reqwest::Client::builder()
+            .user_agent("custom-agent")
+            .build()
+            .unwrap();
+        let client = CoinGeckoClient::with_client(host, client);
+        aw!(client.ping()).unwrap();
 
-        assert!(res.is_ok(), "market chart should resolve");
+        assert!(requests
+            .recv()
+            .unwrap()
+            .to_lowercase()
+            .contains("user-agent: custom-agent\r\n"));
     }
 
     #[test]
-    fn coin_market_chart_range() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
+    fn transport() {
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"{"gecko_says":"(V3) To the Moon!"}"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .api_key(ApiKey::Pro("CG-pro".to_string()))
+            .transport(transport.clone())
+            .build();
+        let res = aw!(client.ping()).unwrap();
+
+        assert_eq!(res.gecko_says, "(V3) To the Moon!");
+        assert_eq!(
+            *transport.requests.lock().unwrap(),
+            [(
+                "https://pro-api.coingecko.com/api/v3//ping".to_string(),
+                Some("CG-pro".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn endpoint_encoding() {
+        assert_eq!(
+            crate::endpoints::coin("wrapped bitcoin#1", false, false, false, false, false, false),
+            "/coins/wrapped%20bitcoin%231?localization=false&tickers=false&market_data=false&community_data=false&developer_data=false&sparkline=false"
+        );
+        assert_eq!(
+            crate::endpoints::contract("ethereum", "0xab/../c+d"),
+            "/coins/ethereum/contract/0xab%2F..%2Fc%2Bd"
+        );
+        assert_eq!(
+            crate::endpoints::exchange("gdax?x=1"),
+            "/exchanges/gdax%3Fx%3D1"
+        );
+        assert_eq!(
+            crate::endpoints::price(&["bitcoin&vs_currencies=eur", "a,b"], &["usd"], false, false, false, false, None),
+            "/simple/price?ids=bitcoin%26vs_currencies%3Deur%2Ca%2Cb&vs_currencies=usd&include_market_cap=false&include_24hr_vol=false&include_24hr_change=false&include_last_updated_at=false"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart(
+                "bitcoin",
+                "usd&days=max",
+                ChartDays::Days(1),
+                true,
+                None
+            ),
+            "/coins/bitcoin/market_chart?vs_currency=usd%26days%3Dmax&days=1&interval=daily"
+        );
+    }
 
-        let from = NaiveDate::from_ymd(2014, 2, 16).and_hms(19, 0, 32);
-        let to = NaiveDate::from_ymd(2015, 1, 30).and_hms(0, 20, 32);
+    #[test]
+    fn params_from_str() {
+        use crate::params::{DerivativeExchangeOrder, DerivativesIncludeTickers};
 
-        let res = aw!(client.coin_market_chart_range("bitcoin", "usd", from, to));
+        for order in MarketsOrder::VARIANTS {
+            assert_eq!(order.to_string().parse(), Ok(order));
+        }
+        for order in TickersOrder::VARIANTS {
+            assert_eq!(order.to_string().parse(), Ok(order));
+        }
+        for order in DerivativeExchangeOrder::VARIANTS {
+            assert_eq!(order.to_string().parse(), Ok(order));
+        }
+        for include_tickers in DerivativesIncludeTickers::VARIANTS {
+            assert_eq!(include_tickers.to_string().parse(), Ok(include_tickers));
+        }
 
-        assert!(res.is_ok(), "market chart range should resolve");
+        assert_eq!("volume_desc".parse(), Ok(MarketsOrder::VolumeDesc));
+        assert_eq!(
+            DerivativeExchangeOrder::TradeVolume24hBtcDesc.to_string(),
+            "trade_volume_24h_btc_desc"
+        );
+
+        let err = "volume_dsc".parse::<TickersOrder>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown tickers order `volume_dsc`, expected one of trust_score_asc, trust_score_desc, volume_desc"
+        );
     }
 
     #[test]
-    fn coin_ohlc() {
-        let client: CoinGeckoClient = CoinGeckoClient::default();
+    fn get_json() {
+        assert_eq!(crate::endpoints::raw("coins/list", &[]), "/coins/list");
+        assert_eq!(
+            crate::endpoints::raw("/search", &[("query", "wrapped btc"), ("x", "a&b")]),
+            "/search?query=wrapped+btc&x=a%26b"
+        );
 
-        let res = aw!(client.coin_ohlc("bitcoin", "usd", OhlcDays::OneDay));
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"{"gecko_says":"(V3) To the Moon!","new_field":1}"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .api_key(ApiKey::Demo("CG-demo".to_string()))
+            .transport(transport.clone())
+            .build();
+        let res: serde_json::Value = aw!(client.get_json("/ping", &[])).unwrap();
 
-        assert!(res.is_ok(), "ohlc should resolve");
+        assert_eq!(res["new_field"], 1);
+        assert_eq!(
+            transport.requests.lock().unwrap()[0].0,
+            "https://api.coingecko.com/api/v3//ping"
+        );
+    }
+
+    #[test]
+    fn error_body() {
+        let unlisted = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], r#"{"error":"coin not found"}"#))
+            .build();
+        assert!(
+            matches!(
+                aw!(unlisted.contract("ethereum", "0x0000000000000000000000000000000000000001")),
+                Err(CoinGeckoError::NotFound { .. })
+            ),
+            "an error body should map to NotFound instead of a decode error"
+        );
+
+        let invalid = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], r#"{"error":"invalid vs_currency"}"#))
+            .build();
+        assert!(matches!(
+            aw!(invalid.ping()),
+            Err(CoinGeckoError::Api { message, .. }) if message == "invalid vs_currency"
+        ));
+
+        let garbage = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], "<html></html>"))
+            .build();
+        assert!(matches!(
+            aw!(garbage.ping()),
+            Err(CoinGeckoError::Decode { .. })
+        ));
+    }
+
+    #[test]
+    fn html_error_page() {
+        let page = format!(
+            "<!DOCTYPE html><html><head><title>Just a moment...</title></head><body>{}</body></html>",
+            "x".repeat(500)
+        );
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &["Content-Type: text/html; charset=UTF-8"],
+                &page,
+            ))
+            .build();
+
+        match aw!(client.ping()) {
+            Err(CoinGeckoError::UnexpectedContentType {
+                content_type,
+                body_snippet,
+            }) => {
+                assert_eq!(content_type, "text/html; charset=UTF-8");
+                assert!(body_snippet.starts_with("<!DOCTYPE html>"));
+                assert_eq!(body_snippet.len(), 200);
+            }
+            res => panic!("expected UnexpectedContentType, got {:?}", res),
+        }
+
+        let json = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &["Content-Type: application/json; charset=utf-8"],
+                r#"{"gecko_says":"(V3) To the Moon!"}"#,
+            ))
+            .build();
+        assert!(aw!(json.ping()).is_ok());
+    }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn etag_cache() {
+        use reqwest::header::{HeaderValue, ETAG, IF_NONE_MATCH};
+
+        // answers with the full list once, then with 304 Not Modified while the ETag matches
+        struct EtagTransport {
+            not_modified: Mutex<usize>,
+        }
+
+        impl HttpTransport for EtagTransport {
+            fn get<'a>(&'a self, _url: &'a str, headers: HeaderMap) -> TransportFuture<'a> {
+                let revalidated =
+                    headers.get(IF_NONE_MATCH) == Some(&HeaderValue::from_static("\"v1\""));
+                if revalidated {
+                    *self.not_modified.lock().unwrap() += 1;
+                }
+
+                Box::pin(async move {
+                    let mut headers = HeaderMap::new();
+                    headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+
+                    Ok(match revalidated {
+                        true => HttpResponse {
+                            status: StatusCode::NOT_MODIFIED,
+                            headers,
+                            body: Vec::new(),
+                        },
+                        false => HttpResponse {
+                            status: StatusCode::OK,
+                            headers,
+                            body: br#"[{"id":"bitcoin","symbol":"btc","name":"Bitcoin"}]"#.to_vec(),
+                        },
+                    })
+                })
+            }
+        }
+
+        let transport = Arc::new(EtagTransport {
+            not_modified: Mutex::new(0),
+        });
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .etag_cache(true)
+            .build();
+
+        let first = aw!(client.coins_list(false)).unwrap();
+        let second = aw!(client.clone().coins_list(false)).unwrap();
+
+        assert_eq!(first[0].id, "bitcoin");
+        assert_eq!(second[0].id, "bitcoin");
+        assert_eq!(*transport.not_modified.lock().unwrap(), 1);
+
+        let uncached = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        aw!(uncached.coins_list(false)).unwrap();
+        aw!(uncached.coins_list(false)).unwrap();
+        assert_eq!(*transport.not_modified.lock().unwrap(), 1);
+    }
+
+    #[cfg(feature = "cancellation")]
+    #[test]
+    fn cancellation_token() {
+        use tokio_util::sync::CancellationToken;
+
+        // never answers, so only the token can end the request
+        struct PendingTransport;
+
+        impl HttpTransport for PendingTransport {
+            fn get<'a>(&'a self, _url: &'a str, _headers: HeaderMap) -> TransportFuture<'a> {
+                Box::pin(std::future::pending())
+            }
+        }
+
+        let token = CancellationToken::new();
+        let client = CoinGeckoClient::builder()
+            .transport(PendingTransport)
+            .cancellation_token(token.clone())
+            .build();
+
+        let canceller = token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+        assert!(matches!(aw!(client.ping()), Err(CoinGeckoError::Cancelled)));
+
+        // a cancelled token stops requests before they are sent
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"{"gecko_says":"(V3) To the Moon!"}"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let batch = CancellationToken::new();
+        let scoped = client.with_cancellation_token(batch.clone());
+        batch.cancel();
+
+        assert!(matches!(aw!(scoped.ping()), Err(CoinGeckoError::Cancelled)));
+        assert!(transport.requests.lock().unwrap().is_empty());
+        assert!(aw!(client.ping()).is_ok());
+        assert_eq!(transport.requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn coalesce_requests() {
+        // answers after yielding once, so concurrent requests overlap
+        struct SlowTransport {
+            status: StatusCode,
+            requests: Mutex<Vec<String>>,
+        }
+
+        impl HttpTransport for SlowTransport {
+            fn get<'a>(&'a self, url: &'a str, _headers: HeaderMap) -> TransportFuture<'a> {
+                self.requests.lock().unwrap().push(url.to_string());
+                Box::pin(async move {
+                    tokio::task::yield_now().await;
+                    Ok(HttpResponse {
+                        status: self.status,
+                        headers: HeaderMap::new(),
+                        body: br#"{"gecko_says":"(V3) To the Moon!"}"#.to_vec(),
+                    })
+                })
+            }
+        }
+
+        let transport = Arc::new(SlowTransport {
+            status: StatusCode::OK,
+            requests: Mutex::new(Vec::new()),
+        });
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .coalesce_requests(true)
+            .build();
+        let (a, b, c, raw) = aw!(async {
+            tokio::join!(
+                client.ping(),
+                client.ping(),
+                client.ping(),
+                client.get_json::<serde_json::Value>("/ping", &[("x", "1")])
+            )
+        });
+        assert_eq!(a.unwrap().gecko_says, "(V3) To the Moon!");
+        assert_eq!(b.unwrap().gecko_says, "(V3) To the Moon!");
+        assert_eq!(c.unwrap().gecko_says, "(V3) To the Moon!");
+        assert!(raw.is_ok());
+        assert_eq!(
+            transport.requests.lock().unwrap().len(),
+            2,
+            "identical requests should be sent once"
+        );
+
+        // later requests are sent again
+        aw!(client.ping()).unwrap();
+        assert_eq!(transport.requests.lock().unwrap().len(), 3);
+
+        let failing = Arc::new(SlowTransport {
+            status: StatusCode::NOT_FOUND,
+            requests: Mutex::new(Vec::new()),
+        });
+        let client = CoinGeckoClient::builder()
+            .transport(failing.clone())
+            .coalesce_requests(true)
+            .build();
+        let (a, b) = aw!(async { tokio::join!(client.ping(), client.ping()) });
+        assert!(matches!(a, Err(CoinGeckoError::NotFound { .. })));
+        assert!(matches!(b, Err(CoinGeckoError::NotFound { .. })));
+        assert_eq!(failing.requests.lock().unwrap().len(), 1);
+
+        let uncoalesced = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let (a, b) = aw!(async { tokio::join!(uncoalesced.ping(), uncoalesced.ping()) });
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(transport.requests.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn error_status() {
+        let not_found = CoinGeckoClient::builder()
+            .host(serve(
+                "404 Not Found",
+                &[],
+                "{\"error\":\"coin not found\"}",
+            ))
+            .build();
+        let res = aw!(not_found.ping());
+        assert!(
+            matches!(res, Err(CoinGeckoError::NotFound { .. })),
+            "404 should map to NotFound"
+        );
+
+        let rate_limited = CoinGeckoClient::builder()
+            .host(serve("429 Too Many Requests", &[], ""))
+            .build();
+        let res = aw!(rate_limited.ping());
+        assert!(
+            matches!(res, Err(CoinGeckoError::RateLimited { .. })),
+            "429 should map to RateLimited"
+        );
+
+        let server_error = CoinGeckoClient::builder()
+            .host(serve("500 Internal Server Error", &[], "oops"))
+            .build();
+        let res = aw!(server_error.ping());
+        assert!(
+            matches!(res, Err(CoinGeckoError::Http { status, ref body, .. }) if status == 500 && body == "oops"),
+            "500 should map to Http with the body"
+        );
+    }
+
+    #[test]
+    fn error_unauthorized() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "401 Unauthorized",
+                &[],
+                r#"{"status":{"error_code":10005,"error_message":"You may not have access to this endpoint"}}"#,
+            ))
+            .build();
+        let res = aw!(client.global_market_cap_chart(30, "usd"));
+
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, CoinGeckoError::Unauthorized { status, ref message, ref body, .. } if status == 401 && message.as_deref() == Some("You may not have access to this endpoint") && body.contains("10005")),
+            "401 should map to Unauthorized with the message of the body"
+        );
+        assert!(err
+            .to_string()
+            .ends_with("You may not have access to this endpoint"));
+    }
+
+    #[test]
+    fn error_forbidden() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "403 Forbidden",
+                &[],
+                r#"{"status":{"error_code":10012,"error_message":"This endpoint is only available to Analyst plans and above"}}"#,
+            ))
+            .build();
+        let res = aw!(client.global_market_cap_chart(30, "usd"));
+
+        let err = res.unwrap_err();
+        assert!(
+            matches!(err, CoinGeckoError::Forbidden { ref plan_hint, ref body, .. } if plan_hint.as_deref() == Some("This endpoint is only available to Analyst plans and above") && body.contains("10012")),
+            "403 should map to Forbidden with the message of the body"
+        );
+        assert!(err.to_string().ends_with("Analyst plans and above"));
+
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "403 Forbidden",
+                &["content-type: text/html"],
+                "<html>Access denied</html>",
+            ))
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::Forbidden {
+                plan_hint: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn error_rate_limited_body() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "429 Too Many Requests",
+                &["Retry-After: 30"],
+                r#"{"status":{"error_code":429,"error_message":"You've exceeded the Rate Limit. Please visit https://www.coingecko.com/en/api/pricing to subscribe to our API plans for higher rate limits."}}"#,
+            ))
+            .build();
+
+        let err = aw!(client.ping()).unwrap_err();
+        assert!(
+            matches!(err, CoinGeckoError::RateLimited { retry_after, ref message, .. } if retry_after == Some(Duration::from_secs(30)) && message.as_deref().is_some_and(|message| message.starts_with("You've exceeded the Rate Limit"))),
+            "429 should map to RateLimited with the message of the body and the Retry-After"
+        );
+        assert_eq!(err.to_string(), "rate limited by CoinGecko, retry in 30s");
+
+        let client = CoinGeckoClient::builder()
+            .host(serve("429 Too Many Requests", &[], ""))
+            .build();
+        assert!(matches!(
+            aw!(client.ping()),
+            Err(CoinGeckoError::RateLimited {
+                retry_after: None,
+                message: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn error_decode() {
+        let client = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], "[]"))
+            .build();
+        let res = aw!(client.ping());
+
+        assert!(
+            matches!(res, Err(CoinGeckoError::Decode { .. })),
+            "unexpected shape should map to Decode"
+        );
+    }
+
+    #[test]
+    fn invalid_params() {
+        let transport = Arc::new(FakeTransport::new(|_| "[]"));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+
+        let res = aw!(client.coin_market_chart("bitcoin", "usd", 0, true, None));
+        assert!(matches!(
+            res,
+            Err(CoinGeckoError::InvalidParam { name: "days", .. })
+        ));
+        let res = aw!(client.coin_market_chart("bitcoin", "usd", -5, true, None));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "invalid days: must be at least 1, got -5"
+        );
+        let res = aw!(client.exchange_volume_chart("binance", -1));
+        assert!(matches!(
+            res,
+            Err(CoinGeckoError::InvalidParam { name: "days", .. })
+        ));
+        let res = aw!(client.exchanges(0, 1));
+        assert!(matches!(
+            res,
+            Err(CoinGeckoError::InvalidParam {
+                name: "per_page",
+                ..
+            })
+        ));
+        let res = aw!(CoinsMarketsRequest::new("usd").page(0).send(&client));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "invalid page: must be at least 1, got 0"
+        );
+        assert!(transport.requests.lock().unwrap().is_empty());
+
+        assert!(aw!(client.exchanges(1, 1)).is_ok());
+        assert_eq!(transport.requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &["x-ratelimit-limit: 30", "x-ratelimit-remaining: 29"],
+                "{\"gecko_says\":\"(V3) To the Moon!\"}",
+            ))
+            .build();
+        assert_eq!(client.last_rate_limit(), None);
+
+        let res = aw!(client.clone().ping());
+        assert!(res.is_ok(), "ping should resolve");
+        assert_eq!(
+            client.last_rate_limit(),
+            Some(RateLimit {
+                limit: Some(30),
+                remaining: Some(29),
+                reset: None,
+            }),
+            "rate limit should be shared with the clone that made the request"
+        );
+    }
+
+    #[test]
+    fn retry() {
+        let client = CoinGeckoClient::builder()
+            .host(serve("429 Too Many Requests", &["Retry-After: 0"], ""))
+            .max_retries(2)
+            .build();
+        let res = aw!(client.ping());
+
+        assert!(
+            matches!(res, Err(CoinGeckoError::RateLimited { .. })),
+            "exhausted retries should map to RateLimited"
+        );
+    }
+
+    #[test]
+    fn retry_backoff() {
+        let policy = crate::retry::RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_wait: Duration::from_secs(5),
+            jitter: false,
+        };
+
+        assert_eq!(
+            policy.delay(0, Duration::ZERO, None),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.delay(1, Duration::from_secs(1), None),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay(2, Duration::from_secs(3), None),
+            None,
+            "waiting 4s more would exceed max_wait"
+        );
+        assert_eq!(
+            policy.delay(1, Duration::from_secs(1), Some(Duration::from_secs(3))),
+            Some(Duration::from_secs(3)),
+            "Retry-After should take precedence"
+        );
+        assert_eq!(
+            policy.delay(1, Duration::from_secs(1), Some(Duration::MAX)),
+            None,
+            "an overflowing Retry-After should exceed max_wait"
+        );
+        assert_eq!(
+            policy.delay(3, Duration::ZERO, None),
+            None,
+            "retries should be exhausted"
+        );
+    }
+
+    #[test]
+    fn throttle() {
+        let throttle = crate::throttle::Throttle::per_minute(30).unwrap();
+        let now = Utc::now();
+
+        assert_eq!(throttle.reserve_at(now), Duration::ZERO);
+        assert_eq!(throttle.reserve_at(now), Duration::from_secs(2));
+        assert_eq!(throttle.reserve_at(now), Duration::from_secs(4));
+
+        let later = now + chrono::Duration::seconds(60);
+        assert_eq!(
+            throttle.reserve_at(later),
+            Duration::ZERO,
+            "unused slots should not be saved up for bursts"
+        );
+        assert_eq!(throttle.reserve_at(later), Duration::from_secs(2));
+
+        assert!(crate::throttle::Throttle::per_minute(0).is_none());
+    }
+
+    #[test]
+    fn circuit_breaker() {
+        let circuit = crate::circuit::CircuitBreaker::new(
+            2,
+            Duration::from_secs(60),
+            Duration::from_secs(30),
+        )
+        .unwrap();
+        let outage = CoinGeckoError::Http {
+            url: String::new(),
+            status: StatusCode::BAD_GATEWAY,
+            body: String::new(),
+        };
+        let now = Utc::now();
+
+        circuit.record_at(now, Some(&outage));
+        circuit.record_at(now, None);
+        circuit.record_at(now, Some(&outage));
+        circuit.record_at(now, Some(&CoinGeckoError::NotFound { url: String::new() }));
+        circuit.record_at(now, Some(&outage));
+        assert!(
+            circuit.check_at(now).is_ok(),
+            "successes and client errors should reset the count"
+        );
+
+        let later = now + chrono::Duration::seconds(61);
+        circuit.record_at(later, Some(&outage));
+        assert!(
+            circuit.check_at(later).is_ok(),
+            "failures outside the window should not count"
+        );
+
+        circuit.record_at(later, Some(&outage));
+        assert!(matches!(
+            circuit.check_at(later + chrono::Duration::seconds(10)),
+            Err(CoinGeckoError::CircuitOpen { retry_after }) if retry_after == Duration::from_secs(20)
+        ));
+
+        let probe = later + chrono::Duration::seconds(30);
+        assert!(
+            circuit.check_at(probe).is_ok(),
+            "a probe should be let through"
+        );
+        circuit.record_at(probe, Some(&outage));
+        assert!(
+            circuit.check_at(probe).is_err(),
+            "a failed probe should open the circuit again"
+        );
+
+        assert!(crate::circuit::CircuitBreaker::new(0, Duration::ZERO, Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn circuit_breaker_requests() {
+        let (host, requests) = serve_recording("503 Service Unavailable", &[], "");
+        let client = CoinGeckoClient::builder()
+            .host(host)
+            .circuit_breaker_threshold(2)
+            .build();
+
+        for _ in 0..2 {
+            assert!(matches!(
+                aw!(client.ping()),
+                Err(CoinGeckoError::Http { .. })
+            ));
+        }
+        assert!(matches!(
+            aw!(client.clone().ping()),
+            Err(CoinGeckoError::CircuitOpen { .. })
+        ));
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn requests_per_minute() {
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"{"gecko_says":"(V3) To the Moon!"}"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .requests_per_minute(600)
+            .build();
+        let clone = client.clone();
+
+        let started = std::time::Instant::now();
+        aw!(client.ping()).unwrap();
+        aw!(clone.ping()).unwrap();
+        aw!(client.ping()).unwrap();
+
+        assert!(
+            started.elapsed() >= Duration::from_millis(200),
+            "requests should be spaced 100ms apart across clones"
+        );
+        assert_eq!(transport.requests.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn blocking() {
+        let client = crate::blocking::CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &["x-ratelimit-remaining: 29"],
+                "{\"gecko_says\":\"(V3) To the Moon!\"}",
+            ))
+            .build_blocking();
+        let res = client.ping();
+
+        assert_eq!(res.unwrap().gecko_says, "(V3) To the Moon!");
+        assert_eq!(client.last_rate_limit().unwrap().remaining, Some(29));
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn blocking_max_response_bytes() {
+        let client = crate::blocking::CoinGeckoClient::builder()
+            .host(serve("500 Internal Server Error", &[], &"x".repeat(64)))
+            .max_response_bytes(32)
+            .build_blocking();
+
+        assert!(matches!(
+            client.ping(),
+            Err(CoinGeckoError::ResponseTooLarge { limit: 32 })
+        ));
+    }
+
+    // ---------------------------------------------
+    //  /ping
+    // ---------------------------------------------
+    #[test]
+    #[doc(hidden)]
+    fn ping() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+        let res = aw!(client.ping());
+        assert!(res.is_ok(), "ping should resolve");
+        assert_eq!(res.unwrap().gecko_says, "(V3) To the Moon!");
+    }
+
+    #[test]
+    fn is_up() {
+        let up = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"gecko_says":"(V3) To the Moon!"}"#,
+            ))
+            .build();
+        assert!(aw!(up.is_up()));
+
+        let down = CoinGeckoClient::builder()
+            .host(serve("503 Service Unavailable", &[], ""))
+            .build();
+        assert!(!aw!(down.is_up()));
+
+        let unexpected = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], "<html></html>"))
+            .build();
+        assert!(!aw!(unexpected.is_up()));
+    }
+
+    #[test]
+    fn strict_schema() {
+        let body = r#"{"gecko_says":"(V3) To the Moon!","new_field":1}"#;
+        let res = serde_json::from_str::<crate::response::ping::SimplePing>(body);
+        assert_eq!(res.is_err(), cfg!(feature = "strict-schema"));
+    }
+
+    #[test]
+    #[cfg(feature = "extra-fields")]
+    fn extra_fields() {
+        let body = r#"{"id":"binance","name":"Binance","new_field":[1,2]}"#;
+        let res = serde_json::from_str::<Exchange>(body);
+
+        if cfg!(feature = "strict-schema") {
+            assert!(res.unwrap_err().to_string().contains("new_field"));
+        } else {
+            let exchange = res.unwrap();
+            assert_eq!(exchange.extra.len(), 1);
+            assert_eq!(exchange.extra["new_field"], serde_json::json!([1, 2]));
+            assert_eq!(
+                serde_json::to_value(&exchange).unwrap()["new_field"],
+                serde_json::json!([1, 2])
+            );
+        }
+    }
+
+    // ---------------------------------------------
+    //  /simple
+    // ---------------------------------------------
+    #[test]
+    fn price() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+        let res_1 = aw!(client.price(&["bitcoin"], &["usd"], true, true, true, true, None));
+
+        assert!(res_1.is_ok(), "price should resolve");
+        let price_1 = &res_1.unwrap()["bitcoin"];
+        assert!(price_1.get("usd").is_some(), "usd price should be defined");
+        assert!(
+            price_1.market_cap("usd").is_some(),
+            "usd price should be defined"
+        );
+        assert!(
+            price_1.vol_24h("usd").is_some(),
+            "usd 24h vol should be defined"
+        );
+        assert!(
+            price_1.change_24h("usd").is_some(),
+            "usd 24h change should be defined"
+        );
+        assert!(
+            price_1.last_updated_at.is_some(),
+            "usd last update should be defined"
+        );
+
+        let res_2 = aw!(client.price(&["ethereum"], &["eur"], true, true, true, true, None));
+
+        assert!(res_2.is_ok(), "price should resolve");
+        let price_2 = &res_2.unwrap()["ethereum"];
+        assert!(price_2.get("eur").is_some(), "eur price should be defined");
+        assert!(
+            price_2.market_cap("eur").is_some(),
+            "eur price should be defined"
+        );
+        assert!(
+            price_2.vol_24h("eur").is_some(),
+            "eur 24h vol should be defined"
+        );
+        assert!(
+            price_2.change_24h("eur").is_some(),
+            "eur 24h change should be defined"
+        );
+        assert!(
+            price_2.last_updated_at.is_some(),
+            "eur last update should be defined"
+        );
+    }
+
+    #[test]
+    fn strict_currencies() {
+        let transport = Arc::new(FakeTransport::new(|url| {
+            if url.ends_with("/simple/supported_vs_currencies") {
+                r#"["usd","eur"]"#
+            } else {
+                r#"{"bitcoin":{"usd":19147.5}}"#
+            }
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .strict_currencies(true)
+            .build();
+
+        assert!(
+            aw!(client.price(&["bitcoin"], &["USD"], false, false, false, false, None)).is_ok()
+        );
+        assert!(matches!(
+            aw!(client.price(&["bitcoin"], &["usd", "usdd"], false, false, false, false, None)),
+            Err(CoinGeckoError::UnsupportedCurrencies(ref currencies)) if currencies == &["usdd"]
+        ));
+        assert!(matches!(
+            aw!(CoinsMarketsRequest::new("xyz").send(&client)),
+            Err(CoinGeckoError::UnsupportedCurrencies(_))
+        ));
+
+        // supported currencies are fetched once, rejected requests are never sent
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].0.contains("/simple/price?"));
+    }
+
+    #[test]
+    fn prices_chunked() {
+        assert_eq!(
+            crate::endpoints::chunk_ids(&["bitcoin", "ethereum", "bitcoin", "tether", "solana"], 2),
+            [vec!["bitcoin", "ethereum"], vec!["tether", "solana"]]
+        );
+        assert_eq!(
+            crate::endpoints::chunk_ids(&["bitcoin"], 0),
+            [vec!["bitcoin"]]
+        );
+
+        let transport = Arc::new(FakeTransport::new(|url| {
+            if url.contains("ids=bitcoin%2Cethereum&") {
+                r#"{"bitcoin":{"usd":19147.5},"ethereum":{"usd":1290.25}}"#
+            } else {
+                r#"{"tether":{"usd":1.0}}"#
+            }
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let res = aw!(client.prices_chunked(
+            &["bitcoin", "ethereum", "tether", "ethereum"],
+            &["usd"],
+            false,
+            false,
+            false,
+            false,
+            None,
+            Some(2),
+            Some(2)
+        ))
+        .unwrap();
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res["ethereum"].get("usd"), "1290.25".parse().ok());
+        assert_eq!(res["tether"].get("usd"), "1.0".parse().ok());
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].0.contains("ids=tether&"));
+    }
+
+    #[test]
+    fn join_bounded() {
+        let in_flight = Mutex::new((0, 0));
+        let futures = (0..10).map(|i| {
+            let in_flight = &in_flight;
+            async move {
+                {
+                    let mut in_flight = in_flight.lock().unwrap();
+                    in_flight.0 += 1;
+                    in_flight.1 = in_flight.1.max(in_flight.0);
+                }
+                for _ in 0..i % 3 + 1 {
+                    tokio::task::yield_now().await;
+                }
+                in_flight.lock().unwrap().0 -= 1;
+                i
+            }
+        });
+
+        let outputs = aw!(crate::client::join_bounded(futures, 3));
+        assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+        assert_eq!(*in_flight.lock().unwrap(), (0, 3));
+    }
+
+    #[test]
+    fn price_precision() {
+        assert_eq!(
+            crate::endpoints::price(&["bitcoin"], &["usd"], false, false, false, false, Some("full")),
+            "/simple/price?ids=bitcoin&vs_currencies=usd&include_market_cap=false&include_24hr_vol=false&include_24hr_change=false&include_last_updated_at=false&precision=full"
+        );
+        assert_eq!(Precision::Decimals(18).param().unwrap(), "18");
+
+        let client = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], "{}"))
+            .build();
+        assert!(matches!(
+            aw!(client.price(
+                &["bitcoin"],
+                &["usd"],
+                false,
+                false,
+                false,
+                false,
+                Some(Precision::Decimals(19))
+            )),
+            Err(CoinGeckoError::InvalidPrecision(19))
+        ));
+    }
+
+    #[test]
+    fn price_by_id() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"bitcoin":{"usd":19147.5},"ethereum":{"usd":1300.25}}"#,
+            ))
+            .build();
+        let bitcoin = CoinId::new("bitcoin");
+        let prices = aw!(client.price_by_id(
+            &[&bitcoin, &CoinId::new("ethereum")],
+            &["usd"],
+            false,
+            false,
+            false,
+            false,
+            None
+        ))
+        .unwrap();
+
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[&bitcoin].get("usd"), "19147.5".parse().ok());
+        assert_eq!(String::from(bitcoin), "bitcoin");
+    }
+
+    #[test]
+    fn token_price_include_tokens() {
+        let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
+
+        assert_eq!(
+            crate::endpoints::token_price("ethereum", &[uniswap_contract], &["usd"], false, false, false, false, None, None),
+            "/simple/token_price/ethereum?contract_addresses=0x1f9840a85d5af5bf1d1762f925bdaddc4201f984&vs_currencies=usd&include_market_cap=false&include_24hr_vol=false&include_24hr_change=false&include_last_updated_at=false"
+        );
+        assert!(crate::endpoints::token_price(
+            "ethereum",
+            &[uniswap_contract],
+            &["usd"],
+            false,
+            false,
+            false,
+            false,
+            Some("full"),
+            Some(IncludeTokens::Top)
+        )
+        .ends_with("&precision=full&include_tokens=top"));
+    }
+
+    #[test]
+    fn price_deserialize() {
+        let price: Price = serde_json::from_str(
+            r#"{"usd":0.00000000123,"usd_market_cap":"1234.5","usd_24h_vol":null,"last_updated_at":1665561600}"#,
+        )
+        .unwrap();
+
+        #[cfg(feature = "decimal")]
+        {
+            use std::str::FromStr;
+            assert_eq!(
+                price.get("usd"),
+                Some(Amount::from_str("0.00000000123").unwrap())
+            );
+            assert_eq!(
+                price.market_cap("usd"),
+                Some(Amount::from_str("1234.5").unwrap())
+            );
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            assert_eq!(price.get("usd"), Some(0.00000000123));
+            assert_eq!(price.market_cap("usd"), Some(1234.5));
+        }
+        assert_eq!(price.vol_24h("usd"), None);
+        assert_eq!(price.get("eur"), None);
+    }
+
+    #[test]
+    fn price_accessors() {
+        let price: Price = serde_json::from_str(
+            r#"{"usd":19147.5,"usd_market_cap":367000000000,"usd_24h_vol":28000000000,"usd_24h_change":-1.25,"eur":19600.75}"#,
+        )
+        .unwrap();
+
+        assert_eq!(price.get("usd"), "19147.5".parse().ok());
+        assert_eq!(price.get("USD"), "19147.5".parse().ok());
+        assert_eq!(price.market_cap("usd"), "367000000000".parse().ok());
+        assert_eq!(price.vol_24h("usd"), "28000000000".parse().ok());
+        assert_eq!(price.change_24h("usd"), "-1.25".parse().ok());
+        assert_eq!(price.get("eur"), "19600.75".parse().ok());
+        assert_eq!(price.market_cap("eur"), None);
+        assert_eq!(price.get("gbp"), None);
+        assert_eq!(price.get("doge"), None);
+
+        let lira: Price = serde_json::from_str(r#"{"try":2345678.5,"try_24h_vol":null}"#).unwrap();
+        assert_eq!(lira.get("try"), "2345678.5".parse().ok());
+        assert_eq!(lira.vol_24h("try"), None);
+    }
+
+    #[test]
+    fn price_display() {
+        let prices: HashMap<String, Price> = serde_json::from_str(
+            r#"{"bitcoin":{"usd":67152.5,"usd_market_cap":1321749463618.4,"usd_24h_vol":27941906094,"usd_24h_change":0.76886,"chf":-3.1,"btc":1.0,"btc_24h_change":-0.004}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            prices["bitcoin"].to_string(),
+            "BTC ₿1.00, 24h +0.00%\n\
+             CHF -3.10\n\
+             USD $67,152.50, market cap $1,321,749,463,618.40, 24h volume $27,941,906,094.00, 24h +0.77%"
+        );
+
+        let meme: HashMap<String, Price> =
+            serde_json::from_str(r#"{"pepe":{"usd":0.00001234,"eur":1234567}}"#).unwrap();
+        assert_eq!(
+            meme["pepe"].to_string(),
+            "EUR €1,234,567.00\nUSD $0.00001234"
+        );
+
+        let cents: Price =
+            serde_json::from_str(r#"{"usd":0.0912345,"eur":0.09000000000000001}"#).unwrap();
+        assert_eq!(cents.to_string(), "EUR €0.09\nUSD $0.09");
+    }
+
+    #[test]
+    fn token_price() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+        let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
+        let res = aw!(client.token_price(
+            "ethereum",
+            &[uniswap_contract],
+            &["usd"],
+            true,
+            true,
+            true,
+            true,
+            None,
+            None
+        ));
+
+        assert!(res.is_ok(), "token price should resolve");
+        let token_price = &res.unwrap()[&uniswap_contract.to_string()];
+        assert!(
+            token_price.get("usd").is_some(),
+            "usd price should be defined"
+        );
+        assert!(
+            token_price.market_cap("usd").is_some(),
+            "usd price should be defined"
+        );
+        assert!(
+            token_price.vol_24h("usd").is_some(),
+            "usd 24h vol should be defined"
+        );
+        assert!(
+            token_price.change_24h("usd").is_some(),
+            "usd 24h change should be defined"
+        );
+        assert!(
+            token_price.last_updated_at.is_some(),
+            "usd last update should be defined"
+        );
+    }
+
+    #[test]
+    fn supported_vs_currencies() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+        let res = aw!(client.supported_vs_currencies());
+
+        assert!(res.is_ok(), "supported_vs_currencies should resolve");
+        assert!(
+            !res.unwrap().is_empty(),
+            "should return at least one currency"
+        );
+    }
+
+    // ---------------------------------------------
+    //  /coins
+    // ---------------------------------------------
+    #[test]
+    fn vs_currency() {
+        assert_eq!(VsCurrency::from("usd"), VsCurrency::Usd);
+        assert_eq!(VsCurrency::from("USD"), VsCurrency::Usd);
+        assert_eq!(
+            VsCurrency::from("XAU".to_string()),
+            VsCurrency::Custom("xau".to_string())
+        );
+        assert_eq!(
+            VsCurrency::from("usdd"),
+            VsCurrency::Custom("usdd".to_string())
+        );
+        assert_eq!(VsCurrency::Eth.to_string(), "eth");
+        assert_eq!(VsCurrency::Custom("xau".to_string()).as_ref(), "xau");
+    }
+
+    #[test]
+    fn asset_platform_id() {
+        assert_eq!(
+            AssetPlatformId::from("polygon-pos"),
+            AssetPlatformId::Polygon
+        );
+        assert_eq!(
+            AssetPlatformId::from("zksync"),
+            AssetPlatformId::Custom("zksync".to_string())
+        );
+        assert_eq!(
+            AssetPlatformId::BinanceSmartChain.to_string(),
+            "binance-smart-chain"
+        );
+
+        let transport = Arc::new(FakeTransport::new(|_| "{}"));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        aw!(client.token_price(
+            AssetPlatformId::Arbitrum,
+            &["0x912ce59144191c1204e64559fe8253a0e49e6548"],
+            &["usd"],
+            false,
+            false,
+            false,
+            false,
+            None,
+            None
+        ))
+        .unwrap();
+
+        assert!(transport.requests.lock().unwrap()[0]
+            .0
+            .contains("/simple/token_price/arbitrum-one?"));
+    }
+
+    #[test]
+    fn coins_list() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+        let res = aw!(client.coins_list(true));
+        assert!(res.is_ok(), "list should resolve");
+        assert!(!res.unwrap().is_empty(), "should return at least one coin");
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn coins_list_stream() {
+        use futures::{StreamExt, TryStreamExt};
+
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &["Content-Type: application/json"],
+                r#" [ {"id":"bitcoin","symbol":"btc","name":"Bitcoin","platforms":{}} ,
+                {"id":"tether","symbol":"usdt","name":"Tether","platforms":{"ethereum":"0xdac17f958d2ee523a2206206994597c13d831ec7"}}]"#,
+            ))
+            .build();
+        let coins: Vec<_> = aw!(client.coins_list_stream(true).try_collect()).unwrap();
+        assert_eq!(coins.len(), 2);
+        assert_eq!(coins[0].id, "bitcoin");
+        assert_eq!(
+            coins[1].platforms.as_ref().unwrap()["ethereum"].as_deref(),
+            Some("0xdac17f958d2ee523a2206206994597c13d831ec7")
+        );
+
+        let client = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], "[]"))
+            .build();
+        let coins: Vec<_> = aw!(client.coins_list_stream(false).try_collect()).unwrap();
+        assert!(coins.is_empty());
+
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"[{"id":"bitcoin"},{"id":"ethereum"}]"#,
+            ))
+            .build();
+        let coins: Vec<_> = aw!(client.coins_list_stream(false).collect());
+        assert_eq!(coins.len(), 1);
+        assert!(matches!(coins[0], Err(CoinGeckoError::Decode { .. })));
+
+        let client = CoinGeckoClient::builder()
+            .host(serve("200 OK", &[], r#"{"error":"plan limit"}"#))
+            .build();
+        let coins: Vec<_> = aw!(client.coins_list_stream(false).collect());
+        assert!(matches!(&coins[..], [Err(CoinGeckoError::Api { .. })]));
+    }
+
+    #[test]
+    fn coins_list_new() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"[{"id":"long-mao","symbol":"lmao","name":"Long Mao","activated_at":1712562430}]"#,
+            ))
+            .build();
+        let res = aw!(client.coins_list_new()).unwrap();
+
+        assert_eq!(res[0].id, "long-mao");
+        assert_eq!(
+            res[0].activated_at,
+            Utc.with_ymd_and_hms(2024, 4, 8, 7, 47, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn top_gainers_losers() {
+        assert_eq!(
+            crate::endpoints::top_gainers_losers(
+                "usd",
+                TopGainersLosersDuration::SevenDays,
+                Some(TopCoins::All)
+            ),
+            "/coins/top_gainers_losers?vs_currency=usd&duration=7d&top_coins=all"
+        );
+
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"top_gainers":[{"id":"bonk","symbol":"bonk","name":"Bonk","image":"https://example.com/bonk.png","market_cap_rank":75,"usd":0.00002547,"usd_24h_vol":520000000.5,"usd_7d_change":48.37}],"top_losers":[{"id":"sats-ordinals","symbol":"sats","name":"SATS (Ordinals)","image":"https://example.com/sats.png","market_cap_rank":null,"usd":0.0000004,"usd_24h_vol":null,"usd_7d_change":-22.5}]}"#,
+            ))
+            .build();
+        let res = aw!(client.top_gainers_losers("usd", TopGainersLosersDuration::SevenDays, None))
+            .unwrap();
+
+        let gainer = &res.top_gainers[0];
+        assert_eq!(gainer.market_cap_rank, Some(75));
+        assert_eq!(gainer.price, "0.00002547".parse().ok());
+        assert_eq!(gainer.volume24_h, "520000000.5".parse().ok());
+        assert_eq!(gainer.price_change_percentage, "48.37".parse().ok());
+
+        let loser = &res.top_losers[0];
+        assert_eq!(loser.market_cap_rank, None);
+        assert_eq!(loser.volume24_h, None);
+        assert_eq!(loser.price_change_percentage, "-22.5".parse().ok());
+    }
+
+    #[test]
+    fn resolve_coin_id() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"[{"id":"bitcoin","symbol":"btc","name":"Bitcoin"},{"id":"ethereum","symbol":"eth","name":"Ethereum"},{"id":"ethereum-wormhole","symbol":"ETH","name":"Ethereum (Wormhole)"}]"#,
+            ))
+            .build();
+
+        assert_eq!(
+            aw!(client.resolve_coin_id("eth")).unwrap(),
+            [CoinId::from("ethereum"), CoinId::from("ethereum-wormhole")]
+        );
+        assert!(aw!(client.resolve_coin_id("usdd")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn coins_markets() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        let res = aw!(client.coins_markets(
+            "usd",
+            &["bitcoin"],
+            None,
+            MarketsOrder::GeckoDesc,
+            1,
+            1,
+            true,
+            &[
+                PriceChangePercentage::OneHour,
+                PriceChangePercentage::TwentyFourHours,
+                PriceChangePercentage::SevenDays,
+                PriceChangePercentage::FourteenDays,
+                PriceChangePercentage::ThirtyDays,
+                PriceChangePercentage::OneYear
+            ],
+        ));
+        assert!(res.is_ok(), "markets should resolve");
+
+        let res2 = aw!(client.coins_markets(
+            "usd",
+            &[] as &[&str],
+            None,
+            MarketsOrder::MarketCapDesc,
+            250,
+            30,
+            false,
+            &[],
+        ));
+        assert!(
+            res2.is_ok(),
+            "markets should resolve for pages near the end"
+        );
+    }
+
+    #[test]
+    fn coins_markets_request() {
+        let (host, requests) = serve_recording("200 OK", &[], "[]");
+        let client = CoinGeckoClient::builder().host(host).build();
+
+        let res = aw!(CoinsMarketsRequest::new("usd").send(&client)).unwrap();
+        assert!(res.is_empty());
+        assert!(requests.recv().unwrap().starts_with(
+            "GET //coins/markets?vs_currency=usd&ids=&order=market_cap_desc&per_page=100&page=1&sparkline=false&price_change_percentage= "
+        ));
+
+        aw!(CoinsMarketsRequest::new(VsCurrency::Eur)
+            .ids(&["bitcoin", "ethereum"])
+            .order(MarketsOrder::VolumeDesc)
+            .per_page(10)
+            .page(2)
+            .sparkline(true)
+            .price_change_percentage([
+                PriceChangePercentage::OneHour,
+                PriceChangePercentage::SevenDays
+            ])
+            .send(&client))
+        .unwrap();
+        assert!(requests.recv().unwrap().starts_with(
+            "GET //coins/markets?vs_currency=eur&ids=bitcoin%2Cethereum&order=volume_desc&per_page=10&page=2&sparkline=true&price_change_percentage=1h%2C7d "
+        ));
+    }
+
+    #[test]
+    fn coins_markets_filter() {
+        let transport = Arc::new(FakeTransport::new(|_| "[]"));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+
+        aw!(CoinsMarketsRequest::new("usd")
+            .names(&["Bitcoin", "Wrapped Bitcoin"])
+            .send(&client))
+        .unwrap();
+        aw!(CoinsMarketsRequest::new("usd")
+            .filter(MarketsFilter::Symbols(vec!["btc".to_string()]))
+            .send(&client))
+        .unwrap();
+        aw!(CoinsMarketsRequest::new("usd")
+            .ids(&["bitcoin"])
+            .ids(&[] as &[&str])
+            .category("layer-1")
+            .send(&client))
+        .unwrap();
+
+        let urls: Vec<String> = transport
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(url, _)| url.clone())
+            .collect();
+        assert!(urls[0].contains("ids=&names=Bitcoin%2CWrapped+Bitcoin&order="));
+        assert!(urls[1].contains("ids=&symbols=btc&order="));
+        assert!(
+            urls[2].contains("ids=&category=layer-1&order="),
+            "empty ids should clear the ids filter"
+        );
+
+        let res = aw!(CoinsMarketsRequest::new("usd")
+            .ids(&["bitcoin"])
+            .category("layer-1")
+            .send(&client));
+        assert!(
+            matches!(res, Err(CoinGeckoError::InvalidParam { name: "category", ref reason }) if reason.contains("`ids`"))
+        );
+        let res = aw!(client.coins_markets(
+            "usd",
+            &["bitcoin"],
+            Some("layer-1"),
+            MarketsOrder::MarketCapDesc,
+            100,
+            1,
+            false,
+            &[],
+        ));
+        assert!(matches!(
+            res,
+            Err(CoinGeckoError::InvalidParam {
+                name: "category",
+                ..
+            })
+        ));
+        assert_eq!(
+            transport.requests.lock().unwrap().len(),
+            3,
+            "conflicting filters should not be sent"
+        );
+    }
+
+    #[test]
+    fn coins_markets_deserialize() {
+        let page: Vec<CoinsMarketItem> = serde_json::from_str(
+            r#"[{"id":"bitcoin","symbol":"btc","name":"Bitcoin","image":"https://example.com/bitcoin.png","current_price":19147.5,"market_cap":367000000000,"market_cap_rank":1,"fully_diluted_valuation":402000000000,"total_volume":28000000000,"high_24h":19500,"low_24h":18900,"price_change_24h":-102.3,"price_change_percentage_24h":-0.53,"market_cap_change_24h":-1900000000,"market_cap_change_percentage_24h":-0.52,"circulating_supply":19180000,"total_supply":21000000,"max_supply":21000000,"ath":69045,"ath_change_percentage":-72.2,"ath_date":"2021-11-10T14:24:11.849Z","atl":67.81,"atl_change_percentage":28120.5,"atl_date":"2013-07-06T00:00:00.000Z","roi":null,"last_updated":"2022-10-12T08:00:00.000Z"},{"id":"obscure-token","symbol":"obs","name":"Obscure Token","image":"missing_large.png","current_price":0.0012,"market_cap":0,"market_cap_rank":null,"fully_diluted_valuation":null,"total_volume":12.5,"high_24h":null,"low_24h":null,"price_change_24h":null,"price_change_percentage_24h":null,"market_cap_change_24h":null,"market_cap_change_percentage_24h":null,"circulating_supply":0,"total_supply":null,"max_supply":null,"ath":0.05,"ath_change_percentage":-97.6,"ath_date":"2021-05-01T00:00:00.000Z","atl":0.0009,"atl_change_percentage":33.3,"atl_date":"2022-06-18T00:00:00.000Z","roi":null,"last_updated":null}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(page[0].market_cap_rank, Some(1));
+        assert_eq!(page[0].fully_diluted_valuation, Some(402000000000.0));
+        assert_eq!(page[1].market_cap_rank, None);
+        assert_eq!(page[1].fully_diluted_valuation, None);
+        assert_eq!(page[1].price_change_percentage24_h, None);
+        assert_eq!(page[1].price_change_percentage7_d_in_currency, None);
+    }
+
+    #[test]
+    fn coins_markets_display() {
+        let page: Vec<CoinsMarketItem> = serde_json::from_str(
+            r#"[{"id":"bitcoin","symbol":"btc","name":"Bitcoin","image":"https://example.com/bitcoin.png","current_price":19147.5,"market_cap":367000000000,"market_cap_rank":1,"fully_diluted_valuation":null,"total_volume":null,"high_24h":null,"low_24h":null,"price_change_24h":null,"price_change_percentage_24h":-0.53,"market_cap_change_24h":null,"market_cap_change_percentage_24h":null,"circulating_supply":null,"total_supply":null,"max_supply":null,"ath":null,"ath_change_percentage":null,"ath_date":null,"atl":null,"atl_change_percentage":null,"atl_date":null,"roi":null,"last_updated":null},{"id":"obscure-token","symbol":"obs","name":"Obscure Token","image":"missing_large.png","current_price":0.0012,"market_cap":null,"market_cap_rank":null,"fully_diluted_valuation":null,"total_volume":null,"high_24h":null,"low_24h":null,"price_change_24h":null,"price_change_percentage_24h":null,"market_cap_change_24h":null,"market_cap_change_percentage_24h":null,"circulating_supply":null,"total_supply":null,"max_supply":null,"ath":null,"ath_change_percentage":null,"ath_date":null,"atl":null,"atl_change_percentage":null,"atl_date":null,"roi":null,"last_updated":null}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            page[0].to_string(),
+            "#1 Bitcoin (BTC) 19,147.50, market cap 367,000,000,000.00, 24h -0.53%"
+        );
+        assert_eq!(page[1].to_string(), "Obscure Token (OBS) 0.0012");
+    }
+
+    #[test]
+    fn coins_markets_locale() {
+        let transport = Arc::new(FakeTransport::new(|_| "[]"));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .locale("de")
+            .build();
+
+        aw!(client.coins_markets(
+            "usd",
+            &["bitcoin"],
+            None,
+            MarketsOrder::MarketCapDesc,
+            1,
+            1,
+            false,
+            &[]
+        ))
+        .unwrap();
+        aw!(CoinsMarketsRequest::new("usd").locale("ja").send(&client)).unwrap();
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        aw!(CoinsMarketsRequest::new("usd").send(&client)).unwrap();
+
+        let requests = transport.requests.lock().unwrap();
+        assert!(requests[0].0.ends_with("&locale=de"));
+        assert!(requests[1].0.ends_with("&locale=ja"));
+        assert!(!requests[2].0.contains("locale"));
+    }
+
+    #[test]
+    fn coins_markets_sparkline() {
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"[{"id":"bitcoin","symbol":"btc","name":"Bitcoin","image":"https://example.com/bitcoin.png","current_price":19147.5,"market_cap":367000000000,"market_cap_rank":1,"fully_diluted_valuation":402000000000,"total_volume":28000000000,"high_24h":19500,"low_24h":18900,"price_change_24h":-102.3,"price_change_percentage_24h":-0.53,"market_cap_change_24h":-1900000000,"market_cap_change_percentage_24h":-0.52,"circulating_supply":19180000,"total_supply":21000000,"max_supply":21000000,"ath":69045,"ath_change_percentage":-72.2,"ath_date":"2021-11-10T14:24:11.849Z","atl":67.81,"atl_change_percentage":28120.5,"atl_date":"2013-07-06T00:00:00.000Z","roi":null,"last_updated":"2022-10-12T08:00:00.000Z","sparkline_in_7d":{"price":[19301.2,19254.8,19147.5]}}]"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let page = aw!(client.coins_markets(
+            "usd",
+            &["bitcoin"],
+            None,
+            MarketsOrder::MarketCapDesc,
+            1,
+            1,
+            true,
+            &[]
+        ))
+        .unwrap();
+
+        assert!(transport.requests.lock().unwrap()[0]
+            .0
+            .contains("&sparkline=true&"));
+        assert_eq!(
+            page[0].sparkline_in7_d.as_ref().unwrap().price,
+            [19301.2, 19254.8, 19147.5]
+        );
+
+        let page: Vec<CoinsMarketItem> = serde_json::from_str(
+            r#"[{"id":"bitcoin","symbol":"btc","name":"Bitcoin","image":"https://example.com/bitcoin.png","current_price":19147.5,"market_cap":367000000000,"market_cap_rank":1,"fully_diluted_valuation":null,"total_volume":28000000000,"high_24h":null,"low_24h":null,"price_change_24h":null,"price_change_percentage_24h":null,"market_cap_change_24h":null,"market_cap_change_percentage_24h":null,"circulating_supply":19180000,"total_supply":null,"max_supply":null,"ath":null,"ath_change_percentage":null,"ath_date":null,"atl":null,"atl_change_percentage":null,"atl_date":null,"roi":null,"last_updated":null}]"#,
+        )
+        .unwrap();
+        assert!(page[0].sparkline_in7_d.is_none());
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn coin_tickers_stream() {
+        use futures::TryStreamExt;
+
+        // 100 tickers on the first page and 30 on the second
+        let transport = Arc::new(FakeTransport::new(|url| {
+            let ticker = r#"{"base":"BTC","target":"USDT","market":{"name":"Binance","identifier":"binance","has_trading_incentive":false},"last":19147.5,"volume":81234.56,"converted_last":{"btc":1.0,"eth":14.8,"usd":19147.5},"converted_volume":{"btc":81234.56,"eth":1202345.1,"usd":1555000000.0},"trust_score":"green","bid_ask_spread_percentage":0.01,"is_anomaly":false,"is_stale":false,"coin_id":"bitcoin"}"#;
+            let count = if url.contains("&page=1&") { 100 } else { 30 };
+            let tickers = vec![ticker; count].join(",");
+            Box::leak(format!(r#"{{"name":"Bitcoin","tickers":[{}]}}"#, tickers).into_boxed_str())
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+
+        let tickers: Vec<Ticker> = aw!(client
+            .coin_tickers_stream::<&str>("bitcoin", None, false, TickersOrder::VolumeDesc, false)
+            .try_collect())
+        .unwrap();
+
+        assert_eq!(tickers.len(), 130);
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]
+            .0
+            .contains("/coins/bitcoin/tickers?include_exchange_logo=false&page=2&"));
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn coins_batch() {
+        let transport = Arc::new(FakeTransport::new(|url| {
+            if url.contains("/coins/unlisted?") {
+                r#"{"error":"coin not found"}"#
+            } else {
+                "[]"
+            }
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let res = aw!(client.coins_batch(
+            &["bitcoin", "unlisted", "ethereum"],
+            false,
+            false,
+            true,
+            false,
+            false,
+            false,
+            Some(2)
+        ));
+
+        assert_eq!(res.len(), 3);
+        assert!(matches!(res[0], Err(CoinGeckoError::Decode { .. })));
+        assert!(
+            matches!(res[1], Err(CoinGeckoError::NotFound { .. })),
+            "results should keep the order of the ids"
+        );
+        assert!(matches!(res[2], Err(CoinGeckoError::Decode { .. })));
+        assert_eq!(transport.requests.lock().unwrap().len(), 3);
+
+        let url = |res: &Result<crate::response::coins::CoinsItem, CoinGeckoError>| {
+            res.as_ref().unwrap_err().url().unwrap().to_string()
+        };
+        assert!(url(&res[0]).contains("/coins/bitcoin?"));
+        assert!(url(&res[1]).contains("/coins/unlisted?"));
+        assert!(url(&res[2]).contains("/coins/ethereum?"));
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn coins_batch_not_found_url() {
+        let client = CoinGeckoClient::builder()
+            .host(serve("404 Not Found", &[], ""))
+            .build();
+        let res = aw!(client.coins_batch(
+            &["bitcoin", "ethereum"],
+            false,
+            false,
+            true,
+            false,
+            false,
+            false,
+            None
+        ));
+
+        for (id, res) in ["bitcoin", "ethereum"].iter().zip(&res) {
+            match res {
+                Err(err @ CoinGeckoError::NotFound { .. }) => {
+                    assert!(err.url().unwrap().contains(&format!("/coins/{}?", id)))
+                }
+                other => panic!("expected NotFound, got {:?}", other.as_ref().err()),
+            }
+        }
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn contracts_batch() {
+        let transport = Arc::new(FakeTransport::new(|url| {
+            if url.ends_with("/contract/0xdead") {
+                r#"{"error":"coin not found"}"#
+            } else {
+                r#"{"error":"invalid contract"}"#
+            }
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let res = aw!(client.contracts_batch(
+            AssetPlatformId::Ethereum,
+            &["0x1f98", "0xdead", "0x5149"],
+            Some(2)
+        ));
+
+        assert_eq!(res.len(), 3);
+        assert!(matches!(res[0], Err(CoinGeckoError::Api { .. })));
+        assert!(matches!(res[1], Err(CoinGeckoError::NotFound { .. })));
+        assert!(matches!(res[2], Err(CoinGeckoError::Api { .. })));
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .any(|(url, _)| url.ends_with("/coins/ethereum/contract/0x5149")));
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn coin_history_range() {
+        let transport = Arc::new(FakeTransport::new(|url| {
+            if url.contains("date=02-01-2023") {
+                r#"{"error":"coin not found"}"#
+            } else {
+                r#"{"id":"bitcoin","symbol":"btc","name":"Bitcoin","localization":null,"image":{"thumb":"https://example.com/thumb.png","small":"https://example.com/small.png"},"market_data":{"current_price":{"usd":16625.1},"market_cap":{"usd":320000000000},"total_volume":{"usd":9000000000}},"community_data":{"facebook_likes":null,"twitter_followers":null},"developer_data":{"forks":31000,"code_additions_deletions_4_weeks":{"additions":null,"deletions":null}},"public_interest_stats":{"alexa_rank":null,"bing_matches":null}}"#
+            }
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let from = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+        let to = NaiveDate::from_ymd_opt(2023, 1, 3).unwrap();
+
+        let res = aw!(client.coin_history_range("bitcoin", from, to, false, Some(2)));
+
+        let dates: Vec<_> = res.iter().map(|(date, _)| date.to_string()).collect();
+        assert_eq!(
+            dates,
+            ["2022-12-31", "2023-01-01", "2023-01-02", "2023-01-03"]
+        );
+        assert_eq!(res[0].1.as_ref().unwrap().id, "bitcoin");
+        assert!(matches!(res[2].1, Err(CoinGeckoError::NotFound { .. })));
+        assert!(transport.requests.lock().unwrap()[1]
+            .0
+            .ends_with("/coins/bitcoin/history?date=01-01-2023&localization=false"));
+
+        assert!(aw!(client.coin_history_range("bitcoin", to, from, false, None)).is_empty());
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn coin_ohlc_multi() {
+        let transport = Arc::new(FakeTransport::new(|url| {
+            if url.contains("vs_currency=btc") {
+                "[[1665532800000,0.0688,0.0691,0.0684,0.0689]]"
+            } else if url.contains("vs_currency=usd") {
+                "[[1665532800000,1295.2,1301.7,1281.9,1292.4],[1665534600000,1292.4,1296.0,1288.1,1290.3]]"
+            } else {
+                r#"{"error":"invalid vs_currency"}"#
+            }
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+
+        let ohlc =
+            aw!(client.coin_ohlc_multi("ethereum", &["usd", "btc"], OhlcDays::OneDay)).unwrap();
+
+        assert_eq!(ohlc.len(), 2);
+        assert_eq!(ohlc["usd"].len(), 2);
+        assert_eq!(ohlc["btc"][0].close, 0.0689);
+        assert_eq!(transport.requests.lock().unwrap().len(), 2);
+
+        assert!(matches!(
+            aw!(client.coin_ohlc_multi("ethereum", &["usd", "xyz"], OhlcDays::OneDay)),
+            Err(CoinGeckoError::Api { .. })
+        ));
+    }
+
+    #[test]
+    fn coin() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        // the response of client.coin is huge, we need a bigger thread stack size to handle it
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .thread_stack_size(10 * 1024 * 1024)
+            .build()
+            .unwrap();
+
+        let res = runtime.block_on(client.coin("bitcoin", true, true, true, true, true, true));
+
+        assert!(res.is_ok(), "coins should resolve");
+    }
+
+    #[test]
+    fn detail_platforms_deserialize() {
+        let usdc: HashMap<String, DetailPlatform> = serde_json::from_str(
+            r#"{"ethereum":{"decimal_place":6,"contract_address":"0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"},"solana":{"decimal_place":6,"contract_address":"EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"},"polygon-pos":{"decimal_place":6,"contract_address":"0x3c499c542cef5e3811e1192ce70d8cc03d5c3359"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(usdc.len(), 3);
+        assert_eq!(
+            usdc["ethereum"],
+            DetailPlatform {
+                decimal_place: Some(6),
+                contract_address: "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string(),
+            }
+        );
+        assert_eq!(usdc["solana"].decimal_place, Some(6));
+
+        let bitcoin: HashMap<String, DetailPlatform> =
+            serde_json::from_str(r#"{"":{"decimal_place":null,"contract_address":""}}"#).unwrap();
+        assert_eq!(bitcoin[""].decimal_place, None);
+    }
+
+    #[test]
+    fn market_data_deserialize() {
+        let market_data: MarketData = serde_json::from_str(
+            r#"{"current_price":{"btc":1.0,"eur":62034.12,"usd":67152.0},"total_value_locked":null,"mcap_to_tvl_ratio":null,"fdv_to_tvl_ratio":null,"roi":null,"ath":{"btc":1.003301,"eur":67405.05,"usd":73738.0},"ath_change_percentage":{"btc":-0.32907,"eur":-7.96625,"usd":-8.93068},"ath_date":{"btc":"2019-10-15T16:00:56.136Z","eur":"2024-03-14T07:10:36.635Z","usd":"2024-03-14T07:10:36.635Z"},"atl":{"btc":0.99895134,"eur":51.30,"usd":67.81},"atl_change_percentage":{"btc":0.10498,"eur":120823.5,"usd":98927.34},"atl_date":{"btc":"2019-10-21T00:00:00.000Z","eur":"2013-07-05T00:00:00.000Z","usd":"2013-07-06T00:00:00.000Z"},"market_cap":{"btc":19683081.0,"eur":1221216391102,"usd":1321749463618},"market_cap_rank":1,"fully_diluted_valuation":{"btc":21000000.0,"usd":1410196590000},"total_volume":{"btc":416134.47,"eur":25811916013,"usd":27941906094},"high_24h":{"btc":1.0,"eur":62564.0,"usd":67720.0},"low_24h":{"btc":1.0,"eur":null,"usd":66139.0},"price_change_24h":512.34,"price_change_percentage_24h":0.76886,"price_change_percentage_7d":-3.1,"price_change_percentage_14d":1.2,"price_change_percentage_30d":5.4,"price_change_percentage_60d":12.3,"price_change_percentage_200d":80.2,"price_change_percentage_1y":140.5,"market_cap_change_24h":9943546051,"market_cap_change_percentage_24h":0.75803,"total_supply":21000000.0,"max_supply":21000000.0,"circulating_supply":19683081.0,"last_updated":"2024-04-08T10:40:16.391Z"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(market_data.current_price("usd"), Some(67152.0));
+        assert_eq!(market_data.current_price("EUR"), Some(62034.12));
+        assert_eq!(market_data.market_cap("usd"), Some(1321749463618.0));
+        assert_eq!(market_data.total_volume("btc"), Some(416134.47));
+        assert_eq!(market_data.high_24h("usd"), Some(67720.0));
+        assert_eq!(market_data.low_24h("eur"), None);
+        assert_eq!(market_data.ath("usd"), Some(73738.0));
+        assert_eq!(market_data.ath_change_percentage("usd"), Some(-8.93068));
+        assert_eq!(
+            market_data.ath_date("usd"),
+            Some(
+                Utc.with_ymd_and_hms(2024, 3, 14, 7, 10, 36).unwrap()
+                    + chrono::Duration::milliseconds(635)
+            )
+        );
+        assert_eq!(market_data.atl("usd"), Some(67.81));
+        assert_eq!(
+            market_data.atl_date("usd"),
+            Some(Utc.with_ymd_and_hms(2013, 7, 6, 0, 0, 0).unwrap())
+        );
+        assert_eq!(market_data.current_price("gbp"), None);
+    }
+
+    #[test]
+    fn coin_tickers() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        let res1 = aw!(client.coin_tickers::<&str>(
+            "bitcoin",
+            None,
+            true,
+            1,
+            TickersOrder::VolumeDesc,
+            true
+        ));
+
+        assert!(res1.is_ok(), "tickers without filter should resolve");
+
+        let res2 = aw!(client.coin_tickers(
+            "bitcoin",
+            #[allow(clippy::useless_vec)]
+            Some(&vec![String::from("binance")]), // &Vec<String> should also work
+            true,
+            1,
+            TickersOrder::VolumeDesc,
+            true
+        ));
+
+        assert!(res2.is_ok(), "tickers without page should resolve");
+
+        let res3 = aw!(client.coin_tickers(
+            "bitcoin",
+            Some(&["binance"]),
+            true,
+            1,
+            TickersOrder::VolumeDesc,
+            true
+        ));
+
+        assert!(res3.is_ok(), "tickers should resolve");
+    }
+
+    #[test]
+    fn coin_history() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        let res = aw!(client.coin_history(
+            "bitcoin",
+            NaiveDate::from_ymd_opt(2017, 12, 30).unwrap(),
+            true
+        ));
+
+        assert!(res.is_ok(), "history should resolve");
+    }
+
+    #[test]
+    fn coin_market_chart() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        let res = aw!(client.coin_market_chart("bitcoin", "usd", 1, true, None));
+
+        assert!(res.is_ok(), "market chart should resolve");
+    }
+
+    #[test]
+    fn coin_market_chart_params() {
+        assert_eq!(
+            crate::endpoints::coin_market_chart("bitcoin", "usd", ChartDays::Days(1), true, None),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=1&interval=daily"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart("bitcoin", "usd", ChartDays::Days(1), false, None),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=1"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart(
+                "bitcoin",
+                "usd",
+                ChartDays::Days(1),
+                true,
+                Some("full")
+            ),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=1&interval=daily&precision=full"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart("bitcoin", "usd", ChartDays::Max, false, None),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=max"
+        );
+        assert_eq!(ChartDays::from(-3), ChartDays::Days(-3));
+        assert_eq!(ChartDays::from(90_i64), ChartDays::Days(90));
+    }
+
+    #[test]
+    fn coin_market_chart_with_interval_params() {
+        assert_eq!(
+            crate::endpoints::coin_market_chart_with_interval(
+                "bitcoin",
+                "usd",
+                ChartDays::Days(1),
+                Interval::Auto,
+                None
+            ),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=1"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart_with_interval(
+                "bitcoin",
+                "usd",
+                ChartDays::Days(1),
+                Interval::FiveMinutes,
+                None
+            ),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=1&interval=5m"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart_with_interval(
+                "bitcoin",
+                "usd",
+                ChartDays::Days(30),
+                Interval::Hourly,
+                Some("2")
+            ),
+            "/coins/bitcoin/market_chart?vs_currency=usd&days=30&interval=hourly&precision=2"
+        );
+    }
+
+    #[test]
+    fn coin_market_chart_range() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        let from = NaiveDate::from_ymd_opt(2014, 2, 16)
+            .unwrap()
+            .and_hms_opt(19, 0, 32)
+            .unwrap();
+        let to = NaiveDate::from_ymd_opt(2015, 1, 30)
+            .unwrap()
+            .and_hms_opt(0, 20, 32)
+            .unwrap();
+
+        let res = aw!(client.coin_market_chart_range("bitcoin", "usd", from, to, None, None));
+
+        assert!(res.is_ok(), "market chart range should resolve");
+    }
+
+    #[test]
+    fn invalid_range() {
+        let date = |year, month, day| {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let now = date(2024, 4, 8);
+        let invalid = |from, to| match crate::params::check_range_at(now, from, to) {
+            Err(CoinGeckoError::InvalidParam { name, reason }) => Some((name, reason)),
+            _ => None,
+        };
+
+        assert_eq!(
+            invalid(date(2024, 2, 1), date(2024, 1, 1)).unwrap().0,
+            "from",
+            "a reversed range should be rejected"
+        );
+        assert_eq!(invalid(date(2024, 1, 1), date(2024, 5, 1)).unwrap().0, "to");
+        assert_eq!(
+            invalid(date(2008, 1, 1), date(2024, 1, 1)).unwrap().0,
+            "from"
+        );
+        assert!(invalid(date(2024, 1, 1), date(2024, 1, 1)).is_none());
+        assert!(invalid(date(2013, 4, 28), now).is_none());
+        assert!(
+            invalid(date(2024, 1, 1), now + chrono::Duration::seconds(1)).is_none(),
+            "a `to` slightly ahead of the local clock should be accepted"
+        );
+        assert_eq!(
+            invalid(date(2024, 1, 1), now + chrono::Duration::seconds(61))
+                .unwrap()
+                .0,
+            "to"
+        );
+
+        let transport = Arc::new(FakeTransport::new(|_| "[]"));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let res =
+            aw!(client.exchange_volume_chart_range("binance", date(2024, 2, 1), date(2024, 1, 1)));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "invalid from: must not be after `to`"
+        );
+        assert!(transport.requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn coin_history_localization() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"id":"bitcoin","symbol":"btc","name":"Bitcoin","localization":{"en":"Bitcoin","de":"Bitcoin","ja":"ビットコイン","zh-tw":"比特幣"},"image":{"thumb":"https://example.com/thumb.png","small":"https://example.com/small.png"},"market_data":{"current_price":{"usd":19147.5},"market_cap":{"usd":367000000000},"total_volume":{"usd":28000000000}},"community_data":{"facebook_likes":null,"twitter_followers":null},"developer_data":{"forks":31000,"code_additions_deletions_4_weeks":{"additions":null,"deletions":null}},"public_interest_stats":{"alexa_rank":null,"bing_matches":null}}"#,
+            ))
+            .build();
+        let date = NaiveDate::from_ymd_opt(2022, 10, 12).unwrap();
+        let res = aw!(client.coin_history("bitcoin", date, true)).unwrap();
+
+        let localization = res.localization.as_ref().unwrap();
+        assert_eq!(localization["de"], "Bitcoin");
+        assert_eq!(localization["ja"], "ビットコイン");
+        assert_eq!(localization["zh-tw"], "比特幣");
+        assert_eq!(res.localized_name("ja"), "ビットコイン");
+        assert_eq!(res.localized_name("ZH-TW"), "比特幣");
+        assert_eq!(res.localized_name("ko"), "Bitcoin");
+    }
+
+    #[test]
+    fn description_locale() {
+        let description: Description = serde_json::from_str(
+            r#"{"en":"Bitcoin is the first cryptocurrency.","de":"Bitcoin ist die erste Kryptowährung.","zh-tw":"比特幣","ja":""}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            description.get("de"),
+            Some("Bitcoin ist die erste Kryptowährung.")
+        );
+        assert_eq!(description.get("zh-tw"), Some("比特幣"));
+        assert_eq!(description.get("ja"), None);
+        assert_eq!(description.get("fr"), None);
+        assert_eq!(description.get("xx"), None);
+    }
+
+    #[test]
+    fn contract_market_chart_params() {
+        let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
+        let from = NaiveDate::from_ymd_opt(2022, 10, 12)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let to = NaiveDate::from_ymd_opt(2022, 10, 13)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            crate::endpoints::contract_market_chart("ethereum", uniswap_contract, "usd", ChartDays::Days(1), None),
+            "/coins/ethereum/contract/0x1f9840a85d5af5bf1d1762f925bdaddc4201f984/market_chart?vs_currency=usd&days=1"
+        );
+        assert_eq!(
+            crate::endpoints::contract_market_chart_range("ethereum", uniswap_contract, "usd", from, to, None, None),
+            "/coins/ethereum/contract/0x1f9840a85d5af5bf1d1762f925bdaddc4201f984/market_chart/range?vs_currency=usd&from=1665532800&to=1665619200"
+        );
+        assert_eq!(
+            crate::endpoints::contract_market_chart("ethereum", uniswap_contract, "usd", ChartDays::Days(1), Some("full")),
+            "/coins/ethereum/contract/0x1f9840a85d5af5bf1d1762f925bdaddc4201f984/market_chart?vs_currency=usd&days=1&precision=full"
+        );
+        assert_eq!(
+            crate::endpoints::contract_market_chart_range("ethereum", uniswap_contract, "usd", from, to, Some(Interval::Hourly), Some("full")),
+            "/coins/ethereum/contract/0x1f9840a85d5af5bf1d1762f925bdaddc4201f984/market_chart/range?vs_currency=usd&from=1665532800&to=1665619200&interval=hourly&precision=full"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart_range("bitcoin", "usd", from, to, None, Some("8")),
+            "/coins/bitcoin/market_chart/range?vs_currency=usd&from=1665532800&to=1665619200&precision=8"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart_range("bitcoin", "usd", from, to, Some(Interval::Daily), None),
+            "/coins/bitcoin/market_chart/range?vs_currency=usd&from=1665532800&to=1665619200&interval=daily"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart_range("bitcoin", "usd", from, to, Some(Interval::FiveMinutes), Some("2")),
+            "/coins/bitcoin/market_chart/range?vs_currency=usd&from=1665532800&to=1665619200&interval=5m&precision=2"
+        );
+        assert_eq!(
+            crate::endpoints::coin_market_chart_range(
+                "bitcoin",
+                "usd",
+                from,
+                to,
+                Some(Interval::Auto),
+                None
+            ),
+            "/coins/bitcoin/market_chart/range?vs_currency=usd&from=1665532800&to=1665619200"
+        );
+    }
+
+    #[test]
+    fn coin_ohlc() {
+        let client: CoinGeckoClient = CoinGeckoClient::default();
+
+        let res = aw!(client.coin_ohlc("bitcoin", "usd", OhlcDays::OneDay));
+
+        assert!(res.is_ok(), "ohlc should resolve");
+    }
+
+    #[test]
+    fn coin_ohlc_days() {
+        assert_eq!(
+            crate::endpoints::coin_ohlc("bitcoin", "usd", OhlcDays::Max),
+            "/coins/bitcoin/ohlc?vs_currency=usd&days=max"
+        );
+        assert_eq!(
+            crate::endpoints::coin_ohlc("bitcoin", "usd", OhlcDays::Custom(2)),
+            "/coins/bitcoin/ohlc?vs_currency=usd&days=2"
+        );
+
+        let transport = Arc::new(FakeTransport::new(|_| "[]"));
+        let free = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        assert!(aw!(free.coin_ohlc("bitcoin", "usd", OhlcDays::Custom(30))).is_ok());
+        assert!(matches!(
+            aw!(free.coin_ohlc("bitcoin", "usd", OhlcDays::Max)),
+            Err(CoinGeckoError::InvalidOhlcDays(OhlcDays::Max))
+        ));
+        assert!(matches!(
+            aw!(free.coin_ohlc("bitcoin", "usd", OhlcDays::Custom(2))),
+            Err(CoinGeckoError::InvalidOhlcDays(OhlcDays::Custom(2)))
+        ));
+
+        let pro = CoinGeckoClient::builder()
+            .api_key(ApiKey::Pro("CG-pro".to_string()))
+            .transport(transport.clone())
+            .build();
+        assert!(aw!(pro.coin_ohlc("bitcoin", "usd", OhlcDays::Max)).is_ok());
+
+        assert_eq!(transport.requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn coin_ohlc_range() {
+        let at = |month, day| {
+            NaiveDate::from_ymd_opt(2024, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+
+        let transport = Arc::new(FakeTransport::new(|_| {
+            "[[1704067200000,42261.04,44186.06,42261.04,44179.92]]"
+        }));
+        let client = CoinGeckoClient::builder()
+            .api_key(ApiKey::Pro("CG-pro".to_string()))
+            .transport(transport.clone())
+            .build();
+
+        let candles =
+            aw!(client.coin_ohlc_range("bitcoin", "usd", at(1, 1), at(3, 1), OhlcInterval::Daily))
+                .unwrap();
+        assert_eq!(candles.len(), 1);
+        assert_eq!(
+            transport.requests.lock().unwrap()[0].0,
+            "https://pro-api.coingecko.com/api/v3//coins/bitcoin/ohlc/range?vs_currency=usd&from=1704067200&to=1709251200&interval=daily"
+        );
+
+        assert!(matches!(
+            aw!(client.coin_ohlc_range("bitcoin", "usd", at(1, 1), at(3, 1), OhlcInterval::Hourly)),
+            Err(CoinGeckoError::InvalidParam { name: "to", .. })
+        ));
+        assert!(matches!(
+            aw!(client.coin_ohlc_range("bitcoin", "usd", at(3, 1), at(1, 1), OhlcInterval::Daily)),
+            Err(CoinGeckoError::InvalidParam { name: "from", .. })
+        ));
+        assert_eq!(transport.requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn market_chart_deserialize() {
+        let chart: MarketChart = serde_json::from_str(
+            r#"{"prices":[[1665561600000,19147.5],[1665565200000,19151.25]],"market_caps":[[1665561600000,367000000000]],"total_volumes":[[1665561600000,28000000000]]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(chart.prices.len(), 2);
+        assert_eq!(
+            chart.prices[1].timestamp,
+            Utc.with_ymd_and_hms(2022, 10, 12, 9, 0, 0).unwrap()
+        );
+        assert_eq!(chart.prices[1].value, "19151.25".parse::<Amount>().unwrap());
+        assert_eq!(
+            chart.market_caps[0].raw(),
+            (1665561600000, "367000000000".parse().unwrap())
+        );
+        assert_eq!(
+            serde_json::to_value(&chart.prices[0]).unwrap()[0],
+            serde_json::json!(1665561600000.0)
+        );
+    }
+
+    #[test]
+    fn supply_chart_deserialize() {
+        let chart: CirculatingSupplyChart = serde_json::from_str(
+            r#"{"circulating_supply":[[1712448000000,"19675987.0"],[1712534400000,19676012.5]]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(chart.circulating_supply.len(), 2);
+        assert_eq!(
+            chart.circulating_supply[0].timestamp,
+            Utc.with_ymd_and_hms(2024, 4, 7, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            chart.circulating_supply[0].value,
+            "19675987.0".parse::<Amount>().unwrap()
+        );
+        assert_eq!(
+            chart.circulating_supply[1].value,
+            "19676012.5".parse::<Amount>().unwrap()
+        );
+
+        assert_eq!(
+            crate::endpoints::coin_circulating_supply_chart("bitcoin", ChartDays::Days(30)),
+            "/coins/bitcoin/circulating_supply_chart?days=30"
+        );
+        assert_eq!(
+            crate::endpoints::coin_total_supply_chart("bitcoin", ChartDays::Max),
+            "/coins/bitcoin/total_supply_chart?days=max"
+        );
+
+        let transport = Arc::new(FakeTransport::new(|_| r#"{"circulating_supply":[]}"#));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        assert!(aw!(client.coin_circulating_supply_chart("bitcoin", 30)).is_ok());
+        assert!(transport.requests.lock().unwrap()[0]
+            .0
+            .ends_with("/coins/bitcoin/circulating_supply_chart?days=30"));
+        assert!(matches!(
+            aw!(client.coin_total_supply_chart("bitcoin", 0)),
+            Err(CoinGeckoError::InvalidParam { name: "days", .. })
+        ));
+    }
+
+    #[test]
+    fn coin_ohlc_deserialize() {
+        let candles: Vec<Ohlc> = serde_json::from_str(
+            "[[1665561600000, 19140.55, 19176.87, 19100.47, 19157.04], [1665563400000, 19158.12, 19169.35, 19123.9, 19154.42]]",
+        )
+        .unwrap();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(
+            candles[0].timestamp,
+            Utc.with_ymd_and_hms(2022, 10, 12, 8, 0, 0).unwrap()
+        );
+        assert_eq!(candles[0].open, 19140.55);
+        assert_eq!(candles[0].high, 19176.87);
+        assert_eq!(candles[0].low, 19100.47);
+        assert_eq!(candles[0].close, 19157.04);
+
+        let res = serde_json::from_str::<Vec<Ohlc>>("[[1665561600000, 19140.55, 19176.87]]");
+        assert!(
+            res.is_err(),
+            "candles with fewer than 5 values should not parse"
+        );
+    }
+
+    // ---------------------------------------------
+    //  /asset_platforms
+    // ---------------------------------------------
+    #[test]
+    fn asset_platforms_filtered() {
+        let (host, requests) = serve_recording(
+            "200 OK",
+            &[],
+            r#"[{"id":"ethereum","chain_identifier":1,"name":"Ethereum","shortname":"Ethereum","native_coin_id":"ethereum","image":{"thumb":"https://example.com/thumb.png","small":"https://example.com/small.png","large":"https://example.com/large.png"}}]"#,
+        );
+        let client = CoinGeckoClient::builder().host(host).build();
+        let res = aw!(client.asset_platforms_filtered(Some(AssetPlatformFilter::Nft))).unwrap();
+
+        assert_eq!(res[0].native_coin_id.as_deref(), Some("ethereum"));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET //asset_platforms?filter=nft "));
+        assert_eq!(crate::endpoints::asset_platforms(None), "/asset_platforms");
+    }
+
+    // ---------------------------------------------
+    //  /exchanges
+    // ---------------------------------------------
+    #[test]
+    fn exchanges_list_filtered() {
+        let (host, requests) =
+            serve_recording("200 OK", &[], r#"[{"id":"mtgox","name":"Mt.Gox"}]"#);
+        let client = CoinGeckoClient::builder().host(host).build();
+        let res = aw!(client.exchanges_list_filtered(Some(ExchangeStatus::Inactive))).unwrap();
+
+        assert_eq!(res[0].id, "mtgox");
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET //exchanges/list?status=inactive "));
+        assert_eq!(
+            crate::endpoints::exchanges_list(Some(ExchangeStatus::Active)),
+            "/exchanges/list?status=active"
+        );
+        assert_eq!(crate::endpoints::exchanges_list(None), "/exchanges/list");
+    }
+
+    #[test]
+    fn exchanges_by_volume() {
+        let transport = Arc::new(FakeTransport::new(|_| {
+            r#"[
+                {"id":"small","name":"Small","trade_volume_24h_btc":12.5},
+                {"id":"dead","name":"Dead","trade_volume_24h_btc":null},
+                {"id":"big","name":"Big","trade_volume_24h_btc":98000.1}
+            ]"#
+        }));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        let res = aw!(client.exchanges_by_volume()).unwrap();
+
+        let ids: Vec<_> = res.iter().map(|exchange| exchange.id.as_str()).collect();
+        assert_eq!(ids, ["big", "small", "dead"]);
+
+        // a page shorter than 250 is the last one
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.ends_with("/exchanges?per_page=250&page=1"));
+    }
+
+    #[test]
+    fn exchange_deserialize() {
+        let exchanges: Vec<Exchange> = serde_json::from_str(
+            r#"[{"id":"binance","name":"Binance","year_established":2017,"country":"Cayman Islands","description":"","url":"https://www.binance.com/","image":"https://example.com/binance.png","has_trading_incentive":false,"trust_score":10,"trust_score_rank":1,"trade_volume_24h_btc":178712.75,"trade_volume_24h_btc_normalized":"104521.5"},{"id":"unknown","name":"Unknown","trade_volume_24h_btc":null}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(exchanges[0].trade_volume24_h_btc, "178712.75".parse().ok());
+        assert_eq!(
+            exchanges[0].trade_volume24_h_btc_normalized,
+            "104521.5".parse().ok()
+        );
+        assert_eq!(exchanges[1].trade_volume24_h_btc, None);
+        assert_eq!(exchanges[1].trade_volume24_h_btc_normalized, None);
+    }
+
+    #[test]
+    fn ticker_deserialize() {
+        let tickers: Vec<Ticker> = serde_json::from_str(
+            r#"[{"base":"BTC","target":"USDT","market":{"name":"Binance","identifier":"binance","has_trading_incentive":false,"logo":null},"last":19147.5,"volume":81234.56,"converted_last":{"btc":1.0,"eth":14.8,"usd":19147.5},"converted_volume":{"btc":81234.56,"eth":1202345.1,"usd":1555000000.0},"cost_to_move_up_usd":null,"cost_to_move_down_usd":null,"trust_score":"green","bid_ask_spread_percentage":0.010013,"timestamp":"2022-10-12T08:00:00+00:00","last_traded_at":"2022-10-12T08:00:00+00:00","last_fetch_at":"2022-10-12T08:00:00+00:00","is_anomaly":false,"is_stale":false,"trade_url":null,"token_info_url":null,"coin_id":"bitcoin","target_coin_id":"tether"},{"base":"BTC","target":"USD","market":{"name":"Tiny","identifier":"tiny","has_trading_incentive":false},"last":19200.0,"volume":0.5,"converted_last":{"btc":1.0,"eth":14.8,"usd":19200.0},"converted_volume":{"btc":0.5,"eth":7.4,"usd":9600.0},"trust_score":null,"bid_ask_spread_percentage":null,"is_anomaly":false,"is_stale":true,"coin_id":"bitcoin"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(tickers[0].trust_score, Some(TrustScore::Green));
+        assert_eq!(tickers[0].bid_ask_spread_percentage, Some(0.010013));
+        assert_eq!(tickers[1].trust_score, None);
+        assert_eq!(
+            tickers
+                .iter()
+                .filter(|ticker| ticker.trust_score == Some(TrustScore::Green))
+                .count(),
+            1
+        );
+        assert!(serde_json::from_str::<TrustScore>(r#""blue""#).is_err());
+    }
+
+    #[test]
+    fn exchange_volume_chart_range_params() {
+        let from = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 1, 31)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+
+        assert_eq!(
+            crate::endpoints::exchange_volume_chart_range("binance", from, to),
+            "/exchanges/binance/volume_chart/range?from=1704067200&to=1706659200"
+        );
+    }
+
+    #[test]
+    fn derivative_exchange_deserialize() {
+        let exchange: DerivativeExchange = serde_json::from_str(
+            r#"{"name":"Binance (Futures)","id":"binance_futures","open_interest_btc":279958.61,"trade_volume_24h_btc":"574366.94","number_of_perpetual_pairs":330,"number_of_futures_pairs":44}"#,
+        )
+        .unwrap();
+
+        assert_eq!(exchange.open_interest_btc, "279958.61".parse().ok());
+        assert_eq!(exchange.trade_volume24_h_btc, "574366.94".parse().ok());
+    }
+
+    #[test]
+    fn derivatives_deserialize() {
+        let derivatives: Vec<Derivative> = serde_json::from_str(
+            r#"[{"market":"Deribit","symbol":"BTC-27DEC24","index_id":"BTC","price":"71250.5","price_percentage_change_24h":0.8,"contract_type":"futures","index":"70025.6","basis":1.75,"spread":0.02,"funding_rate":0,"open_interest":1250000000.5,"volume_24h":"3100.25","last_traded_at":1712467658,"expired_at":1735286400},{"market":"Binance (Futures)","symbol":"BTCUSDT","index_id":"BTC","price":69999.5,"price_percentage_change_24h":1.3,"contract_type":"perpetual","index":70025.6,"basis":-0.037,"spread":null,"funding_rate":0.0099,"open_interest":null,"volume_24h":2664.1,"last_traded_at":1712467600,"expired_at":null}]"#,
+        )
+        .unwrap();
+
+        let future = &derivatives[0];
+        assert_eq!(future.price, "71250.5".parse::<Amount>().unwrap());
+        assert_eq!(future.index, Some(70025.6));
+        assert_eq!(future.open_interest, "1250000000.5".parse().ok());
+        assert_eq!(future.volume24_h, "3100.25".parse::<Amount>().unwrap());
+        assert_eq!(
+            future.last_traded_at,
+            Some(Utc.with_ymd_and_hms(2024, 4, 7, 5, 27, 38).unwrap())
+        );
+        assert_eq!(
+            future.expired_at,
+            Some(Utc.with_ymd_and_hms(2024, 12, 27, 8, 0, 0).unwrap())
+        );
+
+        let perpetual = &derivatives[1];
+        assert_eq!(perpetual.price, "69999.5".parse::<Amount>().unwrap());
+        assert_eq!(perpetual.funding_rate, 0.0099);
+        assert_eq!(perpetual.open_interest, None);
+        assert_eq!(perpetual.expired_at, None);
+    }
+
+    #[test]
+    fn percentage_deserialize() {
+        let derivatives: Vec<Derivative> = serde_json::from_str(
+            r#"[{"market":"Binance (Futures)","symbol":"BTCUSDT","index_id":"BTC","price":"69999.5","price_percentage_change_24h":1.3,"contract_type":"perpetual","index":70025.6,"basis":-0.037,"spread":0.01,"funding_rate":0.0099,"open_interest":null,"volume_24h":2664.1,"last_traded_at":1712467658,"expired_at":null},{"market":"Bybit (Futures)","symbol":"BTCUSD","index_id":"BTC","price":"69998","price_percentage_change_24h":"-0.52","contract_type":"perpetual","index":70025.6,"basis":"0.0012","spread":"0.02","funding_rate":" 0.01 ","open_interest":null,"volume_24h":"1200","last_traded_at":1712467658,"expired_at":null},{"market":"Deribit","symbol":"BTC-PERPETUAL","index_id":"BTC","price":"70001","price_percentage_change_24h":0,"contract_type":"perpetual","index":null,"basis":0,"spread":null,"funding_rate":0,"open_interest":null,"volume_24h":31.5,"last_traded_at":1712467658,"expired_at":null}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(derivatives[0].price_percentage_change24_h, 1.3);
+        assert_eq!(derivatives[0].spread, Some(0.01));
+        assert_eq!(derivatives[1].price_percentage_change24_h, -0.52);
+        assert_eq!(derivatives[1].basis, 0.0012);
+        assert_eq!(derivatives[1].spread, Some(0.02));
+        assert_eq!(derivatives[1].funding_rate, 0.01);
+        assert_eq!(derivatives[2].spread, None);
+
+        let item: CoinsMarketItem = serde_json::from_str(
+            r#"{"id":"bitcoin","symbol":"btc","name":"Bitcoin","image":"https://example.com/bitcoin.png","current_price":19147.5,"market_cap":367000000000,"market_cap_rank":1,"fully_diluted_valuation":null,"total_volume":28000000000,"high_24h":null,"low_24h":null,"price_change_24h":null,"price_change_percentage_24h":"-0.53","market_cap_change_24h":null,"market_cap_change_percentage_24h":-0.52,"circulating_supply":null,"total_supply":null,"max_supply":null,"ath":null,"ath_change_percentage":null,"ath_date":null,"atl":null,"atl_date":null,"roi":null,"last_updated":null,"price_change_percentage_7d_in_currency":"4.1"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.price_change_percentage24_h, Some(-0.53));
+        assert_eq!(item.market_cap_change_percentage24_h, Some(-0.52));
+        assert_eq!(item.ath_change_percentage, None);
+        assert_eq!(
+            item.atl_change_percentage, None,
+            "missing fields stay optional"
+        );
+        assert_eq!(item.price_change_percentage7_d_in_currency, Some(4.1));
+        assert_eq!(item.price_change_percentage1_h_in_currency, None);
+
+        assert!(serde_json::from_str::<Derivative>(
+            r#"{"market":"Deribit","symbol":"BTC-PERPETUAL","index_id":"BTC","price":"70001","price_percentage_change_24h":"n/a","contract_type":"perpetual","index":null,"basis":0,"spread":null,"funding_rate":0,"open_interest":null,"volume_24h":31.5,"last_traded_at":1712467658,"expired_at":null}"#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn derivatives_exchange() {
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"{"name":"BitMEX (Derivative)","open_interest_btc":7690.85,"trade_volume_24h_btc":"462.2","number_of_perpetual_pairs":22,"number_of_futures_pairs":3,"image":"https://assets.coingecko.com/markets/images/378/small/BitMEX_Logo.png?1706864007","year_established":2014,"country":"Seychelles","description":"","url":"https://www.bitmex.com/","tickers":[{"symbol":"XBTUSD","base":"BTC","target":"USD","trade_url":"https://www.bitmex.com/app/trade/XBTUSD","contract_type":"perpetual","last":69999.5,"h24_percentage_change":1.3,"index":70025.6,"index_basis_percentage":-0.037,"bid_ask_spread":0.000014,"funding_rate":0.0099,"open_interest_usd":284592312.0,"h24_volume":2664.1,"converted_volume":{"btc":"888.799","eth":"18029.8","usd":"61664181"},"converted_last":{"btc":"1.000","eth":"20.2","usd":"69999"},"last_traded":1712467658,"expired_at":null},{"symbol":"XBTM24","base":"BTC","target":"USD","trade_url":null,"contract_type":"futures","last":71250.0,"h24_percentage_change":0.8,"index":70025.6,"index_basis_percentage":null,"bid_ask_spread":null,"funding_rate":null,"open_interest_usd":null,"h24_volume":"12.5","converted_volume":{"btc":12.5,"eth":253.1,"usd":875000},"converted_last":{"btc":1.0,"eth":20.6,"usd":71250},"last_traded":1712467600,"expired_at":1719561600}]}"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+
+        let exchange: DerivativeExchangeDetail =
+            aw!(client.derivatives_exchange("bitmex", None)).unwrap();
+
+        assert!(transport.requests.lock().unwrap()[0]
+            .0
+            .ends_with("/derivatives/exchanges/bitmex?include_tickers=unexpired"));
+        assert_eq!(exchange.name, "BitMEX (Derivative)");
+        assert_eq!(exchange.trade_volume24_h_btc, "462.2".parse().ok());
+        assert_eq!(exchange.tickers.len(), 2);
+        assert_eq!(exchange.tickers[0].converted_volume.btc, 888.799);
+        assert_eq!(exchange.tickers[1].funding_rate, None);
+        assert_eq!(exchange.tickers[1].expired_at, Some(1719561600));
+    }
+
+    // ---------------------------------------------
+    //  /indexes
+    // ---------------------------------------------
+    #[cfg(feature = "stream")]
+    #[test]
+    fn indexes_stream() {
+        use futures::TryStreamExt;
+
+        // answers the n-th request with the n-th page
+        let pages = [
+            r#"[{"name":"A","id":"a","market":"Binance"},{"name":"B","id":"b","market":"Binance"}]"#,
+            r#"[{"name":"C","id":"c","market":"Binance"}]"#,
+        ];
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (mut stream, body) in listener.incoming().flatten().zip(pages) {
+                let mut request = [0; 4096];
+                let len = stream.read(&mut request).unwrap_or(0);
+                let _ = requests.send(String::from_utf8_lossy(&request[..len]).into_owned());
+                let _ = stream.write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                );
+            }
+        });
+
+        let client = CoinGeckoClient::builder().host(host).build();
+        let indexes: Vec<_> = aw!(client.indexes_stream(2).try_collect()).unwrap();
+
+        let ids: Vec<_> = indexes.iter().map(|index| index.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert!(received
+            .recv()
+            .unwrap()
+            .starts_with("GET //indexes?per_page=2&page=1 "));
+        assert!(received
+            .recv()
+            .unwrap()
+            .starts_with("GET //indexes?per_page=2&page=2 "));
+        assert!(received.try_recv().is_err());
+    }
+
+    // ---------------------------------------------
+    //  /nfts
+    // ---------------------------------------------
+    #[test]
+    fn nfts_list_params() {
+        assert_eq!(
+            crate::endpoints::nfts_list(None, 100, 1),
+            "/nfts/list?per_page=100&page=1"
+        );
+        assert_eq!(
+            crate::endpoints::nfts_list(Some(NftsOrder::FloorPriceNativeDesc), 10, 2),
+            "/nfts/list?per_page=10&page=2&order=floor_price_native_desc"
+        );
+    }
+
+    #[test]
+    fn nfts_markets() {
+        let transport = Arc::new(FakeTransport::new(
+            |_| r#"[{"id":"pudgy-penguins","contract_address":"0xbd3531da5cf5857e7cfaa92426877b022e612cf8","asset_platform_id":"ethereum","name":"Pudgy Penguins","symbol":"PPG","image":{"small":"https://example.com/pudgy.png"},"description":"Pudgy Penguins is a collection of 8,888 NFTs.","native_currency":"ethereum","native_currency_symbol":"ETH","floor_price":{"native_currency":11.5,"usd":40215.3},"market_cap":{"native_currency":102212.0,"usd":357423000},"volume_24h":{"native_currency":412.7,"usd":1443000},"floor_price_in_usd_24h_percentage_change":2.8,"floor_price_24h_percentage_change":{"usd":2.8,"native_currency":1.1},"market_cap_24h_percentage_change":{"usd":2.8,"native_currency":1.1},"volume_24h_percentage_change":{"usd":-12.4,"native_currency":-13.9},"number_of_unique_addresses":4752,"number_of_unique_addresses_24h_percentage_change":0.04,"volume_in_usd_24h_percentage_change":-12.4,"total_supply":8888,"one_day_sales":36,"one_day_sales_24h_percentage_change":-10.0,"one_day_average_sale_price":11.46,"one_day_average_sale_price_24h_percentage_change":-4.3}]"#,
+        ));
+        let client = CoinGeckoClient::builder()
+            .api_key(ApiKey::Pro("CG-pro".to_string()))
+            .transport(transport.clone())
+            .build();
+
+        let collections = aw!(client.nfts_markets(
+            Some(AssetPlatformId::Ethereum),
+            Some(NftsOrder::MarketCapUsdDesc),
+            10,
+            1
+        ))
+        .unwrap();
+
+        assert_eq!(
+            transport.requests.lock().unwrap()[0].0,
+            "https://pro-api.coingecko.com/api/v3//nfts/markets?per_page=10&page=1&asset_platform_id=ethereum&order=market_cap_usd_desc"
+        );
+        assert_eq!(collections[0].id, "pudgy-penguins");
+        assert_eq!(
+            collections[0].floor_price.native_currency,
+            "11.5".parse().ok()
+        );
+        assert_eq!(
+            collections[0]
+                .floor_price_24h_percentage_change
+                .as_ref()
+                .unwrap()
+                .usd,
+            Some(2.8)
+        );
+        assert_eq!(
+            crate::endpoints::nfts_markets(None, None, 100, 1),
+            "/nfts/markets?per_page=100&page=1"
+        );
+    }
+
+    #[test]
+    fn nft() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"id":"pudgy-penguins","contract_address":"0xbd3531da5cf5857e7cfaa92426877b022e612cf8","asset_platform_id":"ethereum","name":"Pudgy Penguins","symbol":"PPG","image":{"small":"https://example.com/small.png"},"description":"","native_currency":"ethereum","native_currency_symbol":"ETH","floor_price":{"native_currency":12.5,"usd":42000},"market_cap":{"native_currency":111000,"usd":370000000},"volume_24h":{"native_currency":240,"usd":810000},"floor_price_in_usd_24h_percentage_change":1.2,"floor_price_24h_percentage_change":{"usd":1.2,"native_currency":-0.4},"number_of_unique_addresses":4750,"total_supply":8888,"links":{"homepage":"https://www.pudgypenguins.com/","twitter":"https://twitter.com/pudgypenguins","discord":"https://discord.gg/pudgypenguins"},"explorers":[{"name":"Etherscan","link":"https://etherscan.io/token/0xbd3531da5cf5857e7cfaa92426877b022e612cf8"}]}"#,
+            ))
+            .build();
+        let res = aw!(client.nft("pudgy-penguins")).unwrap();
+
+        assert_eq!(res.name, "Pudgy Penguins");
+        assert_eq!(res.floor_price.native_currency, "12.5".parse().ok());
+        assert_eq!(res.floor_price.usd, "42000".parse().ok());
+        assert_eq!(res.native_currency_symbol.as_deref(), Some("ETH"));
+        assert_eq!(res.explorers[0].name, "Etherscan");
+    }
+
+    // ---------------------------------------------
+    //  /events
+    // ---------------------------------------------
+    #[test]
+    fn event_countries() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"data":[{"country":null,"code":""},{"country":"Afghanistan","code":"AF"},{"country":"Albania","code":"AL"}],"count":3}"#,
+            ))
+            .build();
+        let res = aw!(client.event_countries()).unwrap();
+
+        assert_eq!(res.count, 3);
+        assert_eq!(res.data[1].country.as_deref(), Some("Afghanistan"));
+        assert_eq!(res.data[1].code, "AF");
+    }
+
+    // ---------------------------------------------
+    //  /status_updates
+    // ---------------------------------------------
+    #[test]
+    fn status_updates_params() {
+        assert_eq!(
+            crate::endpoints::status_updates(None, None, 10, 1),
+            "/status_updates?per_page=10&page=1"
+        );
+        assert_eq!(
+            crate::endpoints::status_updates(Some("general"), Some("coin"), 10, 1),
+            "/status_updates?category=general&project_type=coin&per_page=10&page=1"
+        );
+    }
+
+    // ---------------------------------------------
+    //  /exchange_rates
+    // ---------------------------------------------
+    #[test]
+    fn exchange_rates_deserialize() {
+        let rates: ExchangeRates = serde_json::from_str(
+            r#"{"rates":{"btc":{"name":"Bitcoin","unit":"BTC","value":1.0,"type":"crypto"},"usd":{"name":"US Dollar","unit":"$","value":60000.5,"type":"fiat"},"xau":{"name":"Gold - Troy Ounce","unit":"XAU","value":25.2,"type":"commodity"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(rates.to("USD").unwrap().type_field, RateType::Fiat);
+        assert_eq!(rates.to("xau").unwrap().type_field, RateType::Commodity);
+        assert_eq!(rates.convert_btc(0.5, "usd"), Some(30000.25));
+        assert_eq!(rates.convert_btc(1.0, "doge"), None);
+    }
+
+    #[test]
+    fn reference_data_ttl() {
+        let respond = |url: &str| {
+            if url.ends_with("/simple/supported_vs_currencies") {
+                r#"["btc","usd"]"#
+            } else {
+                r#"{"rates":{"usd":{"name":"US Dollar","unit":"$","value":60000.5,"type":"fiat"}}}"#
+            }
+        };
+
+        let transport = Arc::new(FakeTransport::new(respond));
+        let client = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .reference_data_ttl(Duration::from_millis(200))
+            .build();
+        for _ in 0..3 {
+            let rates = aw!(client.clone().exchange_rates()).unwrap();
+            assert_eq!(rates.convert_btc(1.0, "usd"), Some(60000.5));
+            assert_eq!(
+                aw!(client.supported_vs_currencies()).unwrap(),
+                ["btc", "usd"]
+            );
+        }
+        assert_eq!(transport.requests.lock().unwrap().len(), 2);
+
+        std::thread::sleep(Duration::from_millis(250));
+        aw!(client.exchange_rates()).unwrap();
+        assert_eq!(
+            transport.requests.lock().unwrap().len(),
+            3,
+            "expired responses should be requested again"
+        );
+
+        let transport = Arc::new(FakeTransport::new(respond));
+        let uncached = CoinGeckoClient::builder()
+            .transport(transport.clone())
+            .build();
+        aw!(uncached.exchange_rates()).unwrap();
+        aw!(uncached.exchange_rates()).unwrap();
+        assert_eq!(transport.requests.lock().unwrap().len(), 2);
+    }
+
+    // ---------------------------------------------
+    //  /search
+    // ---------------------------------------------
+    #[test]
+    fn search_params() {
+        assert_eq!(
+            crate::endpoints::search("wrapped bitcoin & co/ä"),
+            "/search?query=wrapped+bitcoin+%26+co%2F%C3%A4"
+        );
+    }
+
+    #[test]
+    fn search() {
+        let client = CoinGeckoClient::builder()
+            .host(serve(
+                "200 OK",
+                &[],
+                r#"{"coins":[{"id":"bitcoin","name":"Bitcoin","api_symbol":"bitcoin","symbol":"BTC","market_cap_rank":1,"thumb":"https://example.com/thumb.png","large":"https://example.com/large.png"}],"exchanges":[{"id":"binance","name":"Binance","market_type":"spot","thumb":"https://example.com/thumb.png","large":"https://example.com/large.png"}],"icos":[],"categories":[{"id":"bitcoin-fork","name":"Bitcoin Fork"}],"nfts":[{"id":"bitcoin-frogs","name":"Bitcoin Frogs","symbol":"BITCOIN-FROGS","thumb":"https://example.com/thumb.png"}]}"#,
+            ))
+            .build();
+        let res = aw!(client.search("bitcoin")).unwrap();
+
+        assert_eq!(res.coins[0].id, "bitcoin");
+        assert_eq!(res.coins[0].market_cap_rank, Some(1));
+        assert_eq!(res.exchanges[0].market_type, "spot");
+        assert_eq!(res.categories[0].name, "Bitcoin Fork");
+        assert_eq!(res.nfts[0].symbol, "BITCOIN-FROGS");
+        assert!(res.icos.is_empty());
+    }
+
+    #[test]
+    fn trending_deserialize() {
+        let trending: Trending = serde_json::from_str(
+            r#"{"coins":[{"item":{"id":"bitcoin","coin_id":1,"name":"Bitcoin","symbol":"BTC","market_cap_rank":1,"thumb":"https://example.com/thumb.png","small":"https://example.com/small.png","large":"https://example.com/large.png","slug":"bitcoin","price_btc":1.0,"score":0}}],"nfts":[{"id":"pudgy-penguins","name":"Pudgy Penguins","symbol":"PPG","thumb":"https://example.com/ppg.png","nft_contract_id":38,"native_currency_symbol":"eth","floor_price_in_native_currency":12.8,"floor_price_24h_percentage_change":"3.4","data":{"floor_price":"12.80 ETH","h24_volume":"512.3 ETH","sparkline":"https://example.com/ppg.svg"}}],"categories":[{"id":251,"name":"Solana Meme Coins","market_cap_1h_change":1.44,"slug":"solana-meme-coins","coins_count":79,"data":{"market_cap":8237562936.01,"total_volume":1207846354.4,"sparkline":"https://example.com/sol.svg"}}]}"#,
+        )
+        .unwrap();
+
+        assert!(trending.exchanges.is_empty());
+        assert_eq!(trending.nfts[0].id, "pudgy-penguins");
+        assert_eq!(
+            trending.nfts[0].floor_price24_h_percentage_change,
+            Some(3.4)
+        );
+        assert_eq!(trending.categories[0].id, 251);
+        assert_eq!(trending.categories[0].market_cap1_h_change, Some(1.44));
+
+        // responses from before NFTs and categories trended
+        let trending: Trending = serde_json::from_str(r#"{"coins":[],"exchanges":[]}"#).unwrap();
+        assert!(trending.nfts.is_empty());
+        assert!(trending.categories.is_empty());
+    }
+
+    // ---------------------------------------------
+    //  /global
+    // ---------------------------------------------
+    #[test]
+    fn global_deserialize() {
+        let global: Global = serde_json::from_str(
+            r#"{"data":{"active_cryptocurrencies":13690,"upcoming_icos":0,"ongoing_icos":49,"ended_icos":3376,"markets":1046,"total_market_cap":{"btc":39003738.08,"eth":803832180.1,"usd":2721226850772.6},"total_volume":{"btc":993675.23,"eth":20478757.5,"usd":69327091133.5},"market_cap_percentage":{"btc":50.4465,"eth":14.9228,"usdt":3.8791},"market_cap_change_percentage_24h_usd":1.72,"updated_at":1712512855}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(global.data.market_cap_percentage["btc"], 50.4465);
+        assert_eq!(global.data.dominance("ETH"), Some(14.9228));
+        assert_eq!(global.data.dominance("doge"), None);
+        assert_eq!(global.data.total_market_cap["usd"], 2721226850772.6);
+        assert_eq!(global.data.total_volume["btc"], 993675.23);
+    }
+
+    #[test]
+    fn global_display() {
+        let global: Global = serde_json::from_str(
+            r#"{"data":{"active_cryptocurrencies":13690,"upcoming_icos":0,"ongoing_icos":49,"ended_icos":3376,"markets":1046,"total_market_cap":{"btc":39003738.08,"usd":2721226850772.6},"total_volume":{"usd":69327091133.5},"market_cap_percentage":{"eth":14.9228,"usdt":3.8791,"btc":50.4465},"market_cap_change_percentage_24h_usd":-1.725,"updated_at":1712512855}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            global.to_string(),
+            "Active cryptocurrencies: 13,690\n\
+             Markets: 1,046\n\
+             Market cap: $2,721,226,850,772.60 (-1.73% 24h)\n\
+             24h volume: $69,327,091,133.50\n\
+             Dominance: BTC 50.45%, ETH 14.92%, USDT 3.88%"
+        );
+    }
+
+    #[test]
+    fn global_defi_deserialize() {
+        let defi: GlobalDefi = serde_json::from_str(
+            r#"{"data":{"defi_market_cap":"105273842288.229620442228701667","eth_market_cap":"406184911478.5772415794509920285","defi_to_eth_ratio":"25.9175085051483243","trading_volume_24h":"5046503746.288261648853195485","defi_dominance":"3.8676345455870480","top_coin_name":"Lido Staked Ether","top_coin_defi_dominance":30.589442518868}}"#,
+        )
+        .unwrap();
+
+        #[cfg(feature = "decimal")]
+        {
+            use std::str::FromStr;
+            assert_eq!(
+                defi.data.defi_market_cap,
+                Amount::from_str("105273842288.229620442228701667").unwrap()
+            );
+        }
+        #[cfg(not(feature = "decimal"))]
+        assert_eq!(defi.data.defi_market_cap, 105273842288.22962);
+        assert_eq!(defi.data.defi_to_eth_ratio, 25.917508505148324);
+        assert_eq!(defi.data.defi_dominance, 3.867634545587048);
+        assert_eq!(defi.data.top_coin_name, "Lido Staked Ether");
+    }
+
+    // ---------------------------------------------
+    //  /companies
+    // ---------------------------------------------
+    #[test]
+    fn companies_params() {
+        assert_eq!(
+            crate::endpoints::companies(CompaniesCoinId::Ethereum),
+            "/companies/public_treasury/ethereum"
+        );
+        assert_eq!(
+            crate::endpoints::companies_by_id("solana"),
+            "/companies/public_treasury/solana"
+        );
+        assert_eq!(
+            crate::endpoints::companies_by_id("a/b"),
+            "/companies/public_treasury/a%2Fb"
+        );
     }
 }
@@ -1,4 +1,67 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+
+use crate::error::CoinGeckoError;
+
+/// Decimal places of the prices returned by `price`, `token_price` and the market chart endpoints
+///
+/// CoinGecko rounds prices to a default precision depending on their magnitude when none is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// Between 0 and 18 decimal places, larger values fail with `CoinGeckoError::InvalidPrecision`
+    Decimals(u8),
+    /// Every decimal place CoinGecko stores
+    Full,
+}
+
+/// Tokens returned by `token_price` for each contract address
+///
+/// `Top` keeps queries with many contract addresses small, as CoinGecko then only returns
+/// the token with the largest market cap for an address shared by several tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncludeTokens {
+    /// Every token matching an address
+    All,
+    /// Only the top token by market cap for each address
+    Top,
+}
+
+/// Window over which `top_gainers_losers` ranks price changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopGainersLosersDuration {
+    /// 1h
+    OneHour,
+    /// 24h
+    TwentyFourHours,
+    /// 7d
+    SevenDays,
+    /// 14d
+    FourteenDays,
+    /// 30d
+    ThirtyDays,
+    /// 60d
+    SixtyDays,
+    /// 1y
+    OneYear,
+}
+
+/// Coins by market cap rank considered by `top_gainers_losers`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopCoins {
+    /// Top 300
+    ThreeHundred,
+    /// Top 500
+    FiveHundred,
+    /// Top 1000
+    OneThousand,
+    /// All coins
+    All,
+}
+
 /// Market display order for `coins_markets`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarketsOrder {
     /// Marketcap descending
     MarketCapDesc,
@@ -19,6 +82,7 @@ pub enum MarketsOrder {
 }
 
 /// Price change percentage times for `coins_markets`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PriceChangePercentage {
     /// 1h
     OneHour,
@@ -37,6 +101,7 @@ pub enum PriceChangePercentage {
 }
 
 /// Tickers order for `coin_tickers` and `exchange_tickers`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TickersOrder {
     /// Trust Score ascending
     TrustScoreAsc,
@@ -47,6 +112,10 @@ pub enum TickersOrder {
 }
 
 /// Ohlc times for `coin_ohlc`
+///
+/// `Max` and `Custom` days other than the named ones require a Pro API key,
+/// without one they fail with `CoinGeckoError::InvalidOhlcDays` before sending the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OhlcDays {
     /// 1d
     OneDay,
@@ -62,9 +131,147 @@ pub enum OhlcDays {
     OneHundredEightyDays,
     /// 365
     ThreeHundredSixtyFiveDays,
+    /// All available data (Pro API only)
+    Max,
+    /// Any number of days (Pro API only, unless one of the named durations)
+    Custom(u32),
+}
+
+/// Candle size for `coin_ohlc_range`
+///
+/// - `Daily`: up to 180 days per request
+/// - `Hourly`: up to 31 days per request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OhlcInterval {
+    /// daily
+    Daily,
+    /// hourly
+    Hourly,
+}
+
+/// Days of history for the market chart and supply chart endpoints
+///
+/// Market chart methods also take a plain number of days, `Max` returns the full history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartDays {
+    /// Number of days up to now, at least 1
+    Days(i64),
+    /// All available data
+    Max,
+}
+
+impl From<u32> for ChartDays {
+    fn from(days: u32) -> Self {
+        ChartDays::Days(days.into())
+    }
+}
+
+/// Integer literals, counts below 1 fail with `CoinGeckoError::InvalidParam` before sending
+impl From<i32> for ChartDays {
+    fn from(days: i32) -> Self {
+        ChartDays::Days(days.into())
+    }
+}
+
+impl From<i64> for ChartDays {
+    fn from(days: i64) -> Self {
+        ChartDays::Days(days)
+    }
+}
+
+impl ChartDays {
+    /// Fails with `CoinGeckoError::InvalidParam` for fewer than 1 day
+    pub(crate) fn check(self) -> Result<(), CoinGeckoError> {
+        match self {
+            ChartDays::Days(days) => check_positive("days", days),
+            ChartDays::Max => Ok(()),
+        }
+    }
+}
+
+/// Value of the `days` query param
+impl fmt::Display for ChartDays {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartDays::Days(days) => write!(f, "{}", days),
+            ChartDays::Max => f.write_str("max"),
+        }
+    }
+}
+
+/// Data granularity for `coin_market_chart_with_interval` and the market chart range methods
+///
+/// - `Auto`: minutely within 1 day, hourly for 1-90 days, daily above 90 days (all plans)
+/// - `FiveMinutes`: 5 minute data for up to the last 10 days (Enterprise plan only)
+/// - `Hourly`: hourly data for up to the last 100 days (Enterprise plan only)
+/// - `Daily`: daily data at 00:00 UTC for any duration (all plans)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    /// Granularity picked from the duration
+    Auto,
+    /// 5m
+    FiveMinutes,
+    /// hourly
+    Hourly,
+    /// daily
+    Daily,
+}
+
+/// Coins included by `coins_markets`, CoinGecko applies only one filter per request
+///
+/// When several are sent, CoinGecko silently applies only one, `category` taking precedence over `ids`,
+/// `names` and `symbols`, so setting several on `CoinsMarketsRequest` fails with `CoinGeckoError::InvalidParam`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarketsFilter {
+    /// Coins with these ids, e.g. `bitcoin`
+    Ids(Vec<String>),
+    /// Coins with these names, e.g. `Bitcoin`
+    Names(Vec<String>),
+    /// Coins with these symbols, e.g. `btc`, which several coins may share
+    Symbols(Vec<String>),
+    /// Coins of this category, e.g. `layer-1`, see `categories_list`
+    Category(String),
+}
+
+impl MarketsFilter {
+    /// Filters given as the `ids` and `category` args of `coins_markets`, empty ids filtering nothing
+    pub(crate) fn from_args<Id: AsRef<str>>(ids: &[Id], category: Option<&str>) -> Vec<Self> {
+        let mut filters = Vec::new();
+        if !ids.is_empty() {
+            filters.push(MarketsFilter::Ids(
+                ids.iter().map(|id| id.as_ref().to_string()).collect(),
+            ));
+        }
+        if let Some(category) = category {
+            filters.push(MarketsFilter::Category(category.to_string()));
+        }
+
+        filters
+    }
+
+    /// Whether this filter has nothing to filter by, e.g. `Ids` without ids
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            MarketsFilter::Ids(values)
+            | MarketsFilter::Names(values)
+            | MarketsFilter::Symbols(values) => values.is_empty(),
+            MarketsFilter::Category(_) => false,
+        }
+    }
+
+    /// Name of the query param of this filter
+    pub(crate) fn param(&self) -> &'static str {
+        match self {
+            MarketsFilter::Ids(_) => "ids",
+            MarketsFilter::Names(_) => "names",
+            MarketsFilter::Symbols(_) => "symbols",
+            MarketsFilter::Category(_) => "category",
+        }
+    }
 }
 
 /// Tickers to include for `derivatives` and `derivatives_exchange`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DerivativesIncludeTickers {
     /// All tickers
     All,
@@ -73,6 +280,7 @@ pub enum DerivativesIncludeTickers {
 }
 
 /// Order of exchanges for `derivative_exchanges`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DerivativeExchangeOrder {
     /// Name ascending
     NameAsc,
@@ -88,6 +296,27 @@ pub enum DerivativeExchangeOrder {
     TradeVolume24hBtcDesc,
 }
 
+/// Order of NFT collections for `nfts_list` and `nfts_markets`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NftsOrder {
+    /// 24h volume in native currency ascending
+    H24VolumeNativeAsc,
+    /// 24h volume in native currency descending
+    H24VolumeNativeDesc,
+    /// Floor price in native currency ascending
+    FloorPriceNativeAsc,
+    /// Floor price in native currency descending
+    FloorPriceNativeDesc,
+    /// Market cap in native currency ascending
+    MarketCapNativeAsc,
+    /// Market cap in native currency descending
+    MarketCapNativeDesc,
+    /// Market cap in usd ascending
+    MarketCapUsdAsc,
+    /// Market cap in usd descending
+    MarketCapUsdDesc,
+}
+
 /// IDs for coins held in treasury for `companies`
 pub enum CompaniesCoinId {
     /// Bitcoin
@@ -95,3 +324,599 @@ pub enum CompaniesCoinId {
     /// Ethereum
     Ethereum,
 }
+
+/// Quote currency for prices and market data, see `supported_vs_currencies` for the full list
+///
+/// String literals convert into the matching variant, anything unknown becomes `Custom`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VsCurrency {
+    /// US Dollar
+    Usd,
+    /// Euro
+    Eur,
+    /// British Pound
+    Gbp,
+    /// Japanese Yen
+    Jpy,
+    /// Chinese Yuan
+    Cny,
+    /// South Korean Won
+    Krw,
+    /// Indian Rupee
+    Inr,
+    /// Australian Dollar
+    Aud,
+    /// Canadian Dollar
+    Cad,
+    /// Swiss Franc
+    Chf,
+    /// Bitcoin
+    Btc,
+    /// Ether
+    Eth,
+    /// Any other currency supported by CoinGecko
+    Custom(String),
+}
+
+impl AsRef<str> for VsCurrency {
+    fn as_ref(&self) -> &str {
+        match self {
+            VsCurrency::Usd => "usd",
+            VsCurrency::Eur => "eur",
+            VsCurrency::Gbp => "gbp",
+            VsCurrency::Jpy => "jpy",
+            VsCurrency::Cny => "cny",
+            VsCurrency::Krw => "krw",
+            VsCurrency::Inr => "inr",
+            VsCurrency::Aud => "aud",
+            VsCurrency::Cad => "cad",
+            VsCurrency::Chf => "chf",
+            VsCurrency::Btc => "btc",
+            VsCurrency::Eth => "eth",
+            VsCurrency::Custom(currency) => currency,
+        }
+    }
+}
+
+impl fmt::Display for VsCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// Codes are case-insensitive and lowercased like CoinGecko expects them, e.g. `"USD"` converts into `Usd`
+impl From<&str> for VsCurrency {
+    fn from(currency: &str) -> Self {
+        let currency = currency.to_ascii_lowercase();
+        match currency.as_str() {
+            "usd" => VsCurrency::Usd,
+            "eur" => VsCurrency::Eur,
+            "gbp" => VsCurrency::Gbp,
+            "jpy" => VsCurrency::Jpy,
+            "cny" => VsCurrency::Cny,
+            "krw" => VsCurrency::Krw,
+            "inr" => VsCurrency::Inr,
+            "aud" => VsCurrency::Aud,
+            "cad" => VsCurrency::Cad,
+            "chf" => VsCurrency::Chf,
+            "btc" => VsCurrency::Btc,
+            "eth" => VsCurrency::Eth,
+            _ => VsCurrency::Custom(currency),
+        }
+    }
+}
+
+impl From<String> for VsCurrency {
+    fn from(currency: String) -> Self {
+        VsCurrency::from(currency.as_str())
+    }
+}
+
+/// Filter for `asset_platforms_filtered`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetPlatformFilter {
+    /// Only platforms supporting NFTs
+    Nft,
+}
+
+/// Status of the exchanges listed by `exchanges_list_filtered`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeStatus {
+    /// Exchanges still tracked by CoinGecko, the default
+    Active,
+    /// Exchanges no longer tracked, e.g. closed ones
+    Inactive,
+}
+
+/// Id of an asset platform (chain) as used by CoinGecko, see `asset_platforms` for the full list
+///
+/// String literals convert into the matching variant, anything unknown becomes `Custom`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AssetPlatformId {
+    /// Ethereum
+    Ethereum,
+    /// BNB Smart Chain
+    BinanceSmartChain,
+    /// Polygon PoS
+    Polygon,
+    /// Arbitrum One
+    Arbitrum,
+    /// Optimism
+    Optimism,
+    /// Base
+    Base,
+    /// Avalanche C-Chain
+    Avalanche,
+    /// Fantom
+    Fantom,
+    /// Solana
+    Solana,
+    /// Tron
+    Tron,
+    /// Any other platform supported by CoinGecko
+    Custom(String),
+}
+
+impl AsRef<str> for AssetPlatformId {
+    fn as_ref(&self) -> &str {
+        match self {
+            AssetPlatformId::Ethereum => "ethereum",
+            AssetPlatformId::BinanceSmartChain => "binance-smart-chain",
+            AssetPlatformId::Polygon => "polygon-pos",
+            AssetPlatformId::Arbitrum => "arbitrum-one",
+            AssetPlatformId::Optimism => "optimistic-ethereum",
+            AssetPlatformId::Base => "base",
+            AssetPlatformId::Avalanche => "avalanche",
+            AssetPlatformId::Fantom => "fantom",
+            AssetPlatformId::Solana => "solana",
+            AssetPlatformId::Tron => "tron",
+            AssetPlatformId::Custom(id) => id,
+        }
+    }
+}
+
+impl fmt::Display for AssetPlatformId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl From<&str> for AssetPlatformId {
+    fn from(id: &str) -> Self {
+        match id {
+            "ethereum" => AssetPlatformId::Ethereum,
+            "binance-smart-chain" => AssetPlatformId::BinanceSmartChain,
+            "polygon-pos" => AssetPlatformId::Polygon,
+            "arbitrum-one" => AssetPlatformId::Arbitrum,
+            "optimistic-ethereum" => AssetPlatformId::Optimism,
+            "base" => AssetPlatformId::Base,
+            "avalanche" => AssetPlatformId::Avalanche,
+            "fantom" => AssetPlatformId::Fantom,
+            "solana" => AssetPlatformId::Solana,
+            "tron" => AssetPlatformId::Tron,
+            id => AssetPlatformId::Custom(id.to_string()),
+        }
+    }
+}
+
+impl From<String> for AssetPlatformId {
+    fn from(id: String) -> Self {
+        AssetPlatformId::from(id.as_str())
+    }
+}
+
+/// Id of a coin as used by CoinGecko, such as `bitcoin` rather than its symbol `btc`
+///
+/// Use `resolve_coin_id` to look up the ids of a symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoinId(String);
+
+impl CoinId {
+    /// Wraps a coin id as is, without checking that CoinGecko knows it
+    pub fn new(id: impl Into<String>) -> Self {
+        CoinId(id.into())
+    }
+
+    /// The id as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for CoinId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CoinId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for CoinId {
+    fn from(id: &str) -> Self {
+        CoinId(id.to_string())
+    }
+}
+
+impl From<String> for CoinId {
+    fn from(id: String) -> Self {
+        CoinId(id)
+    }
+}
+
+impl From<&CoinId> for CoinId {
+    fn from(id: &CoinId) -> Self {
+        id.clone()
+    }
+}
+
+impl From<CoinId> for String {
+    fn from(id: CoinId) -> Self {
+        id.0
+    }
+}
+
+impl OhlcDays {
+    /// Days accepted by the public API
+    const FREE_TIER_DAYS: [u32; 7] = [1, 7, 14, 30, 90, 180, 365];
+
+    /// Checks that the public API accepts these days, which is only needed without a Pro API key
+    pub(crate) fn check_free_tier(self) -> Result<(), CoinGeckoError> {
+        match self {
+            OhlcDays::Max => Err(CoinGeckoError::InvalidOhlcDays(self)),
+            OhlcDays::Custom(days) if !OhlcDays::FREE_TIER_DAYS.contains(&days) => {
+                Err(CoinGeckoError::InvalidOhlcDays(self))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl OhlcInterval {
+    /// Longest range CoinGecko answers in a single request
+    fn max_span(self) -> Duration {
+        match self {
+            OhlcInterval::Daily => Duration::days(180),
+            OhlcInterval::Hourly => Duration::days(31),
+        }
+    }
+
+    /// Checks that `from` and `to` are no further apart than CoinGecko allows for this interval
+    pub(crate) fn check_span(
+        self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<(), CoinGeckoError> {
+        if to - from > self.max_span() {
+            return Err(CoinGeckoError::InvalidParam {
+                name: "to",
+                reason: format!(
+                    "must be at most {} days after `from` for {} candles",
+                    self.max_span().num_days(),
+                    self
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Value of the `interval` query param
+impl fmt::Display for OhlcInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OhlcInterval::Daily => f.write_str("daily"),
+            OhlcInterval::Hourly => f.write_str("hourly"),
+        }
+    }
+}
+
+/// Value of the `days` query param
+impl fmt::Display for OhlcDays {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OhlcDays::OneDay => f.write_str("1"),
+            OhlcDays::SevenDays => f.write_str("7"),
+            OhlcDays::FourteenDays => f.write_str("14"),
+            OhlcDays::ThirtyDays => f.write_str("30"),
+            OhlcDays::NinetyDays => f.write_str("90"),
+            OhlcDays::OneHundredEightyDays => f.write_str("180"),
+            OhlcDays::ThreeHundredSixtyFiveDays => f.write_str("365"),
+            OhlcDays::Max => f.write_str("max"),
+            OhlcDays::Custom(days) => write!(f, "{}", days),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown value into a param enum, e.g. `"volume_dsc".parse::<MarketsOrder>()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseParamError {
+    param: &'static str,
+    value: String,
+    expected: Vec<&'static str>,
+}
+
+impl fmt::Display for ParseParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown {} `{}`, expected one of {}",
+            self.param,
+            self.value,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseParamError {}
+
+/// Finds the variant whose query value is `value`
+fn parse_param<T: Copy>(
+    param: &'static str,
+    variants: &[T],
+    as_str: fn(T) -> &'static str,
+    value: &str,
+) -> Result<T, ParseParamError> {
+    variants
+        .iter()
+        .copied()
+        .find(|variant| as_str(*variant) == value)
+        .ok_or_else(|| ParseParamError {
+            param,
+            value: value.to_string(),
+            expected: variants.iter().copied().map(as_str).collect(),
+        })
+}
+
+impl MarketsOrder {
+    /// Every markets order, e.g. to list the values accepted by a CLI flag
+    pub const VARIANTS: [MarketsOrder; 8] = [
+        MarketsOrder::MarketCapDesc,
+        MarketsOrder::MarketCapAsc,
+        MarketsOrder::GeckoDesc,
+        MarketsOrder::GeckoAsc,
+        MarketsOrder::VolumeDesc,
+        MarketsOrder::VolumeAsc,
+        MarketsOrder::IdDesc,
+        MarketsOrder::IdAsc,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            MarketsOrder::MarketCapDesc => "market_cap_desc",
+            MarketsOrder::MarketCapAsc => "market_cap_asc",
+            MarketsOrder::GeckoDesc => "gecko_desc",
+            MarketsOrder::GeckoAsc => "gecko_asc",
+            MarketsOrder::VolumeDesc => "volume_desc",
+            MarketsOrder::VolumeAsc => "volume_asc",
+            MarketsOrder::IdDesc => "id_desc",
+            MarketsOrder::IdAsc => "id_asc",
+        }
+    }
+}
+
+/// Value of the query param, e.g. `market_cap_desc`
+impl fmt::Display for MarketsOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MarketsOrder {
+    type Err = ParseParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_param(
+            "markets order",
+            &MarketsOrder::VARIANTS,
+            MarketsOrder::as_str,
+            s,
+        )
+    }
+}
+
+impl TickersOrder {
+    /// Every tickers order
+    pub const VARIANTS: [TickersOrder; 3] = [
+        TickersOrder::TrustScoreAsc,
+        TickersOrder::TrustScoreDesc,
+        TickersOrder::VolumeDesc,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            TickersOrder::TrustScoreAsc => "trust_score_asc",
+            TickersOrder::TrustScoreDesc => "trust_score_desc",
+            TickersOrder::VolumeDesc => "volume_desc",
+        }
+    }
+}
+
+/// Value of the query param, e.g. `trust_score_asc`
+impl fmt::Display for TickersOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TickersOrder {
+    type Err = ParseParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_param(
+            "tickers order",
+            &TickersOrder::VARIANTS,
+            TickersOrder::as_str,
+            s,
+        )
+    }
+}
+
+impl DerivativesIncludeTickers {
+    /// Both options
+    pub const VARIANTS: [DerivativesIncludeTickers; 2] = [
+        DerivativesIncludeTickers::All,
+        DerivativesIncludeTickers::Unexpired,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DerivativesIncludeTickers::All => "all",
+            DerivativesIncludeTickers::Unexpired => "unexpired",
+        }
+    }
+}
+
+/// Value of the query param, e.g. `all`
+impl fmt::Display for DerivativesIncludeTickers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DerivativesIncludeTickers {
+    type Err = ParseParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_param(
+            "derivatives include tickers",
+            &DerivativesIncludeTickers::VARIANTS,
+            DerivativesIncludeTickers::as_str,
+            s,
+        )
+    }
+}
+
+impl DerivativeExchangeOrder {
+    /// Every derivative exchange order
+    pub const VARIANTS: [DerivativeExchangeOrder; 6] = [
+        DerivativeExchangeOrder::NameAsc,
+        DerivativeExchangeOrder::NameDesc,
+        DerivativeExchangeOrder::OpenInterestBtcAsc,
+        DerivativeExchangeOrder::OpenInterestBtcDesc,
+        DerivativeExchangeOrder::TradeVolume24hBtcAsc,
+        DerivativeExchangeOrder::TradeVolume24hBtcDesc,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DerivativeExchangeOrder::NameAsc => "name_asc",
+            DerivativeExchangeOrder::NameDesc => "name_desc",
+            DerivativeExchangeOrder::OpenInterestBtcAsc => "open_interest_btc_asc",
+            DerivativeExchangeOrder::OpenInterestBtcDesc => "open_interest_btc_desc",
+            DerivativeExchangeOrder::TradeVolume24hBtcAsc => "trade_volume_24h_btc_asc",
+            DerivativeExchangeOrder::TradeVolume24hBtcDesc => "trade_volume_24h_btc_desc",
+        }
+    }
+}
+
+/// Value of the query param, e.g. `name_asc`
+impl fmt::Display for DerivativeExchangeOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DerivativeExchangeOrder {
+    type Err = ParseParamError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_param(
+            "derivative exchange order",
+            &DerivativeExchangeOrder::VARIANTS,
+            DerivativeExchangeOrder::as_str,
+            s,
+        )
+    }
+}
+
+impl Precision {
+    /// Most decimal places CoinGecko accepts
+    const MAX_DECIMALS: u8 = 18;
+
+    /// Value of the `precision` query param, or an error if out of range
+    pub(crate) fn param(self) -> Result<String, CoinGeckoError> {
+        match self {
+            Precision::Decimals(decimals) if decimals > Precision::MAX_DECIMALS => {
+                Err(CoinGeckoError::InvalidPrecision(decimals))
+            }
+            Precision::Decimals(decimals) => Ok(decimals.to_string()),
+            Precision::Full => Ok("full".to_string()),
+        }
+    }
+}
+
+/// Fails with `CoinGeckoError::InvalidParam` for a count or page number below 1, which CoinGecko answers confusingly
+pub(crate) fn check_positive(name: &'static str, value: i64) -> Result<(), CoinGeckoError> {
+    if value < 1 {
+        return Err(CoinGeckoError::InvalidParam {
+            name,
+            reason: format!("must be at least 1, got {}", value),
+        });
+    }
+
+    Ok(())
+}
+
+/// The only filter set for `coins_markets`, failing with `CoinGeckoError::InvalidParam` when several are set
+pub(crate) fn single_filter(
+    filters: &[MarketsFilter],
+) -> Result<Option<&MarketsFilter>, CoinGeckoError> {
+    match filters {
+        [] => Ok(None),
+        [filter] => Ok(Some(filter)),
+        [first, second, ..] => Err(CoinGeckoError::InvalidParam {
+            name: second.param(),
+            reason: format!(
+                "cannot be combined with `{}`, CoinGecko applies only one filter",
+                first.param()
+            ),
+        }),
+    }
+}
+
+/// How far `to` may be ahead of the local clock in `check_range`, so a `to` of "now" taken from a slightly
+/// faster clock than ours is not rejected
+const RANGE_CLOCK_SKEW_SECS: i64 = 60;
+
+/// Fails with `CoinGeckoError::InvalidParam` for a range CoinGecko would answer with an empty series
+///
+/// Rejects `from` after `to`, `to` more than a minute in the future and `from` before the Bitcoin genesis block,
+/// as no coin has data before it. Times are taken as UTC like in the requests.
+pub(crate) fn check_range(from: NaiveDateTime, to: NaiveDateTime) -> Result<(), CoinGeckoError> {
+    check_range_at(Utc::now().naive_utc(), from, to)
+}
+
+pub(crate) fn check_range_at(
+    now: NaiveDateTime,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Result<(), CoinGeckoError> {
+    let genesis = NaiveDate::from_ymd_opt(2009, 1, 3)
+        .and_then(|date| date.and_hms_opt(18, 15, 5))
+        .expect("valid genesis block time");
+
+    let invalid = |name, reason: &str| {
+        Err(CoinGeckoError::InvalidParam {
+            name,
+            reason: reason.to_string(),
+        })
+    };
+
+    if from > to {
+        invalid("from", "must not be after `to`")
+    } else if to > now + Duration::seconds(RANGE_CLOCK_SKEW_SECS) {
+        invalid("to", "must not be in the future")
+    } else if from < genesis {
+        invalid(
+            "from",
+            "must not be before the Bitcoin genesis block on 2009-01-03",
+        )
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use reqwest::header::HeaderMap;
+
+/// Rate limit state reported by CoinGecko on the last response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed in the current window (`x-ratelimit-limit`)
+    pub limit: Option<u64>,
+    /// Requests left in the current window (`x-ratelimit-remaining`)
+    pub remaining: Option<u64>,
+    /// When the current window resets, as sent by CoinGecko (`x-ratelimit-reset`)
+    pub reset: Option<String>,
+}
+
+impl RateLimit {
+    /// Reads the rate limit headers of a response, returns `None` if none of them are present
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+        };
+
+        let rate_limit = RateLimit {
+            limit: header("x-ratelimit-limit").and_then(|v| v.parse().ok()),
+            remaining: header("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+            reset: header("x-ratelimit-reset").map(String::from),
+        };
+
+        if rate_limit.limit.is_none()
+            && rate_limit.remaining.is_none()
+            && rate_limit.reset.is_none()
+        {
+            return None;
+        }
+
+        Some(rate_limit)
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::response::{exchange_rates::ExchangeRates, simple::SupportedVsCurrencies};
+
+/// Responses of reference endpoints changing slowly, reused for a while instead of requesting them for every call
+#[derive(Debug)]
+pub(crate) struct ReferenceCache {
+    pub(crate) exchange_rates: TtlCell<ExchangeRates>,
+    pub(crate) supported_vs_currencies: TtlCell<SupportedVsCurrencies>,
+}
+
+impl ReferenceCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        ReferenceCache {
+            exchange_rates: TtlCell::new(ttl),
+            supported_vs_currencies: TtlCell::new(ttl),
+        }
+    }
+}
+
+/// Last value of a response, fresh until it is older than the TTL
+///
+/// Uses chrono's clock, as std's panics on wasm32.
+#[derive(Debug)]
+pub(crate) struct TtlCell<T> {
+    ttl: chrono::Duration,
+    value: Mutex<Option<(DateTime<Utc>, T)>>,
+}
+
+impl<T: Clone> TtlCell<T> {
+    fn new(ttl: Duration) -> Self {
+        TtlCell {
+            ttl: chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX),
+            value: Mutex::new(None),
+        }
+    }
+
+    /// The value if it was stored less than the TTL ago
+    pub(crate) fn get(&self) -> Option<T> {
+        self.get_at(Utc::now())
+    }
+
+    pub(crate) fn get_at(&self, now: DateTime<Utc>) -> Option<T> {
+        let value = self.value.lock().unwrap();
+        let (stored_at, value) = value.as_ref()?;
+
+        (now.signed_duration_since(*stored_at) < self.ttl).then(|| value.clone())
+    }
+
+    pub(crate) fn set(&self, value: T) {
+        *self.value.lock().unwrap() = Some((Utc::now(), value));
+    }
+}
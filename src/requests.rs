@@ -0,0 +1,178 @@
+use crate::client::CoinGeckoClient;
+use crate::error::CoinGeckoError;
+use crate::params::{
+    check_positive, single_filter, MarketsFilter, MarketsOrder, PriceChangePercentage, VsCurrency,
+};
+use crate::response::coins::CoinsMarketItem;
+
+/// Request for `coins_markets` with named parameters and defaults
+///
+/// Defaults to ordering by market cap descending, 100 coins on page 1, no sparkline and no price change percentages.
+///
+/// # Examples
+///
+/// ```rust
+/// #[tokio::main]
+/// async fn main() {
+///     use coingecko::{
+///         params::{MarketsOrder, PriceChangePercentage},
+///         requests::CoinsMarketsRequest,
+///         CoinGeckoClient,
+///     };
+///     let client = CoinGeckoClient::default();
+///
+///     CoinsMarketsRequest::new("usd")
+///         .ids(&["bitcoin", "ethereum"])
+///         .order(MarketsOrder::VolumeDesc)
+///         .per_page(10)
+///         .price_change_percentage([PriceChangePercentage::OneHour])
+///         .send(&client)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CoinsMarketsRequest {
+    vs_currency: VsCurrency,
+    filters: Vec<MarketsFilter>,
+    order: MarketsOrder,
+    per_page: i64,
+    page: i64,
+    sparkline: bool,
+    price_change_percentage: Vec<PriceChangePercentage>,
+    locale: Option<String>,
+}
+
+impl CoinsMarketsRequest {
+    /// Creates a request for market data quoted in `vs_currency`
+    pub fn new(vs_currency: impl Into<VsCurrency>) -> Self {
+        CoinsMarketsRequest {
+            vs_currency: vs_currency.into(),
+            filters: Vec::new(),
+            order: MarketsOrder::MarketCapDesc,
+            per_page: 100,
+            page: 1,
+            sparkline: false,
+            price_change_percentage: Vec::new(),
+            locale: None,
+        }
+    }
+
+    /// Only includes the coins matching this filter, all coins are included by default
+    ///
+    /// Replaces a filter of the same kind. Setting filters of different kinds fails the request
+    /// with `CoinGeckoError::InvalidParam`, as CoinGecko would only apply one of them.
+    pub fn filter(mut self, filter: MarketsFilter) -> Self {
+        self.filters.retain(|set| set.param() != filter.param());
+        if !filter.is_empty() {
+            self.filters.push(filter);
+        }
+        self
+    }
+
+    /// Only includes the coins with these ids, see `filter`
+    pub fn ids<Id: AsRef<str>>(self, ids: &[Id]) -> Self {
+        self.filter(MarketsFilter::Ids(to_strings(ids)))
+    }
+
+    /// Only includes the coins with these names, e.g. `Bitcoin`, see `filter`
+    pub fn names<Name: AsRef<str>>(self, names: &[Name]) -> Self {
+        self.filter(MarketsFilter::Names(to_strings(names)))
+    }
+
+    /// Only includes the coins with these symbols, e.g. `btc`, see `filter`
+    pub fn symbols<Symbol: AsRef<str>>(self, symbols: &[Symbol]) -> Self {
+        self.filter(MarketsFilter::Symbols(to_strings(symbols)))
+    }
+
+    /// Only includes coins of this category, see `filter`
+    pub fn category(self, category: impl Into<String>) -> Self {
+        self.filter(MarketsFilter::Category(category.into()))
+    }
+
+    /// Sets the sort order (defaults to `MarketsOrder::MarketCapDesc`)
+    pub fn order(mut self, order: MarketsOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets the number of coins per page, up to 250 (defaults to 100)
+    pub fn per_page(mut self, per_page: i64) -> Self {
+        self.per_page = per_page;
+        self
+    }
+
+    /// Sets the page to fetch, starting at 1 (defaults to 1)
+    pub fn page(mut self, page: i64) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Includes the 7 day sparkline of each coin (defaults to `false`)
+    pub fn sparkline(mut self, sparkline: bool) -> Self {
+        self.sparkline = sparkline;
+        self
+    }
+
+    /// Includes the price change percentage of each coin over these times
+    pub fn price_change_percentage(
+        mut self,
+        price_change_percentage: impl IntoIterator<Item = PriceChangePercentage>,
+    ) -> Self {
+        self.price_change_percentage = price_change_percentage.into_iter().collect();
+        self
+    }
+
+    /// Sets the language of the response, e.g. `de`, overriding the default locale of the client
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Sends the request with the given client
+    pub async fn send(
+        &self,
+        client: &CoinGeckoClient,
+    ) -> Result<Vec<CoinsMarketItem>, CoinGeckoError> {
+        self.check_pages()?;
+        let endpoint = self.endpoint(client.locale.as_deref())?;
+        client.check_currencies(&[&self.vs_currency]).await?;
+        client.get(&endpoint).await
+    }
+
+    /// Sends the request with the given blocking client
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(
+        &self,
+        client: &crate::blocking::CoinGeckoClient,
+    ) -> Result<Vec<CoinsMarketItem>, CoinGeckoError> {
+        self.check_pages()?;
+        let endpoint = self.endpoint(client.locale.as_deref())?;
+        client.check_currencies(&[&self.vs_currency])?;
+        client.get(&endpoint)
+    }
+
+    fn check_pages(&self) -> Result<(), CoinGeckoError> {
+        check_positive("per_page", self.per_page)?;
+        check_positive("page", self.page)
+    }
+
+    fn endpoint(&self, default_locale: Option<&str>) -> Result<String, CoinGeckoError> {
+        Ok(crate::endpoints::coins_markets(
+            self.vs_currency.as_ref(),
+            single_filter(&self.filters)?,
+            self.order,
+            self.per_page,
+            self.page,
+            self.sparkline,
+            &self.price_change_percentage,
+            self.locale.as_deref().or(default_locale),
+        ))
+    }
+}
+
+fn to_strings<V: AsRef<str>>(values: &[V]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| value.as_ref().to_string())
+        .collect()
+}
@@ -1,10 +1,15 @@
 #![allow(missing_docs)]
 use serde::{Deserialize, Serialize};
 
+use super::common::Image;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct AssetPlatform {
     pub id: String,
     pub chain_identifier: Option<i64>,
     pub name: String,
     pub shortname: String,
+    pub native_coin_id: Option<String>,
+    pub image: Option<Image>,
 }
@@ -1,10 +1,15 @@
 #![allow(missing_docs)]
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{de, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt;
+
+use crate::de::StringOrNumber;
+use crate::format;
 
 use super::common::{
-    CommunityData, CurrentPrice, DeveloperData, Image, Links, Localization, MarketCap,
+    Amount, CommunityData, CurrentPrice, DeveloperData, Image, Links, Localization, MarketCap,
     PublicInterestStats, Ticker, TotalVolume,
 };
 
@@ -12,6 +17,7 @@ use super::common::{
 //  /coins/list
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CoinsListItem {
     pub id: String,
     pub symbol: String,
@@ -19,15 +25,101 @@ pub struct CoinsListItem {
     pub platforms: Option<HashMap<String, Option<String>>>,
 }
 
+// ---------------------------------------------
+//  /coins/list/new
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NewCoin {
+    pub id: String,
+    pub symbol: String,
+    pub name: String,
+    /// When the coin was listed on CoinGecko
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub activated_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------
+//  /coins/top_gainers_losers
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TopGainersLosers {
+    pub top_gainers: Vec<TopMover>,
+    pub top_losers: Vec<TopMover>,
+}
+
+/// Coin ranked by `top_gainers_losers`
+///
+/// CoinGecko names the quote fields after the currency and duration, e.g. `usd`, `usd_24h_vol` and `usd_7d_change`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "RawTopMover")]
+pub struct TopMover {
+    pub id: String,
+    pub symbol: String,
+    pub name: String,
+    pub image: String,
+    pub market_cap_rank: Option<i64>,
+    /// Price in the requested currency
+    pub price: Option<Amount>,
+    /// 24h volume in the requested currency
+    pub volume24_h: Option<Amount>,
+    /// Price change over the requested duration, in percent
+    pub price_change_percentage: Option<Amount>,
+}
+
+#[derive(Deserialize)]
+struct RawTopMover {
+    id: String,
+    symbol: String,
+    name: String,
+    image: String,
+    market_cap_rank: Option<i64>,
+    #[serde(flatten)]
+    quotes: HashMap<String, Option<Amount>>,
+}
+
+impl From<RawTopMover> for TopMover {
+    fn from(raw: RawTopMover) -> Self {
+        let mut mover = TopMover {
+            id: raw.id,
+            symbol: raw.symbol,
+            name: raw.name,
+            image: raw.image,
+            market_cap_rank: raw.market_cap_rank,
+            price: None,
+            volume24_h: None,
+            price_change_percentage: None,
+        };
+
+        for (key, value) in raw.quotes {
+            if key.ends_with("_24h_vol") {
+                mover.volume24_h = value;
+            } else if key.ends_with("_change") {
+                mover.price_change_percentage = value;
+            } else {
+                mover.price = value;
+            }
+        }
+
+        mover
+    }
+}
+
 // ---------------------------------------------
 //  /coins/markets
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct SparklineIn7D {
     pub price: Vec<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "strict-schema", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CoinsMarketItem {
     pub id: String,
     pub symbol: String,
@@ -35,8 +127,8 @@ pub struct CoinsMarketItem {
     pub image: String,
     pub current_price: Option<f64>,
     pub market_cap: Option<f64>,
-    pub market_cap_rank: Value,
-    pub fully_diluted_valuation: Value,
+    pub market_cap_rank: Option<i64>,
+    pub fully_diluted_valuation: Option<f64>,
     pub total_volume: Option<f64>,
     #[serde(rename = "high_24h")]
     pub high24_h: Option<f64>,
@@ -45,56 +137,103 @@ pub struct CoinsMarketItem {
     #[serde(rename = "price_change_24h")]
     pub price_change24_h: Option<f64>,
     #[serde(rename = "price_change_percentage_24h")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage24_h: Option<f64>,
     #[serde(rename = "market_cap_change_24h")]
     pub market_cap_change24_h: Option<f64>,
     #[serde(rename = "market_cap_change_percentage_24h")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub market_cap_change_percentage24_h: Option<f64>,
     pub circulating_supply: Option<f64>,
     pub total_supply: Option<f64>,
     pub max_supply: Option<f64>,
     pub ath: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub ath_change_percentage: Option<f64>,
     pub ath_date: Option<String>,
     pub atl: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub atl_change_percentage: Option<f64>,
     pub atl_date: Option<String>,
     pub roi: Value,
     pub last_updated: Option<String>,
+    /// Hourly prices of the last 7 days, only sent when requested with `sparkline`
     #[serde(rename = "sparkline_in_7d")]
     pub sparkline_in7_d: Option<SparklineIn7D>,
     #[serde(rename = "price_change_percentage_14d_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage14_d_in_currency: Option<f64>,
     #[serde(rename = "price_change_percentage_1h_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage1_h_in_currency: Option<f64>,
     #[serde(rename = "price_change_percentage_1y_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage1_y_in_currency: Option<f64>,
     #[serde(rename = "price_change_percentage_200d_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage200_d_in_currency: Option<f64>,
     #[serde(rename = "price_change_percentage_24h_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage24_h_in_currency: Option<f64>,
     #[serde(rename = "price_change_percentage_30d_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage30_d_in_currency: Option<f64>,
     #[serde(rename = "price_change_percentage_7d_in_currency")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub price_change_percentage7_d_in_currency: Option<f64>,
+    /// Fields CoinGecko sent that this struct does not model yet
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten, deserialize_with = "crate::de::extra_fields")]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Rank, name, price, market cap and 24h change on one line, e.g. `#1 Bitcoin (BTC) 67,152.00, market cap 1,321,749,463,618.00, 24h +0.77%`
+///
+/// Amounts are in the `vs_currency` of the request, which the response does not name, so they are written without a currency.
+impl fmt::Display for CoinsMarketItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(rank) = self.market_cap_rank {
+            write!(f, "#{} ", rank)?;
+        }
+        write!(f, "{} ({})", self.name, self.symbol.to_uppercase())?;
+        if let Some(price) = self.current_price {
+            write!(f, " {}", format::number(price))?;
+        }
+        if let Some(market_cap) = self.market_cap {
+            write!(f, ", market cap {}", format::number(market_cap))?;
+        }
+        if let Some(change) = self.price_change_percentage24_h {
+            write!(f, ", 24h {}", format::percent(change))?;
+        }
+
+        Ok(())
+    }
 }
 
 // ---------------------------------------------
 //  /coins/{id}
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "strict-schema", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct CoinsItem {
     pub id: String,
     pub symbol: String,
     pub name: String,
     pub asset_platform_id: Value,
     pub platforms: Option<HashMap<String, Option<String>>>,
+    /// Contract address and decimals of the token on each platform, keyed like `platforms`
+    #[serde(default)]
+    pub detail_platforms: HashMap<String, DetailPlatform>,
     pub block_time_in_minutes: f64,
     pub hashing_algorithm: Value,
     pub categories: Vec<String>,
     pub public_notice: Value,
     pub additional_notices: Vec<Value>,
-    pub localization: Option<Localization>,
+    /// Localized names keyed by language code, e.g. `de` or `ja`
+    pub localization: Option<HashMap<String, String>>,
     pub description: Description,
     pub links: Links,
     pub image: Image,
@@ -117,9 +256,56 @@ pub struct CoinsItem {
     pub status_updates: Vec<Value>,
     pub last_updated: String,
     pub tickers: Option<Vec<Ticker>>,
+    /// Fields CoinGecko sent that this struct does not model yet
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten, deserialize_with = "crate::de::extra_fields")]
+    pub extra: HashMap<String, Value>,
+}
+
+impl CoinsItem {
+    /// Name in the language with this code, e.g. `de`, falling back to the English `name`
+    ///
+    /// Only localized when requested with `localization`.
+    pub fn localized_name(&self, locale: &str) -> &str {
+        localized_name(&self.localization, locale, &self.name)
+    }
+
+    /// Description in the language with this code, e.g. `de`, falling back to English
+    ///
+    /// Only localized when requested with `localization`.
+    pub fn localized_description(&self, locale: &str) -> Option<&str> {
+        self.description
+            .get(locale)
+            .or_else(|| self.description.get("en"))
+    }
+}
+
+/// Looks up a localized name, falling back to `name` if it is missing or empty
+fn localized_name<'a>(
+    localization: &'a Option<HashMap<String, String>>,
+    locale: &str,
+    name: &'a str,
+) -> &'a str {
+    localization
+        .as_ref()
+        .and_then(|localization| localization.get(&locale.to_ascii_lowercase()))
+        .filter(|localized| !localized.is_empty())
+        .map_or(name, String::as_str)
+}
+
+/// Token of a coin on one platform
+///
+/// Native coins are listed under an empty platform id with an empty contract address and no decimals.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct DetailPlatform {
+    /// Decimal places of the token's smallest unit
+    pub decimal_place: Option<u32>,
+    pub contract_address: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Description {
     pub en: Option<String>,
     pub de: Option<String>,
@@ -145,27 +331,62 @@ pub struct Description {
     pub id: Option<String>,
 }
 
+impl Description {
+    /// Description in the language with this code, e.g. `de` or `zh-tw`, `None` if it is not translated
+    pub fn get(&self, locale: &str) -> Option<&str> {
+        let description = match locale.to_ascii_lowercase().as_str() {
+            "en" => &self.en,
+            "de" => &self.de,
+            "es" => &self.es,
+            "fr" => &self.fr,
+            "it" => &self.it,
+            "pl" => &self.pl,
+            "ro" => &self.ro,
+            "hu" => &self.hu,
+            "nl" => &self.nl,
+            "pt" => &self.pt,
+            "sv" => &self.sv,
+            "vi" => &self.vi,
+            "tr" => &self.tr,
+            "ru" => &self.ru,
+            "ja" => &self.ja,
+            "zh" => &self.zh,
+            "zh-tw" => &self.zh_tw,
+            "ko" => &self.ko,
+            "ar" => &self.ar,
+            "th" => &self.th,
+            "id" => &self.id,
+            _ => return None,
+        };
+
+        description
+            .as_deref()
+            .filter(|description| !description.is_empty())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct MarketData {
-    pub current_price: CurrentPrice,
+    pub current_price: HashMap<String, Option<f64>>,
     pub total_value_locked: Value,
     pub mcap_to_tvl_ratio: Value,
     pub fdv_to_tvl_ratio: Value,
     pub roi: Value,
-    pub ath: Ath,
-    pub ath_change_percentage: AthChangePercentage,
-    pub ath_date: AthDate,
-    pub atl: Atl,
-    pub atl_change_percentage: AtlChangePercentage,
-    pub atl_date: AtlDate,
-    pub market_cap: MarketCap,
+    pub ath: HashMap<String, Option<f64>>,
+    pub ath_change_percentage: HashMap<String, Option<f64>>,
+    pub ath_date: HashMap<String, Option<DateTime<Utc>>>,
+    pub atl: HashMap<String, Option<f64>>,
+    pub atl_change_percentage: HashMap<String, Option<f64>>,
+    pub atl_date: HashMap<String, Option<DateTime<Utc>>>,
+    pub market_cap: HashMap<String, Option<f64>>,
     pub market_cap_rank: Value,
-    pub fully_diluted_valuation: FullyDilutedValuation,
-    pub total_volume: TotalVolume,
+    pub fully_diluted_valuation: HashMap<String, Option<f64>>,
+    pub total_volume: HashMap<String, Option<f64>>,
     #[serde(rename = "high_24h")]
-    pub high24_h: High24H,
+    pub high24_h: HashMap<String, Option<f64>>,
     #[serde(rename = "low_24h")]
-    pub low24_h: Low24H,
+    pub low24_h: HashMap<String, Option<f64>>,
     #[serde(rename = "price_change_24h")]
     pub price_change24_h: Option<f64>,
     #[serde(rename = "price_change_percentage_24h")]
@@ -217,8 +438,71 @@ pub struct MarketData {
     pub last_updated: String,
 }
 
+impl MarketData {
+    /// Current price in `currency`, e.g. `usd`
+    pub fn current_price(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.current_price, currency)
+    }
+
+    /// Market cap in `currency`
+    pub fn market_cap(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.market_cap, currency)
+    }
+
+    /// 24h trading volume in `currency`
+    pub fn total_volume(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.total_volume, currency)
+    }
+
+    /// Highest price of the last 24h in `currency`
+    pub fn high_24h(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.high24_h, currency)
+    }
+
+    /// Lowest price of the last 24h in `currency`
+    pub fn low_24h(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.low24_h, currency)
+    }
+
+    /// All-time high price in `currency`
+    pub fn ath(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.ath, currency)
+    }
+
+    /// Change from the all-time high to the current price in `currency`, in percent
+    pub fn ath_change_percentage(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.ath_change_percentage, currency)
+    }
+
+    /// When the all-time high in `currency` was reached
+    pub fn ath_date(&self, currency: &str) -> Option<DateTime<Utc>> {
+        in_currency(&self.ath_date, currency)
+    }
+
+    /// All-time low price in `currency`
+    pub fn atl(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.atl, currency)
+    }
+
+    /// Change from the all-time low to the current price in `currency`, in percent
+    pub fn atl_change_percentage(&self, currency: &str) -> Option<f64> {
+        in_currency(&self.atl_change_percentage, currency)
+    }
+
+    /// When the all-time low in `currency` was reached
+    pub fn atl_date(&self, currency: &str) -> Option<DateTime<Utc>> {
+        in_currency(&self.atl_date, currency)
+    }
+}
+
+/// Value of a map keyed by lowercase currency, `None` when missing or null
+fn in_currency<T: Copy>(values: &HashMap<String, Option<T>>, currency: &str) -> Option<T> {
+    values.get(&currency.to_lowercase()).copied().flatten()
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Ath {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChange24HInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -283,8 +567,9 @@ pub struct Ath {
     pub sats: Option<f64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AthChangePercentage {
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage1HInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -350,73 +635,8 @@ pub struct AthChangePercentage {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AthDate {
-    pub aed: Option<String>,
-    pub ars: Option<String>,
-    pub aud: Option<String>,
-    pub bch: Option<String>,
-    pub bdt: Option<String>,
-    pub bhd: Option<String>,
-    pub bmd: Option<String>,
-    pub bnb: Option<String>,
-    pub brl: Option<String>,
-    pub btc: Option<String>,
-    pub cad: Option<String>,
-    pub chf: Option<String>,
-    pub clp: Option<String>,
-    pub cny: Option<String>,
-    pub czk: Option<String>,
-    pub dkk: Option<String>,
-    pub dot: Option<String>,
-    pub eos: Option<String>,
-    pub eth: Option<String>,
-    pub eur: Option<String>,
-    pub gbp: Option<String>,
-    pub hkd: Option<String>,
-    pub huf: Option<String>,
-    pub idr: Option<String>,
-    pub ils: Option<String>,
-    pub inr: Option<String>,
-    pub jpy: Option<String>,
-    pub krw: Option<String>,
-    pub kwd: Option<String>,
-    pub lkr: Option<String>,
-    pub ltc: Option<String>,
-    pub mmk: Option<String>,
-    pub mxn: Option<String>,
-    pub myr: Option<String>,
-    pub ngn: Option<String>,
-    pub nok: Option<String>,
-    pub nzd: Option<String>,
-    pub php: Option<String>,
-    pub pkr: Option<String>,
-    pub pln: Option<String>,
-    pub rub: Option<String>,
-    pub sar: Option<String>,
-    pub sek: Option<String>,
-    pub sgd: Option<String>,
-    pub thb: Option<String>,
-    #[serde(rename = "try")]
-    pub try_field: Option<String>,
-    pub twd: Option<String>,
-    pub uah: Option<String>,
-    pub usd: Option<String>,
-    pub vef: Option<String>,
-    pub vnd: Option<String>,
-    pub xag: Option<String>,
-    pub xau: Option<String>,
-    pub xdr: Option<String>,
-    pub xlm: Option<String>,
-    pub xrp: Option<String>,
-    pub yfi: Option<String>,
-    pub zar: Option<String>,
-    pub bits: Option<String>,
-    pub link: Option<String>,
-    pub sats: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Atl {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage24HInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -482,7 +702,8 @@ pub struct Atl {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AtlChangePercentage {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage7DInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -548,73 +769,8 @@ pub struct AtlChangePercentage {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AtlDate {
-    pub aed: Option<String>,
-    pub ars: Option<String>,
-    pub aud: Option<String>,
-    pub bch: Option<String>,
-    pub bdt: Option<String>,
-    pub bhd: Option<String>,
-    pub bmd: Option<String>,
-    pub bnb: Option<String>,
-    pub brl: Option<String>,
-    pub btc: Option<String>,
-    pub cad: Option<String>,
-    pub chf: Option<String>,
-    pub clp: Option<String>,
-    pub cny: Option<String>,
-    pub czk: Option<String>,
-    pub dkk: Option<String>,
-    pub dot: Option<String>,
-    pub eos: Option<String>,
-    pub eth: Option<String>,
-    pub eur: Option<String>,
-    pub gbp: Option<String>,
-    pub hkd: Option<String>,
-    pub huf: Option<String>,
-    pub idr: Option<String>,
-    pub ils: Option<String>,
-    pub inr: Option<String>,
-    pub jpy: Option<String>,
-    pub krw: Option<String>,
-    pub kwd: Option<String>,
-    pub lkr: Option<String>,
-    pub ltc: Option<String>,
-    pub mmk: Option<String>,
-    pub mxn: Option<String>,
-    pub myr: Option<String>,
-    pub ngn: Option<String>,
-    pub nok: Option<String>,
-    pub nzd: Option<String>,
-    pub php: Option<String>,
-    pub pkr: Option<String>,
-    pub pln: Option<String>,
-    pub rub: Option<String>,
-    pub sar: Option<String>,
-    pub sek: Option<String>,
-    pub sgd: Option<String>,
-    pub thb: Option<String>,
-    #[serde(rename = "try")]
-    pub try_field: Option<String>,
-    pub twd: Option<String>,
-    pub uah: Option<String>,
-    pub usd: Option<String>,
-    pub vef: Option<String>,
-    pub vnd: Option<String>,
-    pub xag: Option<String>,
-    pub xau: Option<String>,
-    pub xdr: Option<String>,
-    pub xlm: Option<String>,
-    pub xrp: Option<String>,
-    pub yfi: Option<String>,
-    pub zar: Option<String>,
-    pub bits: Option<String>,
-    pub link: Option<String>,
-    pub sats: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct FullyDilutedValuation {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage14DInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -680,7 +836,8 @@ pub struct FullyDilutedValuation {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct High24H {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage30DInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -746,7 +903,8 @@ pub struct High24H {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Low24H {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage60DInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -812,7 +970,8 @@ pub struct Low24H {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChange24HInCurrency {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage200DInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -877,8 +1036,9 @@ pub struct PriceChange24HInCurrency {
     pub sats: Option<f64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
-pub struct PriceChangePercentage1HInCurrency {
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct PriceChangePercentage1YInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -895,7 +1055,6 @@ pub struct PriceChangePercentage1HInCurrency {
     pub cny: Option<f64>,
     pub czk: Option<f64>,
     pub dkk: Option<f64>,
-    pub dot: Option<f64>,
     pub eos: Option<f64>,
     pub eth: Option<f64>,
     pub eur: Option<f64>,
@@ -936,7 +1095,6 @@ pub struct PriceChangePercentage1HInCurrency {
     pub xdr: Option<f64>,
     pub xlm: Option<f64>,
     pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
     pub zar: Option<f64>,
     pub bits: Option<f64>,
     pub link: Option<f64>,
@@ -944,7 +1102,8 @@ pub struct PriceChangePercentage1HInCurrency {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage24HInCurrency {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct MarketCapChange24HInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -1010,7 +1169,8 @@ pub struct PriceChangePercentage24HInCurrency {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage7DInCurrency {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct MarketCapChangePercentage24HInCurrency {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
     pub aud: Option<f64>,
@@ -1076,513 +1236,176 @@ pub struct PriceChangePercentage7DInCurrency {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage14DInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub dot: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct Sparkline7D {
+    pub price: Vec<f64>,
 }
 
+// ---------------------------------------------
+//  /coins/{id}/history
+// ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage30DInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub dot: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct History {
+    pub id: String,
+    pub symbol: String,
+    pub name: String,
+    /// Localized names keyed by language code, e.g. `de` or `ja`
+    pub localization: Option<HashMap<String, String>>,
+    pub image: Image,
+    pub market_data: HistoryMarketData,
+    pub community_data: CommunityData,
+    pub developer_data: DeveloperData,
+    pub public_interest_stats: PublicInterestStats,
+}
+
+impl History {
+    /// Name in the language with this code, e.g. `de`, falling back to the English `name`
+    ///
+    /// Only localized when requested with `localization`.
+    pub fn localized_name(&self, locale: &str) -> &str {
+        localized_name(&self.localization, locale, &self.name)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage60DInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub dot: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct HistoryMarketData {
+    pub current_price: CurrentPrice,
+    pub market_cap: MarketCap,
+    pub total_volume: TotalVolume,
 }
 
+// ---------------------------------------------
+//  /coins/{id}/market_chart
+// ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage200DInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub dot: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct MarketChart {
+    pub prices: Vec<MarketChartPoint>,
+    pub market_caps: Vec<MarketChartPoint>,
+    pub total_volumes: Vec<MarketChartPoint>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct PriceChangePercentage1YInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+/// Data point deserialized from CoinGecko's `[timestamp, value]` array
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketChartPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: Amount,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct MarketCapChange24HInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub dot: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+impl MarketChartPoint {
+    /// The point as sent by CoinGecko, with the timestamp in milliseconds since the epoch
+    pub fn raw(&self) -> (i64, Amount) {
+        (self.timestamp.timestamp_millis(), self.value)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct MarketCapChangePercentage24HInCurrency {
-    pub aed: Option<f64>,
-    pub ars: Option<f64>,
-    pub aud: Option<f64>,
-    pub bch: Option<f64>,
-    pub bdt: Option<f64>,
-    pub bhd: Option<f64>,
-    pub bmd: Option<f64>,
-    pub bnb: Option<f64>,
-    pub brl: Option<f64>,
-    pub btc: Option<f64>,
-    pub cad: Option<f64>,
-    pub chf: Option<f64>,
-    pub clp: Option<f64>,
-    pub cny: Option<f64>,
-    pub czk: Option<f64>,
-    pub dkk: Option<f64>,
-    pub dot: Option<f64>,
-    pub eos: Option<f64>,
-    pub eth: Option<f64>,
-    pub eur: Option<f64>,
-    pub gbp: Option<f64>,
-    pub hkd: Option<f64>,
-    pub huf: Option<f64>,
-    pub idr: Option<f64>,
-    pub ils: Option<f64>,
-    pub inr: Option<f64>,
-    pub jpy: Option<f64>,
-    pub krw: Option<f64>,
-    pub kwd: Option<f64>,
-    pub lkr: Option<f64>,
-    pub ltc: Option<f64>,
-    pub mmk: Option<f64>,
-    pub mxn: Option<f64>,
-    pub myr: Option<f64>,
-    pub ngn: Option<f64>,
-    pub nok: Option<f64>,
-    pub nzd: Option<f64>,
-    pub php: Option<f64>,
-    pub pkr: Option<f64>,
-    pub pln: Option<f64>,
-    pub rub: Option<f64>,
-    pub sar: Option<f64>,
-    pub sek: Option<f64>,
-    pub sgd: Option<f64>,
-    pub thb: Option<f64>,
-    #[serde(rename = "try")]
-    pub try_field: Option<f64>,
-    pub twd: Option<f64>,
-    pub uah: Option<f64>,
-    pub usd: Option<f64>,
-    pub vef: Option<f64>,
-    pub vnd: Option<f64>,
-    pub xag: Option<f64>,
-    pub xau: Option<f64>,
-    pub xdr: Option<f64>,
-    pub xlm: Option<f64>,
-    pub xrp: Option<f64>,
-    pub yfi: Option<f64>,
-    pub zar: Option<f64>,
-    pub bits: Option<f64>,
-    pub link: Option<f64>,
-    pub sats: Option<f64>,
+impl<'de> Deserialize<'de> for MarketChartPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (timestamp, value) = <(f64, StringOrNumber)>::deserialize(deserializer)?;
+        let value = value.into_amount()?;
+
+        let timestamp = Utc
+            .timestamp_millis_opt(timestamp as i64)
+            .single()
+            .ok_or_else(|| de::Error::custom(format!("invalid timestamp {}", timestamp)))?;
+
+        Ok(MarketChartPoint { timestamp, value })
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Sparkline7D {
-    pub price: Vec<f64>,
+impl Serialize for MarketChartPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut point = serializer.serialize_tuple(2)?;
+        point.serialize_element(&(self.timestamp.timestamp_millis() as f64))?;
+        point.serialize_element(&self.value)?;
+        point.end()
+    }
 }
 
 // ---------------------------------------------
-//  /coins/{id}/history
+//  /coins/{id}/circulating_supply_chart
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct History {
-    pub id: String,
-    pub symbol: String,
-    pub name: String,
-    pub localization: Option<Localization>,
-    pub image: Image,
-    pub market_data: HistoryMarketData,
-    pub community_data: CommunityData,
-    pub developer_data: DeveloperData,
-    pub public_interest_stats: PublicInterestStats,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct CirculatingSupplyChart {
+    pub circulating_supply: Vec<MarketChartPoint>,
 }
 
+// ---------------------------------------------
+//  /coins/{id}/total_supply_chart
+// ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct HistoryMarketData {
-    pub current_price: CurrentPrice,
-    pub market_cap: MarketCap,
-    pub total_volume: TotalVolume,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TotalSupplyChart {
+    pub total_supply: Vec<MarketChartPoint>,
 }
 
 // ---------------------------------------------
-//  /coins/{id}/market_chart
+//  /coins/{id}/ohlc
 // ---------------------------------------------
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct MarketChart {
-    pub prices: Vec<Vec<f64>>,
-    pub market_caps: Vec<Vec<f64>>,
-    pub total_volumes: Vec<Vec<f64>>,
+/// Candle deserialized from CoinGecko's `[timestamp, open, high, low, close]` array
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ohlc {
+    pub timestamp: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl<'de> Deserialize<'de> for Ohlc {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let candle = Vec::<f64>::deserialize(deserializer)?;
+        if candle.len() < 5 {
+            return Err(de::Error::invalid_length(
+                candle.len(),
+                &"an array of timestamp, open, high, low and close",
+            ));
+        }
+
+        let timestamp = Utc
+            .timestamp_millis_opt(candle[0] as i64)
+            .single()
+            .ok_or_else(|| de::Error::custom(format!("invalid timestamp {}", candle[0])))?;
+
+        Ok(Ohlc {
+            timestamp,
+            open: candle[1],
+            high: candle[2],
+            low: candle[3],
+            close: candle[4],
+        })
+    }
+}
+
+impl Serialize for Ohlc {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut candle = serializer.serialize_tuple(5)?;
+        candle.serialize_element(&(self.timestamp.timestamp_millis() as f64))?;
+        candle.serialize_element(&self.open)?;
+        candle.serialize_element(&self.high)?;
+        candle.serialize_element(&self.low)?;
+        candle.serialize_element(&self.close)?;
+        candle.end()
+    }
 }
 
 // ---------------------------------------------
 //  /coins/{id}/contract/{contract_address}
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Contract {
     pub id: String,
     pub symbol: String,
     pub name: String,
     pub asset_platform_id: String,
     pub platforms: Option<HashMap<String, Option<String>>>,
+    /// Contract address and decimals of the token on each platform, keyed like `platforms`
+    #[serde(default)]
+    pub detail_platforms: HashMap<String, DetailPlatform>,
     pub block_time_in_minutes: i64,
     pub hashing_algorithm: ::serde_json::Value,
     pub categories: Vec<String>,
@@ -1617,6 +1440,7 @@ pub struct Contract {
 //  /coins/categories/list
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CategoryId {
     pub category_id: String,
     pub name: String,
@@ -1626,6 +1450,7 @@ pub struct CategoryId {
 //  /coins/categories
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Category {
     pub id: String,
     pub name: String,
@@ -2,7 +2,15 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Numeric type of prices and market values, `rust_decimal::Decimal` with the `decimal` feature and `f64` otherwise
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+/// Numeric type of prices and market values, `rust_decimal::Decimal` with the `decimal` feature and `f64` otherwise
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Localization {
     pub en: Option<String>,
     pub de: Option<String>,
@@ -29,6 +37,7 @@ pub struct Localization {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Image {
     pub thumb: Option<String>,
     pub small: Option<String>,
@@ -36,6 +45,7 @@ pub struct Image {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CurrentPrice {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
@@ -102,6 +112,7 @@ pub struct CurrentPrice {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct MarketCap {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
@@ -168,6 +179,7 @@ pub struct MarketCap {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct TotalVolume {
     pub aed: Option<f64>,
     pub ars: Option<f64>,
@@ -234,6 +246,7 @@ pub struct TotalVolume {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CommunityData {
     pub facebook_likes: Option<f64>,
     pub twitter_followers: Option<f64>,
@@ -248,6 +261,7 @@ pub struct CommunityData {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DeveloperData {
     pub forks: Option<f64>,
     pub stars: Option<f64>,
@@ -265,18 +279,21 @@ pub struct DeveloperData {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct PublicInterestStats {
     pub alexa_rank: Option<f64>,
     pub bing_matches: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CodeAdditionsDeletions4Weeks {
     pub additions: Option<f64>,
     pub deletions: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Links {
     pub homepage: Vec<String>,
     pub blockchain_site: Vec<String>,
@@ -292,18 +309,21 @@ pub struct Links {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ReposUrl {
     pub github: Vec<String>,
     pub bitbucket: Vec<Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Tickers {
     pub name: String,
     pub tickers: Vec<Ticker>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Ticker {
     pub base: String,
     pub target: String,
@@ -314,7 +334,8 @@ pub struct Ticker {
     pub converted_volume: ConvertedVolume,
     pub cost_to_move_up_usd: Option<f64>,
     pub cost_to_move_down_usd: Option<f64>,
-    pub trust_score: Option<String>,
+    pub trust_score: Option<TrustScore>,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub bid_ask_spread_percentage: Option<f64>,
     pub timestamp: Option<String>,
     pub last_traded_at: Option<String>,
@@ -327,7 +348,17 @@ pub struct Ticker {
     pub target_coin_id: Option<String>,
 }
 
+/// Trust score of a ticker, CoinGecko recommends considering how to display low-trust tickers
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustScore {
+    Green,
+    Yellow,
+    Red,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Market {
     pub name: String,
     pub identifier: String,
@@ -336,25 +367,35 @@ pub struct Market {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ConvertedLast {
+    #[serde(deserialize_with = "crate::de::float")]
     pub btc: f64,
+    #[serde(deserialize_with = "crate::de::float")]
     pub eth: f64,
+    #[serde(deserialize_with = "crate::de::float")]
     pub usd: f64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ConvertedVolume {
+    #[serde(deserialize_with = "crate::de::float")]
     pub btc: f64,
+    #[serde(deserialize_with = "crate::de::float")]
     pub eth: f64,
+    #[serde(deserialize_with = "crate::de::float")]
     pub usd: f64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct StatusUpdates {
     pub status_updates: Vec<StatusUpdate>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct StatusUpdate {
     pub description: Option<String>,
     pub category: Option<String>,
@@ -366,6 +407,7 @@ pub struct StatusUpdate {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Project {
     #[serde(rename = "type")]
     pub type_field: Option<String>,
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 //  /companies/public_treasury/{coin_id}
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CompaniesPublicTreasury {
     pub total_holdings: f64,
     pub total_value_usd: f64,
@@ -13,6 +14,7 @@ pub struct CompaniesPublicTreasury {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Company {
     pub name: String,
     pub symbol: String,
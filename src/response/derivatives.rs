@@ -1,5 +1,6 @@
 #![allow(missing_docs)]
-use super::common::{ConvertedLast, ConvertedVolume};
+use super::common::{Amount, ConvertedLast, ConvertedVolume};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // ---------------------------------------------
@@ -7,32 +8,47 @@ use serde::{Deserialize, Serialize};
 // ---------------------------------------------
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Derivative {
     pub market: String,
     pub symbol: String,
     pub index_id: String,
-    pub price: String,
+    #[serde(deserialize_with = "crate::de::amount")]
+    pub price: Amount,
     #[serde(rename = "price_percentage_change_24h")]
+    #[serde(deserialize_with = "crate::de::float")]
     pub price_percentage_change24_h: f64,
     pub contract_type: String,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub index: Option<f64>,
+    #[serde(deserialize_with = "crate::de::float")]
     pub basis: f64,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
     pub spread: Option<f64>,
+    #[serde(deserialize_with = "crate::de::float")]
     pub funding_rate: f64,
-    pub open_interest: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub open_interest: Option<Amount>,
     #[serde(rename = "volume_24h")]
-    pub volume24_h: f64,
-    pub last_traded_at: i64,
-    pub expired_at: Option<i64>,
+    #[serde(deserialize_with = "crate::de::amount")]
+    pub volume24_h: Amount,
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub last_traded_at: Option<DateTime<Utc>>,
+    /// Expiry of a futures contract, `None` for perpetuals
+    #[serde(default, with = "chrono::serde::ts_seconds_option")]
+    pub expired_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DerivativeExchange {
     pub name: String,
     pub id: String,
-    pub open_interest_btc: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub open_interest_btc: Option<Amount>,
     #[serde(rename = "trade_volume_24h_btc")]
-    pub trade_volume24_h_btc: Option<String>,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub trade_volume24_h_btc: Option<Amount>,
     pub number_of_perpetual_pairs: Option<i64>,
     pub number_of_futures_pairs: Option<i64>,
     pub image: Option<String>,
@@ -42,37 +58,51 @@ pub struct DerivativeExchange {
     pub url: Option<String>,
 }
 
+// ---------------------------------------------
+//  /derivatives/exchanges/{id}
+// ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct DerivativeExchangeData {
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct DerivativeExchangeDetail {
     pub name: String,
-    pub open_interest_btc: f64,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub open_interest_btc: Option<Amount>,
     #[serde(rename = "trade_volume_24h_btc")]
-    pub trade_volume24_h_btc: String,
-    pub number_of_perpetual_pairs: i64,
-    pub number_of_futures_pairs: i64,
-    pub image: String,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub trade_volume24_h_btc: Option<Amount>,
+    pub number_of_perpetual_pairs: Option<i64>,
+    pub number_of_futures_pairs: Option<i64>,
+    pub image: Option<String>,
     pub year_established: Option<i64>,
-    pub country: String,
-    pub description: String,
-    pub url: String,
+    pub country: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    #[serde(default)]
     pub tickers: Vec<DerivativeExchangeTicker>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DerivativeExchangeTicker {
     pub symbol: String,
     pub base: String,
     pub target: String,
-    pub trade_url: String,
+    pub trade_url: Option<String>,
     pub contract_type: String,
     pub last: f64,
+    #[serde(deserialize_with = "crate::de::float")]
     pub h24_percentage_change: f64,
     pub index: Option<f64>,
-    pub index_basis_percentage: f64,
-    pub bid_ask_spread: f64,
-    pub funding_rate: f64,
-    pub open_interest_usd: f64,
-    pub h24_volume: f64,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
+    pub index_basis_percentage: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
+    pub bid_ask_spread: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
+    pub funding_rate: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub open_interest_usd: Option<Amount>,
+    #[serde(deserialize_with = "crate::de::amount")]
+    pub h24_volume: Amount,
     pub converted_volume: ConvertedVolume,
     pub converted_last: ConvertedLast,
     pub last_traded: i64,
@@ -80,6 +110,7 @@ pub struct DerivativeExchangeTicker {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DerivativeExchangeId {
     pub name: String,
     pub id: String,
@@ -5,12 +5,14 @@ use serde::{Deserialize, Serialize};
 //  /events
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Events {
     pub data: Vec<Event>,
     pub count: i64,
     pub page: i64,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Event {
     #[serde(rename = "type")]
     pub type_field: String,
@@ -31,12 +33,14 @@ pub struct Event {
 //  /events/countries
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EventCountries {
     pub data: Vec<Country>,
     pub count: i64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Country {
     pub country: Option<String>,
     pub code: String,
@@ -46,6 +50,7 @@ pub struct Country {
 //  /events/types
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EventTypes {
     pub data: Vec<String>,
     pub count: i64,
@@ -6,14 +6,37 @@ use std::collections::HashMap;
 //  /exchange_rates
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ExchangeRates {
     pub rates: HashMap<String, ExchangeRateData>,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ExchangeRateData {
     pub name: String,
     pub unit: String,
     pub value: f64,
     #[serde(rename = "type")]
-    pub type_field: String,
+    pub type_field: RateType,
+}
+
+impl ExchangeRates {
+    /// Rate of a currency against BTC, e.g. `usd`
+    pub fn to(&self, currency: &str) -> Option<&ExchangeRateData> {
+        self.rates.get(&currency.to_lowercase())
+    }
+
+    /// Value of `amount` BTC in another currency
+    pub fn convert_btc(&self, amount: f64, currency: &str) -> Option<f64> {
+        self.to(currency).map(|rate| amount * rate.value)
+    }
+}
+
+/// Kind of currency of an exchange rate
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RateType {
+    Crypto,
+    Fiat,
+    Commodity,
 }
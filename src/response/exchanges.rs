@@ -1,10 +1,20 @@
 #![allow(missing_docs)]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "extra-fields")]
+use serde_json::Value;
+#[cfg(feature = "extra-fields")]
+use std::collections::HashMap;
+
+use super::common::Amount;
 
 // ---------------------------------------------
 //  /exchanges
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "strict-schema", not(feature = "extra-fields")),
+    serde(deny_unknown_fields)
+)]
 pub struct Exchange {
     pub id: String,
     pub name: String,
@@ -17,15 +27,22 @@ pub struct Exchange {
     pub trust_score: Option<i64>,
     pub trust_score_rank: Option<i64>,
     #[serde(rename = "trade_volume_24h_btc")]
-    pub trade_volume24_h_btc: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub trade_volume24_h_btc: Option<Amount>,
     #[serde(rename = "trade_volume_24h_btc_normalized")]
-    pub trade_volume24_h_btc_normalized: Option<f64>,
+    #[serde(default, deserialize_with = "crate::de::optional_amount")]
+    pub trade_volume24_h_btc_normalized: Option<Amount>,
+    /// Fields CoinGecko sent that this struct does not model yet
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten, deserialize_with = "crate::de::extra_fields")]
+    pub extra: HashMap<String, Value>,
 }
 
 // ---------------------------------------------
 //  /exchanges/list
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ExchangeId {
     pub id: String,
     pub name: String,
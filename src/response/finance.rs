@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 //  /finance_platforms
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct FinancePlatform {
     pub name: String,
     pub facts: String,
@@ -17,6 +18,7 @@ pub struct FinancePlatform {
 //  /finance_products
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct FinanceProduct {
     pub platform: String,
     pub identifier: String,
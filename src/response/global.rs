@@ -1,15 +1,22 @@
 #![allow(missing_docs)]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+
+use super::coins::MarketChartPoint;
+use super::common::Amount;
+use crate::format;
 
 // ---------------------------------------------
 //  /global
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Global {
     pub data: GlobalData,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GlobalData {
     pub active_cryptocurrencies: f64,
     pub upcoming_icos: f64,
@@ -24,21 +31,99 @@ pub struct GlobalData {
     pub updated_at: f64,
 }
 
+impl GlobalData {
+    /// Share of the total market cap in percent held by a coin, e.g. `btc` for Bitcoin dominance
+    ///
+    /// Keyed by symbol, CoinGecko only sends the top 10 coins by market cap.
+    pub fn dominance(&self, symbol: &str) -> Option<f64> {
+        self.market_cap_percentage
+            .get(&symbol.to_lowercase())
+            .copied()
+    }
+}
+
+impl fmt::Display for Global {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.data.fmt(f)
+    }
+}
+
+/// Counts, USD market cap and volume and the dominance of the top coins, one per line
+impl fmt::Display for GlobalData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Active cryptocurrencies: {}",
+            format::count(self.active_cryptocurrencies)
+        )?;
+        write!(f, "Markets: {}", format::count(self.markets))?;
+        if let Some(market_cap) = self.total_market_cap.get("usd") {
+            write!(
+                f,
+                "\nMarket cap: {} ({} 24h)",
+                format::money(*market_cap, "usd"),
+                format::percent(self.market_cap_change_percentage24_h_usd)
+            )?;
+        }
+        if let Some(volume) = self.total_volume.get("usd") {
+            write!(f, "\n24h volume: {}", format::money(*volume, "usd"))?;
+        }
+
+        let mut dominance: Vec<_> = self.market_cap_percentage.iter().collect();
+        dominance.sort_by(|a, b| b.1.total_cmp(a.1));
+        for (index, (symbol, percentage)) in dominance.into_iter().enumerate() {
+            let separator = if index == 0 { "\nDominance: " } else { ", " };
+            write!(
+                f,
+                "{}{} {:.2}%",
+                separator,
+                symbol.to_uppercase(),
+                percentage
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 // ---------------------------------------------
 //  /global/decentralized_finance_defi
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GlobalDefi {
     pub data: GlobalDefiData,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GlobalDefiData {
-    pub defi_market_cap: String,
-    pub eth_market_cap: String,
-    pub defi_to_eth_ratio: String,
+    #[serde(deserialize_with = "crate::de::amount")]
+    pub defi_market_cap: Amount,
+    #[serde(deserialize_with = "crate::de::amount")]
+    pub eth_market_cap: Amount,
+    #[serde(deserialize_with = "crate::de::float")]
+    pub defi_to_eth_ratio: f64,
     #[serde(rename = "trading_volume_24h")]
-    pub trading_volume24_h: String,
-    pub defi_dominance: String,
+    #[serde(deserialize_with = "crate::de::amount")]
+    pub trading_volume24_h: Amount,
+    /// Share of the total market cap held by DeFi coins, in percent
+    #[serde(deserialize_with = "crate::de::float")]
+    pub defi_dominance: f64,
     pub top_coin_name: String,
     pub top_coin_defi_dominance: f64,
 }
+
+// ---------------------------------------------
+//  /global/market_cap_chart
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct GlobalMarketCapChart {
+    pub market_cap_chart: GlobalMarketCapChartData,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct GlobalMarketCapChartData {
+    pub market_cap: Vec<MarketChartPoint>,
+    pub volume: Vec<MarketChartPoint>,
+}
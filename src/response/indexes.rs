@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 //  /indexes
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Index {
     pub name: String,
     pub id: String,
@@ -17,6 +18,7 @@ pub struct Index {
 //  /index/{market_id}/{id}
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct MarketIndex {
     pub name: String,
     pub market: String,
@@ -28,6 +30,7 @@ pub struct MarketIndex {
 //  /indexes/list
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct IndexId {
     pub name: String,
     pub id: String,
@@ -9,6 +9,8 @@ pub mod exchanges;
 pub mod finance;
 pub mod global;
 pub mod indexes;
+pub mod nfts;
 pub mod ping;
+pub mod search;
 pub mod simple;
 pub mod trending;
@@ -0,0 +1,140 @@
+#![allow(missing_docs)]
+use serde::{Deserialize, Serialize};
+
+use super::common::Amount;
+
+// ---------------------------------------------
+//  /nfts/list
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftId {
+    pub id: String,
+    pub contract_address: Option<String>,
+    pub name: String,
+    pub asset_platform_id: Option<String>,
+    pub symbol: Option<String>,
+}
+
+// ---------------------------------------------
+//  /nfts/markets
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftMarketItem {
+    pub id: String,
+    pub contract_address: Option<String>,
+    pub asset_platform_id: Option<String>,
+    pub name: String,
+    pub symbol: Option<String>,
+    pub image: Option<NftImage>,
+    pub description: Option<String>,
+    pub native_currency: Option<String>,
+    pub native_currency_symbol: Option<String>,
+    pub floor_price: NftPrice,
+    pub market_cap: NftPrice,
+    pub volume_24h: NftPrice,
+    pub floor_price_in_usd_24h_percentage_change: Option<f64>,
+    pub floor_price_24h_percentage_change: Option<NftPercentageChange>,
+    pub market_cap_24h_percentage_change: Option<NftPercentageChange>,
+    pub volume_24h_percentage_change: Option<NftPercentageChange>,
+    pub number_of_unique_addresses: Option<f64>,
+    pub number_of_unique_addresses_24h_percentage_change: Option<f64>,
+    pub volume_in_usd_24h_percentage_change: Option<f64>,
+    pub total_supply: Option<f64>,
+    pub one_day_sales: Option<f64>,
+    pub one_day_sales_24h_percentage_change: Option<f64>,
+    pub one_day_average_sale_price: Option<f64>,
+    pub one_day_average_sale_price_24h_percentage_change: Option<f64>,
+}
+
+// ---------------------------------------------
+//  /nfts/{id} and /nfts/{asset_platform_id}/contract/{contract_address}
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct Nft {
+    pub id: String,
+    pub contract_address: Option<String>,
+    pub asset_platform_id: Option<String>,
+    pub name: String,
+    pub symbol: Option<String>,
+    pub image: Option<NftImage>,
+    pub description: Option<String>,
+    pub native_currency: Option<String>,
+    pub native_currency_symbol: Option<String>,
+    pub floor_price: NftPrice,
+    pub market_cap: NftPrice,
+    pub volume_24h: NftPrice,
+    pub floor_price_in_usd_24h_percentage_change: Option<f64>,
+    pub floor_price_24h_percentage_change: Option<NftPercentageChange>,
+    pub market_cap_24h_percentage_change: Option<NftPercentageChange>,
+    pub volume_24h_percentage_change: Option<NftPercentageChange>,
+    pub number_of_unique_addresses: Option<f64>,
+    pub number_of_unique_addresses_24h_percentage_change: Option<f64>,
+    pub volume_in_usd_24h_percentage_change: Option<f64>,
+    pub total_supply: Option<f64>,
+    pub one_day_sales: Option<f64>,
+    pub one_day_sales_24h_percentage_change: Option<f64>,
+    pub one_day_average_sale_price: Option<f64>,
+    pub one_day_average_sale_price_24h_percentage_change: Option<f64>,
+    pub links: Option<NftLinks>,
+    pub floor_price_7d_percentage_change: Option<NftPercentageChange>,
+    pub floor_price_14d_percentage_change: Option<NftPercentageChange>,
+    pub floor_price_30d_percentage_change: Option<NftPercentageChange>,
+    pub floor_price_60d_percentage_change: Option<NftPercentageChange>,
+    pub floor_price_1y_percentage_change: Option<NftPercentageChange>,
+    #[serde(default)]
+    pub explorers: Vec<NftExplorer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftImage {
+    pub small: Option<String>,
+    pub small_2x: Option<String>,
+}
+
+/// Value in the native coin of the NFT's platform and in usd
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftPrice {
+    pub native_currency: Option<Amount>,
+    pub usd: Option<Amount>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftPercentageChange {
+    pub native_currency: Option<f64>,
+    pub usd: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftLinks {
+    pub homepage: Option<String>,
+    pub twitter: Option<String>,
+    pub discord: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftExplorer {
+    pub name: String,
+    pub link: String,
+}
+
+// ---------------------------------------------
+//  /nfts/{id}/market_chart
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct NftMarketChart {
+    pub floor_price_usd: Vec<Vec<Amount>>,
+    pub floor_price_native: Vec<Vec<Amount>>,
+    pub h24_volume_usd: Vec<Vec<Amount>>,
+    pub h24_volume_native: Vec<Vec<Amount>>,
+    pub market_cap_usd: Vec<Vec<Amount>>,
+    pub market_cap_native: Vec<Vec<Amount>>,
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 //  /ping
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct SimplePing {
     pub gecko_says: String,
 }
@@ -0,0 +1,50 @@
+#![allow(missing_docs)]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// ---------------------------------------------
+//  /search
+// ---------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct SearchResults {
+    pub coins: Vec<SearchCoin>,
+    pub exchanges: Vec<SearchExchange>,
+    pub icos: Vec<Value>,
+    pub categories: Vec<SearchCategory>,
+    pub nfts: Vec<SearchNft>,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct SearchCoin {
+    pub id: String,
+    pub name: String,
+    pub api_symbol: Option<String>,
+    pub symbol: String,
+    pub market_cap_rank: Option<i64>,
+    pub thumb: String,
+    pub large: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct SearchExchange {
+    pub id: String,
+    pub name: String,
+    pub market_type: String,
+    pub thumb: String,
+    pub large: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct SearchCategory {
+    pub id: Value,
+    pub name: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct SearchNft {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub thumb: String,
+}
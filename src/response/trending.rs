@@ -6,15 +6,25 @@ use serde_json::Value;
 //  /search/trending
 // ---------------------------------------------
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Trending {
     pub coins: Vec<TrendingCoin>,
+    #[serde(default)]
     pub exchanges: Vec<Value>,
+    /// Trending NFT collections, missing from older responses
+    #[serde(default)]
+    pub nfts: Vec<TrendingNft>,
+    /// Trending categories, missing from older responses
+    #[serde(default)]
+    pub categories: Vec<TrendingCategory>,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct TrendingCoin {
     pub item: TrendingCoinMarketData,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct TrendingCoinMarketData {
     pub id: String,
     pub coin_id: f64,
@@ -28,3 +38,33 @@ pub struct TrendingCoinMarketData {
     pub price_btc: f64,
     pub score: f64,
 }
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TrendingNft {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub thumb: Option<String>,
+    pub nft_contract_id: Option<i64>,
+    pub native_currency_symbol: Option<String>,
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
+    pub floor_price_in_native_currency: Option<f64>,
+    #[serde(rename = "floor_price_24h_percentage_change")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
+    pub floor_price24_h_percentage_change: Option<f64>,
+    /// Preformatted floor price, volume and sparkline url
+    pub data: Option<Value>,
+}
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TrendingCategory {
+    pub id: i64,
+    pub name: String,
+    pub slug: Option<String>,
+    #[serde(rename = "market_cap_1h_change")]
+    #[serde(default, deserialize_with = "crate::de::optional_float")]
+    pub market_cap1_h_change: Option<f64>,
+    pub coins_count: Option<i64>,
+    /// Market cap, volume and sparkline url of the category
+    pub data: Option<Value>,
+}
@@ -0,0 +1,93 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// When and how long to wait before retrying a rate limited request
+#[derive(Debug, Clone)]
+pub(crate) struct RetryPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) max_wait: Duration,
+    pub(crate) jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+            max_wait: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt + 1`, or `None` if the request should not be retried again
+    ///
+    /// A `Retry-After` sent by CoinGecko takes precedence over the exponential backoff.
+    /// `waited` is the time already spent waiting on earlier retries of the same request.
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        waited: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let delay = retry_after.unwrap_or_else(|| {
+            let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+
+            if self.jitter {
+                backoff / 2 + backoff.mul_f64(random_fraction() / 2.0)
+            } else {
+                backoff
+            }
+        });
+
+        // a huge `Retry-After` overflows the total, which is then over `max_wait` as well
+        if waited
+            .checked_add(delay)
+            .is_none_or(|total| total > self.max_wait)
+        {
+            return None;
+        }
+
+        Some(delay)
+    }
+}
+
+/// Reads a `Retry-After` header given in seconds
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Random value in `[0, 1)`, good enough to spread out retries
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Waits out a retry delay, with a browser timer on wasm32 where tokio's timer is unavailable
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await;
+}
+
+/// Waits out a retry delay, with a browser timer on wasm32 where tokio's timer is unavailable
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(delay: Duration) {
+    gloo_timers::future::sleep(delay).await;
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use reqwest::header::HeaderMap;
+
+use crate::error::CoinGeckoError;
+
+/// Headers and body of a successful response, or the error of a failed request
+pub(crate) type Response = Result<(HeaderMap, Vec<u8>), CoinGeckoError>;
+
+/// Identical requests in flight at the same time, so only the first is sent and the others share its response
+///
+/// Keyed by url without any API key.
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    flights: Mutex<HashMap<String, Arc<Flight>>>,
+}
+
+#[derive(Debug, Default)]
+struct Flight {
+    state: Mutex<FlightState>,
+}
+
+#[derive(Debug, Default)]
+struct FlightState {
+    outcome: Option<Outcome>,
+    waiting: Vec<Waker>,
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Response(HeaderMap, Vec<u8>),
+    Error(CoinGeckoError),
+    /// The first request was dropped, e.g. cancelled, or failed with an error that cannot be shared
+    Abandoned,
+}
+
+/// Role of a request joining the flights
+pub(crate) enum Join<'a> {
+    /// No identical request is in flight, this one is sent and shares its response
+    Leader(Leader<'a>),
+    /// An identical request is in flight, resolving to its response or to `None` if this one has to be sent itself
+    Follower(Follower),
+}
+
+impl SingleFlight {
+    pub(crate) fn join(&self, key: &str) -> Join<'_> {
+        let mut flights = self.flights.lock().unwrap();
+        if let Some(flight) = flights.get(key) {
+            return Join::Follower(Follower {
+                flight: flight.clone(),
+            });
+        }
+
+        let flight = Arc::new(Flight::default());
+        flights.insert(key.to_string(), flight.clone());
+
+        Join::Leader(Leader {
+            flights: self,
+            key: key.to_string(),
+            flight,
+            finished: false,
+        })
+    }
+}
+
+/// First of identical requests, handing its response to the others once finished or dropped
+pub(crate) struct Leader<'a> {
+    flights: &'a SingleFlight,
+    key: String,
+    flight: Arc<Flight>,
+    finished: bool,
+}
+
+impl Leader<'_> {
+    /// Shares the response with the requests waiting for it
+    pub(crate) fn finish(mut self, response: &Response) {
+        self.complete(|| match response {
+            Ok((headers, body)) => Outcome::Response(headers.clone(), body.clone()),
+            Err(e) => e.try_clone().map_or(Outcome::Abandoned, Outcome::Error),
+        });
+    }
+
+    fn complete(&mut self, outcome: impl FnOnce() -> Outcome) {
+        self.finished = true;
+
+        let mut flights = self.flights.flights.lock().unwrap();
+        if flights
+            .get(&self.key)
+            .is_some_and(|flight| Arc::ptr_eq(flight, &self.flight))
+        {
+            flights.remove(&self.key);
+        }
+        drop(flights);
+
+        // followers only hold the flight after taking it from the map, so none can join anymore
+        if Arc::strong_count(&self.flight) == 1 {
+            return;
+        }
+
+        let mut state = self.flight.state.lock().unwrap();
+        state.outcome = Some(outcome());
+        for waker in state.waiting.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.complete(|| Outcome::Abandoned);
+        }
+    }
+}
+
+/// Request waiting for an identical one in flight
+pub(crate) struct Follower {
+    flight: Arc<Flight>,
+}
+
+impl Future for Follower {
+    type Output = Option<Response>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.flight.state.lock().unwrap();
+
+        let shared = match &state.outcome {
+            Some(Outcome::Response(headers, body)) => Some(Ok((headers.clone(), body.clone()))),
+            Some(Outcome::Error(e)) => e.try_clone().map(Err),
+            Some(Outcome::Abandoned) => None,
+            None => {
+                if !state
+                    .waiting
+                    .iter()
+                    .any(|waker| waker.will_wake(cx.waker()))
+                {
+                    state.waiting.push(cx.waker().clone());
+                }
+                return Poll::Pending;
+            }
+        };
+
+        Poll::Ready(shared)
+    }
+}
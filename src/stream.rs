@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+
+use chrono::NaiveDate;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+
+use crate::client::{CoinGeckoClient, BATCH_CONCURRENCY};
+use crate::endpoints;
+use crate::error::CoinGeckoError;
+use crate::params::{AssetPlatformId, ChartDays, CoinId, OhlcDays, Precision, TickersOrder};
+use crate::response::{
+    coins::{CoinsItem, CoinsListItem, Contract, History, MarketChart, Ohlc},
+    common::Ticker,
+    exchanges::Exchange,
+    finance::{FinancePlatform, FinanceProduct},
+    indexes::Index,
+};
+
+/// Page size of `coin_tickers` and `exchange_tickers`, which cannot be configured
+const TICKERS_PER_PAGE: usize = 100;
+
+/// Fetches pages starting at 1 and yields their items, stopping after the first page with fewer than `per_page` items
+///
+/// The stream ends after yielding the first error.
+fn paginate<'a, T, F, Fut>(
+    per_page: usize,
+    fetch: F,
+) -> impl Stream<Item = Result<T, CoinGeckoError>> + 'a
+where
+    T: 'a,
+    F: Fn(i64) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>, CoinGeckoError>> + 'a,
+{
+    stream::try_unfold(Some(1), move |page| {
+        let items = page.map(|page| (page, fetch(page)));
+
+        async move {
+            let (page, items) = match items {
+                Some(items) => items,
+                None => return Ok(None),
+            };
+
+            let items = items.await?;
+            let next = if items.is_empty() || items.len() < per_page {
+                None
+            } else {
+                Some(page + 1)
+            };
+
+            Ok::<_, CoinGeckoError>(Some((stream::iter(items.into_iter().map(Ok)), next)))
+        }
+    })
+    .try_flatten()
+}
+
+/// Items of a JSON array body, parsed one at a time as they are iterated
+///
+/// The body must already be checked to be an array, see `CoinGeckoClient::get_array`.
+/// Iteration ends after yielding the first error.
+struct ArrayItems<T> {
+    body: Vec<u8>,
+    /// Url of the request, attached to decode errors
+    url: String,
+    pos: usize,
+    done: bool,
+    item: PhantomData<T>,
+}
+
+impl<T> ArrayItems<T> {
+    fn new(body: Vec<u8>, url: String) -> Self {
+        ArrayItems {
+            body,
+            url,
+            pos: 0,
+            done: false,
+            item: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for ArrayItems<T> {
+    type Item = Result<T, CoinGeckoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // skip the whitespace and the `[` or `,` before the next item
+        while let Some(byte) = self.body.get(self.pos) {
+            match byte {
+                b'[' | b',' => self.pos += 1,
+                byte if byte.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+
+        if matches!(self.body.get(self.pos), None | Some(b']')) {
+            self.done = true;
+            return None;
+        }
+
+        let mut items = serde_json::Deserializer::from_slice(&self.body[self.pos..]).into_iter();
+        match items.next()? {
+            Ok(item) => {
+                self.pos += items.byte_offset();
+                Some(Ok(item))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(CoinGeckoError::from(e).with_url(&self.url)))
+            }
+        }
+    }
+}
+
+impl CoinGeckoClient {
+    /// Stream of all exchanges, fetched `per_page` at a time as the stream is polled
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     use futures::TryStreamExt;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let exchanges: Result<Vec<_>, _> = client.exchanges_stream(250).try_collect().await;
+    /// }
+    /// ```
+    pub fn exchanges_stream(
+        &self,
+        per_page: i64,
+    ) -> impl Stream<Item = Result<Exchange, CoinGeckoError>> + '_ {
+        paginate(per_page as usize, move |page| {
+            self.exchanges(per_page, page)
+        })
+    }
+
+    /// Stream of all exchange tickers, fetched 100 at a time as the stream is polled
+    pub fn exchange_tickers_stream<'a, CoinId: AsRef<str>>(
+        &'a self,
+        id: &'a str,
+        coin_ids: Option<&'a [CoinId]>,
+        include_exchange_logo: bool,
+        order: TickersOrder,
+        depth: bool,
+    ) -> impl Stream<Item = Result<Ticker, CoinGeckoError>> + 'a {
+        paginate(TICKERS_PER_PAGE, move |page| async move {
+            let tickers = self
+                .exchange_tickers(id, coin_ids, include_exchange_logo, page, order, depth)
+                .await?;
+            Ok(tickers.tickers)
+        })
+    }
+
+    /// Stream of all tickers of a coin, fetched 100 at a time as the stream is polled
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::TickersOrder, CoinGeckoClient};
+    ///     use futures::TryStreamExt;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let tickers: Result<Vec<_>, _> = client
+    ///         .coin_tickers_stream::<&str>("bitcoin", None, false, TickersOrder::VolumeDesc, false)
+    ///         .try_collect()
+    ///         .await;
+    /// }
+    /// ```
+    pub fn coin_tickers_stream<'a, Ex: AsRef<str>>(
+        &'a self,
+        id: &'a str,
+        exchange_ids: Option<&'a [Ex]>,
+        include_exchange_logo: bool,
+        order: TickersOrder,
+        depth: bool,
+    ) -> impl Stream<Item = Result<Ticker, CoinGeckoError>> + 'a {
+        paginate(TICKERS_PER_PAGE, move |page| async move {
+            let tickers = self
+                .coin_tickers(id, exchange_ids, include_exchange_logo, page, order, depth)
+                .await?;
+            Ok(tickers.tickers)
+        })
+    }
+
+    /// Stream of all finance platforms, fetched `per_page` at a time as the stream is polled
+    pub fn finance_platforms_stream(
+        &self,
+        per_page: i64,
+    ) -> impl Stream<Item = Result<FinancePlatform, CoinGeckoError>> + '_ {
+        paginate(per_page as usize, move |page| {
+            self.finance_platforms(per_page, page)
+        })
+    }
+
+    /// Stream of all finance products, fetched `per_page` at a time as the stream is polled
+    pub fn finance_products_stream(
+        &self,
+        per_page: i64,
+    ) -> impl Stream<Item = Result<FinanceProduct, CoinGeckoError>> + '_ {
+        paginate(per_page as usize, move |page| {
+            self.finance_products(per_page, page)
+        })
+    }
+
+    /// Stream of all market indexes, fetched `per_page` at a time as the stream is polled
+    pub fn indexes_stream(
+        &self,
+        per_page: i64,
+    ) -> impl Stream<Item = Result<Index, CoinGeckoError>> + '_ {
+        paginate(per_page as usize, move |page| self.indexes(per_page, page))
+    }
+
+    /// Stream of all supported coins, parsed one at a time as the stream is polled rather than collected into a `Vec`
+    ///
+    /// The response body is still read in full before the first coin is yielded, but the coins themselves,
+    /// which take several times the memory of the body with platforms included, are never all held at once.
+    /// The stream ends after yielding the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     use futures::TryStreamExt;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let ethereum_tokens = client
+    ///         .coins_list_stream(true)
+    ///         .try_filter(|coin| {
+    ///             let on_ethereum = coin
+    ///                 .platforms
+    ///                 .as_ref()
+    ///                 .is_some_and(|platforms| platforms.contains_key("ethereum"));
+    ///             async move { on_ethereum }
+    ///         })
+    ///         .try_collect::<Vec<_>>()
+    ///         .await;
+    /// }
+    /// ```
+    pub fn coins_list_stream(
+        &self,
+        include_platform: bool,
+    ) -> impl Stream<Item = Result<CoinsListItem, CoinGeckoError>> + '_ {
+        stream::once(async move {
+            let endpoint = endpoints::coins_list(include_platform);
+            let body = self.get_array(&endpoint).await?;
+            Ok::<_, CoinGeckoError>(stream::iter(ArrayItems::<CoinsListItem>::new(
+                body,
+                self.get_url(&endpoint),
+            )))
+        })
+        .try_flatten()
+    }
+
+    /// Get current data for several coins, fetching up to `concurrency` coins at a time (defaults to 5)
+    ///
+    /// Returns the result of each coin in the order of `ids`, a coin failing does not fail the others.
+    /// Takes the same flags as `coin`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     let coins = client
+    ///         .coins_batch(&["bitcoin", "ethereum"], false, false, true, false, false, false, None)
+    ///         .await;
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn coins_batch<Id: AsRef<str>>(
+        &self,
+        ids: &[Id],
+        localization: bool,
+        tickers: bool,
+        market_data: bool,
+        community_data: bool,
+        developer_data: bool,
+        sparkline: bool,
+        concurrency: Option<usize>,
+    ) -> Vec<Result<CoinsItem, CoinGeckoError>> {
+        stream::iter(ids)
+            .map(|id| {
+                self.coin(
+                    id.as_ref(),
+                    localization,
+                    tickers,
+                    market_data,
+                    community_data,
+                    developer_data,
+                    sparkline,
+                )
+            })
+            .buffered(concurrency.unwrap_or(BATCH_CONCURRENCY).max(1))
+            .collect()
+            .await
+    }
+
+    /// Get coin info from several contract addresses on a platform, fetching up to `concurrency` addresses at a time (defaults to 5)
+    ///
+    /// Returns the result of each address in the order of `contract_addresses`, an address failing does not fail the others.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::AssetPlatformId, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///     let uniswap_contract = "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984";
+    ///     let chainlink_contract = "0x514910771af9ca656af840dff83e8264ecf986ca";
+    ///
+    ///     let contracts = client
+    ///         .contracts_batch(AssetPlatformId::Ethereum, &[uniswap_contract, chainlink_contract], None)
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn contracts_batch<Addr: AsRef<str>>(
+        &self,
+        id: impl Into<AssetPlatformId>,
+        contract_addresses: &[Addr],
+        concurrency: Option<usize>,
+    ) -> Vec<Result<Contract, CoinGeckoError>> {
+        let id = id.into();
+
+        stream::iter(contract_addresses)
+            .map(|address| self.contract(id.clone(), address.as_ref()))
+            .buffered(concurrency.unwrap_or(BATCH_CONCURRENCY).max(1))
+            .collect()
+            .await
+    }
+
+    /// Get historical data of a coin for every day from `from` to `to` inclusive, fetching up to `concurrency` days at a time (defaults to 5)
+    ///
+    /// Returns each day with its result sorted by date, a day failing does not fail the others.
+    /// Each day is a separate request, so set `requests_per_minute` on the builder when backfilling long ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use chrono::NaiveDate;
+    ///     use coingecko::CoinGeckoClient;
+    ///     let client = CoinGeckoClient::builder().requests_per_minute(30).build();
+    ///
+    ///     let from = NaiveDate::from_ymd_opt(2017, 12, 1).unwrap();
+    ///     let to = NaiveDate::from_ymd_opt(2017, 12, 7).unwrap();
+    ///
+    ///     for (date, history) in client.coin_history_range("bitcoin", from, to, false, None).await {
+    ///         if let Ok(history) = history {
+    ///             println!("{}: {:?}", date, history.market_data.current_price.usd);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn coin_history_range(
+        &self,
+        id: impl Into<CoinId>,
+        from: NaiveDate,
+        to: NaiveDate,
+        localization: bool,
+        concurrency: Option<usize>,
+    ) -> Vec<(NaiveDate, Result<History, CoinGeckoError>)> {
+        let id = id.into();
+        let id = id.as_ref();
+
+        stream::iter(from.iter_days().take_while(|date| *date <= to))
+            .map(|date| async move { (date, self.coin_history(id, date, localization).await) })
+            .buffered(concurrency.unwrap_or(BATCH_CONCURRENCY).max(1))
+            .collect()
+            .await
+    }
+
+    /// Get a coin's OHLC in several quote currencies, requesting each currency concurrently
+    ///
+    /// CoinGecko returns a single currency per request, so this sends one request per currency in parallel
+    /// instead of one after another. Keyed by the currencies as given, fails with the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     use coingecko::{params::OhlcDays, CoinGeckoClient};
+    ///     let client = CoinGeckoClient::default();
+    ///
+    ///     if let Ok(ohlc) = client.coin_ohlc_multi("ethereum", &["usd", "btc"], OhlcDays::OneDay).await {
+    ///         println!("{} candles in btc", ohlc["btc"].len());
+    ///     }
+    /// }
+    /// ```
+    pub async fn coin_ohlc_multi<Curr: AsRef<str>>(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currencies: &[Curr],
+        days: OhlcDays,
+    ) -> Result<HashMap<String, Vec<Ohlc>>, CoinGeckoError> {
+        let id = id.into();
+        let id = id.as_ref();
+
+        stream::iter(vs_currencies)
+            .map(|vs_currency| async move {
+                let ohlc = self.coin_ohlc(id, vs_currency.as_ref(), days).await?;
+                Ok((vs_currency.as_ref().to_string(), ohlc))
+            })
+            .buffer_unordered(BATCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Get a coin's market chart in several quote currencies, requesting each currency concurrently
+    ///
+    /// Takes the same parameters as `coin_market_chart`, keyed by the currencies as given, fails with the first error.
+    pub async fn coin_market_chart_multi<Curr: AsRef<str>>(
+        &self,
+        id: impl Into<CoinId>,
+        vs_currencies: &[Curr],
+        days: impl Into<ChartDays>,
+        use_daily_interval: bool,
+        precision: Option<Precision>,
+    ) -> Result<HashMap<String, MarketChart>, CoinGeckoError> {
+        let id = id.into();
+        let id = id.as_ref();
+        let days = days.into();
+
+        stream::iter(vs_currencies)
+            .map(|vs_currency| async move {
+                let chart = self
+                    .coin_market_chart(
+                        id,
+                        vs_currency.as_ref(),
+                        days,
+                        use_daily_interval,
+                        precision,
+                    )
+                    .await?;
+                Ok((vs_currency.as_ref().to_string(), chart))
+            })
+            .buffer_unordered(BATCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Token bucket spacing out requests to stay below a number of requests per minute
+///
+/// Holds a single token so requests are sent at an even pace. Callers reserve a token up front,
+/// which may leave the bucket in debt, so concurrent requests queue up without holding the lock while waiting.
+/// Uses chrono's clock, which unlike `std::time::Instant` is also available on wasm32.
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    interval: Duration,
+    next: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl Throttle {
+    /// Throttle allowing `requests_per_minute`, or `None` for no limit when zero
+    pub(crate) fn per_minute(requests_per_minute: u32) -> Option<Self> {
+        if requests_per_minute == 0 {
+            return None;
+        }
+
+        Some(Throttle {
+            interval: Duration::from_secs(60) / requests_per_minute,
+            next: Mutex::new(None),
+        })
+    }
+
+    /// Reserves the next request slot, returning how long to wait before sending the request
+    pub(crate) fn reserve(&self) -> Duration {
+        self.reserve_at(Utc::now())
+    }
+
+    pub(crate) fn reserve_at(&self, now: DateTime<Utc>) -> Duration {
+        let mut next = self.next.lock().unwrap();
+
+        let slot = match *next {
+            Some(next) if next > now => next,
+            _ => now,
+        };
+        *next = Some(slot + chrono::Duration::from_std(self.interval).unwrap());
+
+        (slot - now).to_std().unwrap_or_default()
+    }
+}
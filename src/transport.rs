@@ -0,0 +1,170 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::error::CoinGeckoError;
+#[cfg(feature = "vcr")]
+pub use crate::vcr::{RecordingTransport, ReplayTransport};
+
+/// Future returned by [`HttpTransport::get`]
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, CoinGeckoError>> + Send + 'a>>;
+
+/// Future returned by [`HttpTransport::get`], not `Send` as requests are sent with the browser's fetch API
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HttpResponse, CoinGeckoError>> + 'a>>;
+
+/// Raw response of a GET request, before status handling and deserialization
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// Response status
+    pub status: StatusCode,
+    /// Response headers, read for rate limits and `Retry-After`
+    pub headers: HeaderMap,
+    /// Response body, deserialized from JSON on success
+    pub body: Vec<u8>,
+}
+
+/// Sends the HTTP requests of a [`crate::CoinGeckoClient`]
+///
+/// Defaults to [`ReqwestTransport`]. Set another one with `CoinGeckoClientBuilder::transport`,
+/// e.g. to answer requests with canned JSON in tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use coingecko::transport::{HttpResponse, HttpTransport, TransportFuture};
+/// use coingecko::CoinGeckoClient;
+/// use reqwest::{header::HeaderMap, StatusCode};
+///
+/// struct FakeTransport;
+///
+/// impl HttpTransport for FakeTransport {
+///     fn get<'a>(&'a self, url: &'a str, _headers: HeaderMap) -> TransportFuture<'a> {
+///         assert!(url.ends_with("/ping"));
+///         Box::pin(async {
+///             Ok(HttpResponse {
+///                 status: StatusCode::OK,
+///                 headers: HeaderMap::new(),
+///                 body: br#"{"gecko_says":"(V3) To the Moon!"}"#.to_vec(),
+///             })
+///         })
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = CoinGeckoClient::builder().transport(FakeTransport).build();
+///
+///     let ping = client.ping().await.unwrap();
+///     assert_eq!(ping.gecko_says, "(V3) To the Moon!");
+/// }
+/// ```
+pub trait HttpTransport: Send + Sync {
+    /// Sends a GET request to `url` with the given headers
+    ///
+    /// Responses with any status should be returned as `Ok`, errors are for requests that could not be completed.
+    fn get<'a>(&'a self, url: &'a str, headers: HeaderMap) -> TransportFuture<'a>;
+}
+
+/// Shares a transport, e.g. to inspect a fake transport after handing it to a client
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn get<'a>(&'a self, url: &'a str, headers: HeaderMap) -> TransportFuture<'a> {
+        (**self).get(url, headers)
+    }
+}
+
+impl fmt::Debug for dyn HttpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HttpTransport")
+    }
+}
+
+/// Default transport sending requests with a `reqwest::Client`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    max_response_bytes: Option<u64>,
+}
+
+impl ReqwestTransport {
+    /// Wraps a configured `reqwest::Client`
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport {
+            client,
+            max_response_bytes: None,
+        }
+    }
+
+    /// Fails responses with a body over `limit` bytes with `CoinGeckoError::ResponseTooLarge`
+    ///
+    /// A larger `Content-Length` fails before the body is read, otherwise reading stops once the limit is exceeded.
+    /// On wasm32 the body is only checked after it has been read.
+    pub fn with_max_response_bytes(mut self, limit: u64) -> Self {
+        self.max_response_bytes = Some(limit);
+        self
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn get<'a>(&'a self, url: &'a str, headers: HeaderMap) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let response = self.client.get(url).headers(headers).send().await?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = match self.max_response_bytes {
+                Some(limit) => read_limited(response, limit).await?,
+                None => response.bytes().await?.to_vec(),
+            };
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// Reads a response body, failing as soon as it grows over `limit` bytes
+#[cfg(not(target_arch = "wasm32"))]
+async fn read_limited(
+    mut response: reqwest::Response,
+    limit: u64,
+) -> Result<Vec<u8>, CoinGeckoError> {
+    check_response_size(response.content_length().unwrap_or(0), Some(limit))?;
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        check_response_size((body.len() + chunk.len()) as u64, Some(limit))?;
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Reads a response body, failing if it is over `limit` bytes, as the fetch API cannot be read in chunks
+#[cfg(target_arch = "wasm32")]
+async fn read_limited(response: reqwest::Response, limit: u64) -> Result<Vec<u8>, CoinGeckoError> {
+    check_response_size(response.content_length().unwrap_or(0), Some(limit))?;
+
+    let body = response.bytes().await?.to_vec();
+    check_response_size(body.len() as u64, Some(limit))?;
+
+    Ok(body)
+}
+
+/// Fails with `CoinGeckoError::ResponseTooLarge` if `size` exceeds `limit`
+pub(crate) fn check_response_size(size: u64, limit: Option<u64>) -> Result<(), CoinGeckoError> {
+    match limit {
+        Some(limit) if size > limit => Err(CoinGeckoError::ResponseTooLarge { limit }),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::error::CoinGeckoError;
+use crate::file_name::request_path;
+use crate::transport::{HttpResponse, HttpTransport, TransportFuture};
+
+/// Sends requests with another transport and writes the body of every successful response to a directory
+///
+/// Each body is stored as JSON in a file named after the path and query params of the request,
+/// so fixtures recorded against one host replay against any other, see [`ReplayTransport`].
+/// Responses with any other status are passed on without being recorded.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn HttpTransport>,
+    dir: PathBuf,
+}
+
+impl RecordingTransport {
+    /// Records the responses received by `inner` to `dir`, which is created if missing
+    pub fn new(dir: impl Into<PathBuf>, inner: impl HttpTransport + 'static) -> Self {
+        RecordingTransport {
+            inner: Arc::new(inner),
+            dir: dir.into(),
+        }
+    }
+}
+
+impl HttpTransport for RecordingTransport {
+    fn get<'a>(&'a self, url: &'a str, headers: HeaderMap) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let response = self.inner.get(url, headers).await?;
+
+            if response.status.is_success() {
+                let path = request_path(&self.dir, url);
+                fs::create_dir_all(&self.dir)
+                    .and_then(|_| fs::write(&path, &response.body))
+                    .map_err(|source| CoinGeckoError::Fixture { path, source })?;
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Answers requests with the response bodies written by a [`RecordingTransport`], without any network access
+///
+/// Requests without a recorded response fail with `CoinGeckoError::Fixture`.
+#[derive(Debug, Clone)]
+pub struct ReplayTransport {
+    dir: PathBuf,
+}
+
+impl ReplayTransport {
+    /// Replays the responses recorded to `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ReplayTransport { dir: dir.into() }
+    }
+}
+
+impl HttpTransport for ReplayTransport {
+    fn get<'a>(&'a self, url: &'a str, _headers: HeaderMap) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let path = request_path(&self.dir, url);
+            let body =
+                fs::read(&path).map_err(|source| CoinGeckoError::Fixture { path, source })?;
+
+            Ok(HttpResponse {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body,
+            })
+        })
+    }
+}